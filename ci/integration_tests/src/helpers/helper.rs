@@ -8,8 +8,8 @@ use cw_core::{
 };
 use cw_utils::Duration;
 use voting::{
-    deposit::DepositInfo, deposit::DepositToken, threshold::PercentageThreshold,
-    threshold::Threshold,
+    curve::VotingPowerCurve, deposit::DepositInfo, deposit::DepositRefundPolicy,
+    deposit::DepositToken, threshold::PercentageThreshold, threshold::Threshold,
 };
 
 #[derive(Debug)]
@@ -72,9 +72,13 @@ pub fn create_dao(
                 deposit_info: Some(DepositInfo {
                     token: DepositToken::VotingModuleToken {},
                     deposit: Uint128::new(1000000000),
-                    refund_failed_proposals: true,
+                    refund_policy: DepositRefundPolicy::Always {},
                 }),
                 close_proposal_on_execution_failure: false,
+                voting_power_curve: VotingPowerCurve::Linear {},
+                timelock: None,
+                vetoer: None,
+                pre_propose: None,
             })?,
             admin: Admin::CoreContract {},
             label: "DAO DAO Proposal Module".to_string(),
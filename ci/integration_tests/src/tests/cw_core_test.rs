@@ -8,7 +8,11 @@ use cw20_stake::msg::{StakedValueResponse, TotalValueResponse};
 use cw_core::query::{GetItemResponse, PauseInfoResponse};
 use cw_utils::Duration;
 use test_context::test_context;
-use voting::{deposit::CheckedDepositInfo, threshold::PercentageThreshold, threshold::Threshold};
+use voting::{
+    deposit::{CheckedDepositInfo, CheckedDepositToken, DepositRefundPolicy},
+    threshold::PercentageThreshold,
+    threshold::Threshold,
+};
 
 // #### ExecuteMsg #####
 
@@ -354,9 +358,9 @@ fn instantiate_with_admin(chain: &mut Chain) {
     assert_eq!(
         config_res.deposit_info,
         Some(CheckedDepositInfo {
-            token: Addr::unchecked(token_addr),
+            token: CheckedDepositToken::Cw20(Addr::unchecked(token_addr)),
             deposit: Uint128::new(1000000000),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         })
     );
     assert_eq!(
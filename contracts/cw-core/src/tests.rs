@@ -1110,7 +1110,7 @@ fn test_admin_permissions() {
     );
 
     // DAO unpauses after 10 blocks
-    app.update_block(|mut block| block.height += 11);
+    app.update_block(|block| block.height += 11);
 
     // Admin can nominate a new admin.
     let res = app.execute_contract(
@@ -1376,7 +1376,7 @@ fn test_admin_nomination() {
     );
 
     // DAO unpauses after 10 blocks
-    app.update_block(|mut block| block.height += 11);
+    app.update_block(|block| block.height += 11);
 
     // Remove the admin.
     app.execute_contract(
@@ -2300,7 +2300,7 @@ fn test_pause() {
 
     assert!(matches!(err, ContractError::Paused { .. }));
 
-    app.update_block(|mut block| block.height += 9);
+    app.update_block(|block| block.height += 9);
 
     // Still not unpaused.
     let err: ContractError = app
@@ -2326,7 +2326,7 @@ fn test_pause() {
 
     assert!(matches!(err, ContractError::Paused { .. }));
 
-    app.update_block(|mut block| block.height += 1);
+    app.update_block(|block| block.height += 1);
 
     let paused: PauseInfoResponse = app
         .wrap()
@@ -2823,7 +2823,7 @@ fn test_created_timestamp_set() {
     };
 
     let timestamp = Timestamp::from_seconds(300_000_000);
-    app.update_block(|mut block| block.time = timestamp);
+    app.update_block(|block| block.time = timestamp);
     let instantiate = InstantiateMsg {
         dao_uri: None,
         admin: None,
@@ -0,0 +1,311 @@
+use crate::error::ContractError;
+use crate::msg::{
+    DelegationResponse, DelegatorListResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{Delegation, DAO, DELEGATIONS, DELEGATORS, MAX_DELEGATORS, VOTING_MODULE};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cw-delegation-voting";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u64 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let voting_module = deps.api.addr_validate(&msg.voting_module)?;
+    // Make sure the wrapped module is a voting module.
+    let _total: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        &voting_module,
+        &cw_core_interface::voting::Query::TotalPowerAtHeight { height: None },
+    )?;
+
+    VOTING_MODULE.save(deps.storage, &voting_module)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("voting_module", voting_module))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Delegate { delegate } => execute_delegate(deps, env, info, delegate),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
+    }
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+    let height = env.block.height;
+    let power = wrapped_voting_power(deps.as_ref(), &info.sender, height)?;
+    if power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        remove_delegator(deps.storage, &previous.delegate, &info.sender, height)?;
+    }
+    DELEGATIONS.save(
+        deps.storage,
+        &info.sender,
+        &Delegation {
+            delegate: delegate.clone(),
+        },
+        height,
+    )?;
+    let mut delegators = DELEGATORS
+        .may_load(deps.storage, &delegate)?
+        .unwrap_or_default();
+    if delegators.len() >= MAX_DELEGATORS {
+        return Err(ContractError::TooManyDelegators {
+            max: MAX_DELEGATORS,
+        });
+    }
+    if let Err(index) = delegators.binary_search(&info.sender) {
+        delegators.insert(index, info.sender.clone());
+    }
+    DELEGATORS.save(deps.storage, &delegate, &delegators, height)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("power", power))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegation = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotDelegating {})?;
+    DELEGATIONS.remove(deps.storage, &info.sender, env.block.height)?;
+    remove_delegator(
+        deps.storage,
+        &delegation.delegate,
+        &info.sender,
+        env.block.height,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender))
+}
+
+/// Removes DELEGATOR from the delegators of DELEGATE as of HEIGHT.
+fn remove_delegator(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    delegator: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    let mut delegators = DELEGATORS.may_load(storage, delegate)?.unwrap_or_default();
+    delegators.retain(|current| current != delegator);
+    if delegators.is_empty() {
+        DELEGATORS.remove(storage, delegate, height)
+    } else {
+        DELEGATORS.save(storage, delegate, &delegators, height)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::TokenContract {} => query_token_contract(deps),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::VotingModule {} => to_binary(&VOTING_MODULE.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Delegation { delegator, height } => {
+            query_delegation(deps, env, delegator, height)
+        }
+        QueryMsg::ListDelegators {
+            delegate,
+            height,
+            start_after,
+            limit,
+        } => query_list_delegators(deps, delegate, height, start_after, limit),
+    }
+}
+
+/// Gets the voting power of ADDRESS at HEIGHT in the wrapped voting
+/// module.
+fn wrapped_voting_power(deps: Deps, address: &Addr, height: u64) -> StdResult<Uint128> {
+    let voting_module = VOTING_MODULE.load(deps.storage)?;
+    let response: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        voting_module,
+        &cw_core_interface::voting::Query::VotingPowerAtHeight {
+            address: address.to_string(),
+            height: Some(height),
+        },
+    )?;
+    Ok(response.power)
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+
+    let mut power = match delegation(deps.storage, &address, Some(height))? {
+        Some(_) => Uint128::zero(),
+        None => wrapped_voting_power(deps, &address, height)?,
+    };
+    for delegator in delegators(deps.storage, &address, Some(height))? {
+        power = power.checked_add(wrapped_voting_power(deps, &delegator, height)?)?;
+    }
+
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let voting_module = VOTING_MODULE.load(deps.storage)?;
+    let height = height.unwrap_or(env.block.height);
+    let response: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        voting_module,
+        &cw_core_interface::voting::Query::TotalPowerAtHeight {
+            height: Some(height),
+        },
+    )?;
+    to_binary(&response)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+pub fn query_token_contract(deps: Deps) -> StdResult<Binary> {
+    let voting_module = VOTING_MODULE.load(deps.storage)?;
+    let token: Addr = deps.querier.query_wasm_smart(
+        voting_module,
+        &cw_core_interface::voting::Query::TokenContract {},
+    )?;
+    to_binary(&token)
+}
+
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    let voting_module = VOTING_MODULE.load(deps.storage)?;
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let response: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true });
+    to_binary(&response)
+}
+
+pub fn query_delegation(
+    deps: Deps,
+    env: Env,
+    delegator: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let delegation = delegation(deps.storage, &delegator, height)?;
+    to_binary(&match delegation {
+        Some(Delegation { delegate }) => DelegationResponse {
+            delegate: Some(delegate),
+            power: wrapped_voting_power(deps, &delegator, height.unwrap_or(env.block.height))?,
+        },
+        None => DelegationResponse {
+            delegate: None,
+            power: Uint128::zero(),
+        },
+    })
+}
+
+pub fn query_list_delegators(
+    deps: Deps,
+    delegate: String,
+    height: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let delegators = delegators(deps.storage, &delegate, height)?
+        .into_iter()
+        .filter(|delegator| start_after.as_ref().is_none_or(|start| delegator > start))
+        .take(limit as usize)
+        .collect();
+
+    to_binary(&DelegatorListResponse { delegators })
+}
+
+/// Returns DELEGATOR's delegation at HEIGHT, or currently if HEIGHT
+/// is None.
+fn delegation(
+    storage: &dyn Storage,
+    delegator: &Addr,
+    height: Option<u64>,
+) -> StdResult<Option<Delegation>> {
+    match height {
+        Some(height) => DELEGATIONS.may_load_at_height(storage, delegator, height),
+        None => DELEGATIONS.may_load(storage, delegator),
+    }
+}
+
+/// Returns the addresses that had delegated their voting power to
+/// DELEGATE at HEIGHT, or currently if HEIGHT is None, in ascending
+/// order.
+fn delegators(storage: &dyn Storage, delegate: &Addr, height: Option<u64>) -> StdResult<Vec<Addr>> {
+    let delegators = match height {
+        Some(height) => DELEGATORS.may_load_at_height(storage, delegate, height)?,
+        None => DELEGATORS.may_load(storage, delegate)?,
+    };
+    Ok(delegators.unwrap_or_default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Set contract to version to latest
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
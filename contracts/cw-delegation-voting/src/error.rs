@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("An address may not delegate its voting power to itself")]
+    SelfDelegation {},
+
+    #[error("Sender has not delegated their voting power")]
+    NotDelegating {},
+
+    #[error("Sender has no voting power to delegate")]
+    NoVotingPower {},
+
+    #[error("Delegate already has the maximum number of delegators ({max})")]
+    TooManyDelegators { max: usize },
+}
@@ -0,0 +1,74 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_core_macros::{active_query, token_query, voting_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The voting module to wrap. Voting power is read from this
+    /// module.
+    pub voting_module: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Delegates the sender's voting power to DELEGATE, replacing
+    /// any existing delegation. At any height the delegate votes with
+    /// the sender's voting power in the wrapped module at that
+    /// height. The sender must have voting power and a delegate may
+    /// have at most `state::MAX_DELEGATORS` delegators.
+    Delegate { delegate: String },
+    /// Removes the sender's delegation.
+    Undelegate {},
+}
+
+#[token_query]
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Gets the wrapped voting module. Returns `Addr`.
+    VotingModule {},
+    Dao {},
+    /// Gets the address that DELEGATOR had delegated their voting
+    /// power to at HEIGHT, or currently if HEIGHT is not
+    /// set. Returns `DelegationResponse`.
+    Delegation {
+        delegator: String,
+        height: Option<u64>,
+    },
+    /// Lists the addresses that had delegated their voting power to
+    /// DELEGATE at HEIGHT, or currently if HEIGHT is not set. Returns
+    /// `DelegatorListResponse`.
+    ListDelegators {
+        delegate: String,
+        height: Option<u64>,
+        /// The delegator to start listing delegators after. Ordering
+        /// is done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of delegators to return. If no limit is
+        /// set a max of 30 will be returned.
+        limit: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DelegationResponse {
+    /// The address the delegator had delegated their voting power
+    /// to. None if they had not delegated their voting power.
+    pub delegate: Option<Addr>,
+    /// The delegator's voting power in the wrapped module at the
+    /// queried height, which is counted with their delegate's voting
+    /// power. Zero if they had not delegated their voting power.
+    pub power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DelegatorListResponse {
+    pub delegators: Vec<Addr>,
+}
@@ -0,0 +1,38 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, SnapshotMap, Strategy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const DAO: Item<Addr> = Item::new("dao");
+pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
+
+/// An address' delegation of its voting power.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Delegation {
+    /// The address the voting power is delegated to.
+    pub delegate: Addr,
+}
+
+/// Maps delegators to their delegation.
+pub const DELEGATIONS: SnapshotMap<&Addr, Delegation> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum number of addresses that may delegate their voting
+/// power to a single delegate. Bounds the number of queries made to
+/// the wrapped module when a delegate's voting power is queried.
+pub const MAX_DELEGATORS: usize = 50;
+
+/// The addresses that have delegated their voting power to each
+/// delegate, in ascending order. A delegate's voting power at a
+/// height includes the wrapped module voting power its delegators
+/// had at that height.
+pub const DELEGATORS: SnapshotMap<&Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegator_lists",
+    "delegator_lists__checkpoints",
+    "delegator_lists__changelog",
+    Strategy::EveryBlock,
+);
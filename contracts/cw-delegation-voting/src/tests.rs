@@ -0,0 +1,426 @@
+use cosmwasm_std::{
+    coins,
+    testing::{mock_dependencies, mock_env},
+    Addr, Empty, Uint128,
+};
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+
+use crate::{
+    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
+    msg::{
+        DelegationResponse, DelegatorListResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    },
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_voting::contract::execute,
+        cw4_voting::contract::instantiate,
+        cw4_voting::contract::query,
+    )
+    .with_reply(cw4_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn native_staked_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_native_staked_balance_voting::contract::execute,
+        cw_native_staked_balance_voting::contract::instantiate,
+        cw_native_staked_balance_voting::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn delegation_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+/// Instantiates a cw4-voting module with ADDR1, ADDR2, and ADDR3
+/// having weights 1, 2, and 3 and wraps it in a delegation voting
+/// module.
+fn setup_test_case(app: &mut App) -> Addr {
+    let cw4_id = app.store_code(cw4_contract());
+    let cw4_voting_id = app.store_code(cw4_voting_contract());
+    let voting_id = app.store_code(delegation_voting_contract());
+
+    let members = vec![
+        cw4::Member {
+            addr: ADDR1.to_string(),
+            weight: 1,
+        },
+        cw4::Member {
+            addr: ADDR2.to_string(),
+            weight: 2,
+        },
+        cw4::Member {
+            addr: ADDR3.to_string(),
+            weight: 3,
+        },
+    ];
+    let cw4_voting = app
+        .instantiate_contract(
+            cw4_voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_voting::msg::InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members: members,
+            },
+            &[],
+            "cw4 voting module",
+            None,
+        )
+        .unwrap();
+
+    let voting = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                voting_module: cw4_voting.to_string(),
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+    voting
+}
+
+fn delegate(app: &mut App, voting: &Addr, delegator: &str, delegate: &str) {
+    app.execute_contract(
+        Addr::unchecked(delegator),
+        voting.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: delegate.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn voting_power(app: &App, voting: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let response: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    response.power
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    // Only voting modules may be wrapped.
+    let cw4_voting: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::VotingModule {})
+        .unwrap();
+    let group: Addr = app
+        .wrap()
+        .query_wasm_smart(cw4_voting, &cw4_voting::msg::QueryMsg::GroupContract {})
+        .unwrap();
+    let voting_id = app.store_code(delegation_voting_contract());
+    app.instantiate_contract(
+        voting_id,
+        Addr::unchecked(DAO_ADDR),
+        &InstantiateMsg {
+            voting_module: group.to_string(),
+        },
+        &[],
+        "voting module",
+        None,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_delegation() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+    let before_delegation = app.block_info().height;
+
+    delegate(&mut app, &voting, ADDR1, ADDR3);
+    // Delegations apply from the next block.
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(3));
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::zero());
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(2));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(4));
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, Some(before_delegation)),
+        Uint128::new(1)
+    );
+    assert_eq!(
+        voting_power(&app, &voting, ADDR3, Some(before_delegation)),
+        Uint128::new(3)
+    );
+
+    // Total power is unchanged.
+    let total: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(6));
+
+    // Delegation is not transitive.
+    delegate(&mut app, &voting, ADDR3, ADDR2);
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(5));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(1));
+
+    let delegators: DelegatorListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::ListDelegators {
+                delegate: ADDR3.to_string(),
+                height: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(delegators.delegators, vec![Addr::unchecked(ADDR1)]);
+
+    // Redelegating moves voting power to the new delegate.
+    delegate(&mut app, &voting, ADDR1, ADDR2);
+    let after_redelegation = app.block_info().height + 1;
+    app.execute_contract(
+        Addr::unchecked(ADDR3),
+        voting.clone(),
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(3));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(3));
+
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::Delegation {
+                delegator: ADDR1.to_string(),
+                height: Some(after_redelegation - 1),
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked(ADDR3)));
+    assert_eq!(delegation.power, Uint128::new(1));
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::Delegation {
+                delegator: ADDR1.to_string(),
+                height: Some(after_redelegation),
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked(ADDR2)));
+}
+
+#[test]
+fn test_delegator_unstakes() {
+    let mut app = App::default();
+    let staked_voting_id = app.store_code(native_staked_voting_contract());
+    let voting_id = app.store_code(delegation_voting_contract());
+    let staked_voting = app
+        .instantiate_contract(
+            staked_voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw_native_staked_balance_voting::msg::InstantiateMsg {
+                owner: None,
+                manager: None,
+                denom: "ujuno".to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "staked voting module",
+            None,
+        )
+        .unwrap();
+    let voting = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                voting_module: staked_voting.to_string(),
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+    for (addr, amount) in [(ADDR1, 10), (ADDR2, 20)] {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: addr.to_string(),
+            amount: coins(amount, "ujuno"),
+        }))
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(addr),
+            staked_voting.clone(),
+            &cw_native_staked_balance_voting::msg::ExecuteMsg::Stake {},
+            &coins(amount, "ujuno"),
+        )
+        .unwrap();
+    }
+    app.update_block(next_block);
+
+    delegate(&mut app, &voting, ADDR1, ADDR2);
+    app.update_block(next_block);
+    let before_unstake = app.block_info().height;
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(30));
+
+    // Power unstaked after delegating stops counting for the
+    // delegate, so no voting power is counted twice.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        staked_voting,
+        &cw_native_staked_balance_voting::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(10),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(20));
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(before_unstake)),
+        Uint128::new(30)
+    );
+    let total: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(20));
+
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::Delegation {
+                delegator: ADDR1.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked(ADDR2)));
+    assert_eq!(delegation.power, Uint128::zero());
+}
+
+#[test]
+fn test_delegation_errors() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: ADDR1.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::SelfDelegation {}));
+
+    // Addresses without voting power may not delegate.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("stranger"),
+            voting.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: ADDR1.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoVotingPower {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting,
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotDelegating {}));
+}
+
+#[test]
+fn test_passthrough_queries() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    // cw4-voting does not implement `IsActive` so it is active.
+    let active: IsActiveResponse = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::IsActive {})
+        .unwrap();
+    assert!(active.active);
+
+    // cw4-voting does not have a token.
+    app.wrap()
+        .query_wasm_smart::<Addr>(&voting, &QueryMsg::TokenContract {})
+        .unwrap_err();
+}
+
+#[test]
+pub fn test_migrate_update_version() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(version.contract, CONTRACT_NAME);
+}
@@ -204,7 +204,6 @@ fn query_dump(deps: Deps) -> StdResult<DumpResponse> {
             .groups_to_addresses
             .prefix(&name)
             .keys(deps.storage, None, None, Order::Ascending)
-            .into_iter()
             .map(|addr| addr.map(Into::into))
             .collect::<StdResult<Vec<String>>>()?;
 
@@ -8,7 +8,8 @@ use cw_core_interface::voting::{
     InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use cw_native_staked_balance_voting::msg::{
-    ExecuteMsg, InstantiateMsg, ListStakersResponse, MigrateMsg, Owner, QueryMsg,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, Owner, QueryMsg,
+    StakedBalanceAtHeightResponse, TotalStakedAtHeightResponse,
 };
 use cw_native_staked_balance_voting::state::Config;
 
@@ -30,6 +31,9 @@ fn main() {
     export_schema(&schema_for!(IsActiveResponse), &out_dir);
     export_schema(&schema_for!(ClaimsResponse), &out_dir);
     export_schema(&schema_for!(ListStakersResponse), &out_dir);
+    export_schema(&schema_for!(GetHooksResponse), &out_dir);
+    export_schema(&schema_for!(StakedBalanceAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(TotalStakedAtHeightResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
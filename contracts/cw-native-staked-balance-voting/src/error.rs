@@ -10,6 +10,9 @@ pub enum ContractError {
     #[error("{0}")]
     PaymentError(#[from] PaymentError),
 
+    #[error("{0}")]
+    HookError(#[from] cw_controllers::HookError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
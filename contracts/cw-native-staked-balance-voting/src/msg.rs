@@ -41,6 +41,12 @@ pub enum ExecuteMsg {
         duration: Option<Duration>,
     },
     Claim {},
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
 }
 
 #[voting_query]
@@ -56,6 +62,19 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    GetHooks {},
+    /// Gets ADDRESS's staked balance in the format returned by
+    /// cw20-stake, so that contracts built for cw20-stake may be used
+    /// with this contract. Returns `StakedBalanceAtHeightResponse`.
+    StakedBalanceAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Gets the total staked balance in the format returned by
+    /// cw20-stake. Returns `TotalStakedAtHeightResponse`.
+    TotalStakedAtHeight {
+        height: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -73,3 +92,23 @@ pub struct StakerBalanceResponse {
     pub address: String,
     pub balance: Uint128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GetHooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct StakedBalanceAtHeightResponse {
+    pub balance: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TotalStakedAtHeightResponse {
+    pub total: Uint128,
+    pub height: u64,
+}
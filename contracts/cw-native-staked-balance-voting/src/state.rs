@@ -1,5 +1,5 @@
 use cosmwasm_std::{Addr, Uint128};
-use cw_controllers::Claims;
+use cw_controllers::{Claims, Hooks};
 use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 use schemars::JsonSchema;
@@ -33,3 +33,6 @@ pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
 pub const MAX_CLAIMS: u64 = 100;
 
 pub const CLAIMS: Claims = Claims::new("claims");
+
+// Hooks to contracts that will receive staking and unstaking messages
+pub const HOOKS: Hooks = Hooks::new("hooks");
@@ -1,7 +1,7 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, ListStakersResponse, MigrateMsg, Owner, QueryMsg,
-    StakerBalanceResponse,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, Owner, QueryMsg,
+    StakedBalanceAtHeightResponse, StakerBalanceResponse, TotalStakedAtHeightResponse,
 };
 use crate::state::Config;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
@@ -179,6 +179,44 @@ fn get_total_power_at_height(
         .unwrap()
 }
 
+fn add_hook(
+    app: &mut App,
+    staking_addr: Addr,
+    sender: &str,
+    addr: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr,
+        &ExecuteMsg::AddHook {
+            addr: addr.to_string(),
+        },
+        &[],
+    )
+}
+
+fn remove_hook(
+    app: &mut App,
+    staking_addr: Addr,
+    sender: &str,
+    addr: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr,
+        &ExecuteMsg::RemoveHook {
+            addr: addr.to_string(),
+        },
+        &[],
+    )
+}
+
+fn get_hooks(app: &mut App, staking_addr: Addr) -> GetHooksResponse {
+    app.wrap()
+        .query_wasm_smart(staking_addr, &QueryMsg::GetHooks {})
+        .unwrap()
+}
+
 fn get_config(app: &mut App, staking_addr: Addr) -> Config {
     app.wrap()
         .query_wasm_smart(staking_addr, &QueryMsg::GetConfig {})
@@ -974,6 +1012,119 @@ fn test_query_list_stakers() {
     assert_eq!(stakers, ListStakersResponse { stakers: vec![] });
 }
 
+#[test]
+fn test_add_remove_hooks() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: None,
+        },
+    );
+
+    // Only the owner and manager may add hooks.
+    add_hook(&mut app, addr.clone(), ADDR2, "hook").unwrap_err();
+    add_hook(&mut app, addr.clone(), DAO_ADDR, "hook").unwrap();
+    add_hook(&mut app, addr.clone(), ADDR1, "other_hook").unwrap();
+    // Hooks may not be added twice.
+    add_hook(&mut app, addr.clone(), DAO_ADDR, "hook").unwrap_err();
+
+    let hooks = get_hooks(&mut app, addr.clone());
+    assert_eq!(
+        hooks.hooks,
+        vec!["hook".to_string(), "other_hook".to_string()]
+    );
+
+    remove_hook(&mut app, addr.clone(), ADDR2, "hook").unwrap_err();
+    remove_hook(&mut app, addr.clone(), ADDR1, "hook").unwrap();
+    remove_hook(&mut app, addr.clone(), DAO_ADDR, "hook").unwrap_err();
+
+    let hooks = get_hooks(&mut app, addr);
+    assert_eq!(hooks.hooks, vec!["other_hook".to_string()]);
+}
+
+#[test]
+fn test_staked_queries() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    app.update_block(next_block);
+    let height = app.block_info().height;
+
+    // Mirrors the voting power queries in the format returned by
+    // cw20-stake.
+    let resp: StakedBalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::StakedBalanceAtHeight {
+                address: ADDR1.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        StakedBalanceAtHeightResponse {
+            balance: Uint128::new(100),
+            height,
+        }
+    );
+
+    let resp: TotalStakedAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::TotalStakedAtHeight { height: None },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        TotalStakedAtHeightResponse {
+            total: Uint128::new(150),
+            height,
+        }
+    );
+
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 100).unwrap();
+    app.update_block(next_block);
+
+    let resp: StakedBalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::StakedBalanceAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.balance, Uint128::new(100));
+
+    let resp: TotalStakedAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::TotalStakedAtHeight { height: None })
+        .unwrap();
+    assert_eq!(resp.total, Uint128::new(50));
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -0,0 +1,911 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::IsActiveResponse;
+use cw_storage_plus::Bound;
+use indexable_hooks::{HookOptions, Hooks};
+use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks, ProposalHookFilter};
+use vote_hooks::{new_vote_hooks, VoteHookMsgV2};
+
+use voting::deposit::{
+    check_native_deposit_paid, get_deposit_msg, get_refund_deposit_msgs, DepositInfo,
+};
+use voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use voting::reply::{error::TagError, TaggedReplyId};
+use voting::status::Status;
+use voting::voting::{get_total_power, get_voting_power};
+
+use crate::msg::FundingToken;
+use crate::proposal::{advance_proposal_id, conviction_threshold, ConvictionProposal};
+use crate::state::{Config, VOTER_SUPPORT};
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cw-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let dao = info.sender;
+    let deposit_info = msg
+        .deposit_info
+        .map(|info| info.into_checked(deps.as_ref(), dao.clone()))
+        .transpose()?;
+
+    let config = Config {
+        funding_token: msg.funding_token.into_checked(deps.as_ref())?,
+        decay: msg.decay,
+        max_ratio: msg.max_ratio,
+        weight: msg.weight,
+        only_members_execute: msg.only_members_execute,
+        dao: dao.clone(),
+        deposit_info,
+    };
+    config.validate()?;
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            recipient,
+            amount,
+        } => execute_propose(deps, env, info, title, description, recipient, amount),
+        ExecuteMsg::Vote { proposal_id, power } => {
+            execute_vote(deps, env, info, proposal_id, power)
+        }
+        ExecuteMsg::UpdateSupport { proposal_id, voter } => {
+            execute_update_support(deps, env, proposal_id, voter)
+        }
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            funding_token,
+            decay,
+            max_ratio,
+            weight,
+            only_members_execute,
+            dao,
+            deposit_info,
+        } => execute_update_config(
+            deps,
+            info,
+            funding_token,
+            decay,
+            max_ratio,
+            weight,
+            only_members_execute,
+            dao,
+            deposit_info,
+        ),
+        ExecuteMsg::AddProposalHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+            filter,
+        } => execute_add_proposal_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+            filter,
+        ),
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_proposal_hook(deps, env, info, address)
+        }
+        ExecuteMsg::AddVoteHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+        } => execute_add_vote_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+        ),
+        ExecuteMsg::RemoveVoteHook { address } => {
+            execute_remove_vote_hook(deps, env, info, address)
+        }
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_native_deposit_paid(&config.deposit_info, &info)?;
+    let sender = info.sender;
+
+    let voting_module: Addr = deps
+        .querier
+        .query_wasm_smart(config.dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true });
+
+    if !active_resp.active {
+        return Err(ContractError::InactiveDao {});
+    }
+
+    // Check that the sender is a member of the governance contract.
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        sender.clone(),
+        config.dao.clone(),
+        Some(env.block.height),
+    )?;
+    if sender_power.is_zero() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let proposal = ConvictionProposal {
+        title,
+        description,
+        proposer: sender.clone(),
+        start_height: env.block.height,
+        recipient,
+        amount,
+        funding_token: config.funding_token.clone(),
+        decay: config.decay,
+        status: Status::Open,
+        support: Uint128::zero(),
+        conviction: Uint128::zero(),
+        conviction_height: env.block.height,
+        deposit_info: config.deposit_info.clone(),
+        created: env.block.time,
+        last_updated: env.block.time,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    // Limit the size of proposals so that they may always be
+    // queried. See the comment in `cw-proposal-single` for details.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let deposit_msg = get_deposit_msg(&config.deposit_info, &env.contract.address, &sender)?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        &env.contract.address,
+        &[proposal.transfer_msg()?],
+    )?;
+    Ok(Response::default()
+        .add_messages(deposit_msg)
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    power: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let current = BALLOTS
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .map(|ballot| ballot.power)
+        .unwrap_or_default();
+    if current == power {
+        return Err(ContractError::AlreadyCast { power });
+    }
+
+    let voter_support = VOTER_SUPPORT
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    let voter_support = voter_support - current + power;
+
+    // Support may always be withdrawn, even if the voter's voting
+    // power has since decreased or the proposal has closed.
+    if power > current {
+        if prop.status != Status::Open {
+            return Err(ContractError::NotOpen { id: proposal_id });
+        }
+        let voting_power =
+            get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?;
+        if voting_power.is_zero() {
+            return Err(ContractError::NotRegistered {});
+        }
+        // Support for a proposal is bounded by the voter's voting
+        // power when the proposal was created. Otherwise voting power
+        // could be moved to another address and committed to the
+        // same proposal again.
+        let snapshot_power = get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            config.dao,
+            Some(prop.start_height),
+        )?;
+        if power > snapshot_power {
+            return Err(ContractError::InsufficientVotingPower {
+                support: power,
+                power: snapshot_power,
+            });
+        }
+        if voter_support > voting_power {
+            return Err(ContractError::InsufficientVotingPower {
+                support: voter_support,
+                power: voting_power,
+            });
+        }
+    }
+
+    let vote_hooks = set_support(
+        deps.storage,
+        &env,
+        &mut prop,
+        proposal_id,
+        &info.sender,
+        current,
+        power,
+        voter_support,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("power", power)
+        .add_attribute("support", prop.support))
+}
+
+/// Reduces VOTER's support for an open proposal until their support
+/// across all proposals no longer exceeds their current voting power.
+pub fn execute_update_support(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+    voter: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let voter = deps.api.addr_validate(&voter)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let current = BALLOTS
+        .may_load(deps.storage, (proposal_id, voter.clone()))?
+        .map(|ballot| ballot.power)
+        .unwrap_or_default();
+    let voter_support = VOTER_SUPPORT
+        .may_load(deps.storage, voter.clone())?
+        .unwrap_or_default();
+    let voting_power = get_voting_power(deps.as_ref(), voter.clone(), config.dao, None)?;
+    let excess = voter_support.saturating_sub(voting_power).min(current);
+    if excess.is_zero() {
+        return Err(ContractError::SupportWithinPower {});
+    }
+    let power = current - excess;
+
+    let vote_hooks = set_support(
+        deps.storage,
+        &env,
+        &mut prop,
+        proposal_id,
+        &voter,
+        current,
+        power,
+        voter_support - excess,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "update_support")
+        .add_attribute("voter", voter)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("power", power)
+        .add_attribute("support", prop.support))
+}
+
+/// Changes VOTER's support for PROP from CURRENT to POWER, leaving
+/// them with VOTER_SUPPORT across all proposals, and returns the vote
+/// hook messages for the change.
+#[allow(clippy::too_many_arguments)]
+fn set_support(
+    storage: &mut dyn Storage,
+    env: &Env,
+    prop: &mut ConvictionProposal,
+    proposal_id: u64,
+    voter: &Addr,
+    current: Uint128,
+    power: Uint128,
+    voter_support: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    // Accumulate conviction with the old support before changing it.
+    prop.update_conviction(env.block.height);
+    prop.support = prop.support - current + power;
+    PROPOSALS.save(storage, proposal_id, prop)?;
+
+    if power.is_zero() {
+        BALLOTS.remove(storage, (proposal_id, voter.clone()));
+    } else {
+        BALLOTS.save(storage, (proposal_id, voter.clone()), &Ballot { power })?;
+    }
+    if voter_support.is_zero() {
+        VOTER_SUPPORT.remove(storage, voter.clone());
+    } else {
+        VOTER_SUPPORT.save(storage, voter.clone(), &voter_support)?;
+    }
+
+    new_vote_hooks(
+        VOTE_HOOKS,
+        storage,
+        VoteHookMsgV2::NewVote {
+            proposal_id,
+            voter: voter.clone(),
+            vote: power,
+            power,
+            rationale: None,
+            module: env.contract.address.clone(),
+        },
+    )
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.only_members_execute {
+        let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?;
+        if power.is_zero() {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let threshold = query_threshold(deps.as_ref(), &config, &prop)?;
+    let old_status = prop.current_status(&env.block, threshold);
+    if old_status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    prop.update_conviction(env.block.height);
+    prop.status = Status::Executed;
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let refund_message = match prop.deposit_info {
+        Some(ref deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, true)?
+        }
+        None => vec![],
+    };
+
+    let transfer = prop.transfer_msg()?;
+    let execute_message = WasmMsg::Execute {
+        contract_addr: config.dao.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![transfer],
+        })?,
+        funds: vec![],
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &[prop.transfer_msg()?],
+    )?;
+    Ok(Response::default()
+        .add_message(execute_message)
+        .add_messages(refund_message)
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    if info.sender != prop.proposer && info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let old_status = prop.status;
+    prop.update_conviction(env.block.height);
+    prop.status = Status::Closed;
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let refund_message = match &prop.deposit_info {
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, false)?
+        }
+        None => vec![],
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &[prop.transfer_msg()?],
+    )?;
+    Ok(Response::default()
+        .add_messages(refund_message)
+        .add_submessages(hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    funding_token: FundingToken,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+    only_members_execute: bool,
+    dao: String,
+    deposit_info: Option<DepositInfo>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let dao = deps.api.addr_validate(&dao)?;
+    let deposit_info = deposit_info
+        .map(|info| info.into_checked(deps.as_ref(), dao.clone()))
+        .transpose()?;
+
+    let config = Config {
+        funding_token: funding_token.into_checked(deps.as_ref())?,
+        decay,
+        max_ratio,
+        weight,
+        only_members_execute,
+        dao,
+        deposit_info,
+    };
+    config.validate()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn add_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validated_address: Addr,
+    options: HookOptions,
+) -> Result<(), ContractError> {
+    hooks
+        .add_hook_with_options(storage, validated_address, options)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn remove_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validate_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .remove_hook(storage, validate_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn execute_add_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+    options: HookOptions,
+    filter: Option<ProposalHookFilter>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    let options = match filter {
+        Some(filter) => filter.apply(deps.api, options)?,
+        None => options,
+    };
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address, options)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_add_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+    options: HookOptions,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(VOTE_HOOKS, deps.storage, validated_address, options)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_vote_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_vote_hook")
+        .add_attribute("address", address))
+}
+
+/// Computes the conviction PROP must reach to pass given the DAO's
+/// current voting power and treasury. Returns None if the proposal
+/// is not open or can not currently pass.
+pub fn query_threshold(
+    deps: Deps,
+    config: &Config,
+    prop: &ConvictionProposal,
+) -> StdResult<Option<Uint128>> {
+    if prop.status != Status::Open {
+        return Ok(None);
+    }
+    let total_power = get_total_power(deps, config.dao.clone(), None)?;
+    let funds = prop.funding_token.query_balance(deps, &config.dao)?;
+    Ok(conviction_threshold(
+        total_power,
+        funds,
+        prop.amount,
+        prop.decay,
+        config.max_ratio,
+        config.weight,
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ProposalCount {} => query_proposal_count(deps),
+        QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::VoterSupport { voter } => query_voter_support(deps, voter),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::ProposalHooks { start_after, limit } => to_binary(
+            &PROPOSAL_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::VoteHooks { start_after, limit } => to_binary(
+            &VOTE_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::DisabledProposalHooks {} => {
+            to_binary(&PROPOSAL_HOOKS.query_disabled_hooks(deps)?)
+        }
+        QueryMsg::DisabledVoteHooks {} => to_binary(&VOTE_HOOKS.query_disabled_hooks(deps)?),
+        QueryMsg::ProposalHookFailures { address } => to_binary(
+            &PROPOSAL_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookFailures { address } => {
+            to_binary(&VOTE_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&config)
+}
+
+fn proposal_response(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    prop: ConvictionProposal,
+) -> StdResult<ProposalResponse> {
+    let threshold = query_threshold(deps, config, &prop)?;
+    Ok(prop.into_response(&env.block, id, threshold))
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal_response(deps, &env, &config, id, proposal)?)
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props = PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal_response(deps, &env, &config, id, proposal)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let max = start_before.map(Bound::exclusive);
+    let props = PROPOSALS
+        .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal_response(deps, &env, &config, id, proposal)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
+    let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
+    to_binary(&proposal_count)
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let vote = ballot.map(|ballot| VoteInfo {
+        voter,
+        power: ballot.power,
+    });
+    to_binary(&VoteResponse { vote })
+}
+
+pub fn query_list_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let votes = BALLOTS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, ballot) = item?;
+            Ok(VoteInfo {
+                voter,
+                power: ballot.power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoteListResponse { votes })
+}
+
+pub fn query_voter_support(deps: Deps, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let support = VOTER_SUPPORT
+        .may_load(deps.storage, voter)?
+        .unwrap_or_default();
+    to_binary(&support)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        // Proposals are executed with regular messages, so their
+        // failure reverts the execution instead of replying.
+        TaggedReplyId::FailedProposalExecution(_) => {
+            Err(TagError::UnknownReplyId { id: msg.id }.into())
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let (addr, outcome) =
+                PROPOSAL_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} proposal hook"), format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedVoteHook(idx) => {
+            let (addr, outcome) =
+                VOTE_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} vote hook"), format!("{addr}:{idx}")))
+        }
+    }
+}
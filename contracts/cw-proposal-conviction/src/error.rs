@@ -0,0 +1,63 @@
+use cosmwasm_std::{StdError, Uint128};
+use indexable_hooks::HookError;
+use thiserror::Error;
+use voting::reply::error::TagError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    HookError(#[from] HookError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{0}")]
+    VotingError(#[from] voting::error::VotingError),
+
+    #[error("Decay must be greater than zero and less than one")]
+    InvalidDecay {},
+
+    #[error("Max ratio must be greater than zero and no more than one")]
+    InvalidMaxRatio {},
+
+    #[error("Weight must be greater than zero")]
+    ZeroWeight {},
+
+    #[error("Proposals must request a non-zero amount")]
+    ZeroAmount {},
+
+    #[error("No such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("Proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("Proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("Not registered to vote (no voting power).")]
+    NotRegistered {},
+
+    #[error(
+        "Insufficient voting power. Support would total ({support}), but voting power is ({power})"
+    )]
+    InsufficientVotingPower { support: Uint128, power: Uint128 },
+
+    #[error("Support for this proposal is already ({power})")]
+    AlreadyCast { power: Uint128 },
+
+    #[error("Support does not exceed the voter's voting power")]
+    SupportWithinPower {},
+
+    #[error("Proposal is not in 'passed' state.")]
+    NotPassed {},
+
+    #[error("The DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error("{0}")]
+    Tag(#[from] TagError),
+}
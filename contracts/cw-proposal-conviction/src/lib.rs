@@ -0,0 +1,24 @@
+//! # cw-proposal-conviction
+//!
+//! A conviction voting proposal module for a DAO DAO DAO. Proposals
+//! request an amount of a funding token from the DAO's treasury and
+//! members support them with their voting power. Support accumulates
+//! into conviction over blocks, and a proposal passes once its
+//! conviction crosses a threshold that scales with the fraction of the
+//! treasury it requests. Unlike the other proposal modules proposals
+//! do not have a voting period, which suits continuous funding
+//! decisions.
+//!
+//! For more information see the README.
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod query;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
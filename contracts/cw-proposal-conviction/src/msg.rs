@@ -0,0 +1,257 @@
+use cosmwasm_std::{Decimal, Uint128};
+use indexable_hooks::{HookFailurePolicy, HookVersion};
+use proposal_hooks::ProposalHookFilter;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw_core_macros::govmod_query;
+use voting::deposit::DepositInfo;
+
+/// The token that proposals request funding in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FundingToken {
+    /// A native token with the given denomination.
+    Native { denom: String },
+    /// A cw20 token with the given address.
+    Cw20 { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The token that proposals request funding in. The conviction
+    /// needed for a proposal to pass scales with the amount it
+    /// requests relative to the DAO's balance of this token.
+    pub funding_token: FundingToken,
+    /// The fraction of a proposal's conviction that is retained each
+    /// block. Must be greater than zero and less than one. Higher
+    /// values cause conviction to take longer to accumulate and to
+    /// decay.
+    pub decay: Decimal,
+    /// The maximum fraction of the DAO's funding token balance that a
+    /// single proposal may request. Must be greater than zero and no
+    /// more than one.
+    pub max_ratio: Decimal,
+    /// Scales the conviction needed for proposals to pass. Must be
+    /// greater than zero.
+    pub weight: Decimal,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// Information about the deposit required to create a
+    /// proposal. None if there is no deposit requirement, Some
+    /// otherwise.
+    pub deposit_info: Option<DepositInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal requesting funding from the DAO's
+    /// treasury.
+    Propose {
+        /// The title of the proposal.
+        title: String,
+        /// A description of the proposal.
+        description: String,
+        /// The address that will receive the requested funds should
+        /// this proposal pass.
+        recipient: String,
+        /// The amount of the funding token requested.
+        amount: Uint128,
+    },
+    /// Sets the amount of the sender's voting power supporting a
+    /// proposal. The sender's support across all proposals may not
+    /// exceed their current voting power, and their support for a
+    /// proposal may not exceed their voting power when it was
+    /// created. Setting support to zero
+    /// withdraws it, which is also permitted once a proposal has
+    /// been executed or closed.
+    Vote {
+        /// The ID of the proposal to support.
+        proposal_id: u64,
+        /// The amount of voting power to support the proposal with.
+        power: Uint128,
+    },
+    /// Reduces a voter's support for an open proposal until their
+    /// support across all proposals no longer exceeds their current
+    /// voting power. May be called by any address, for example once
+    /// a voter has unstaked tokens whose voting power still supports
+    /// the proposal.
+    UpdateSupport {
+        /// The ID of the proposal to reduce support for.
+        proposal_id: u64,
+        /// The voter whose support should be reduced.
+        voter: String,
+    },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes an open proposal. May only be called by the proposal's
+    /// proposer or the DAO.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config.
+    UpdateConfig {
+        /// The token that proposals request funding in. Applies to
+        /// proposals created after the config update.
+        funding_token: FundingToken,
+        /// The fraction of a proposal's conviction that is retained
+        /// each block. Applies to proposals created after the config
+        /// update.
+        decay: Decimal,
+        /// The maximum fraction of the DAO's funding token balance
+        /// that a single proposal may request. Applies to all
+        /// outstanding and future proposals.
+        max_ratio: Decimal,
+        /// Scales the conviction needed for proposals to
+        /// pass. Applies to all outstanding and future proposals.
+        weight: Decimal,
+        /// If set to true only members may execute passed
+        /// proposals. Otherwise, any address may execute a passed
+        /// proposal. Applies to all outstanding and future proposals.
+        only_members_execute: bool,
+        /// The address of the DAO that this governance module is
+        /// associated with.
+        dao: String,
+        /// Information about the deposit required to make a
+        /// proposal. None if no deposit, Some otherwise.
+        deposit_info: Option<DepositInfo>,
+    },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook {
+        address: String,
+        /// The version of the proposal hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+        /// Limits the messages sent to ADDRESS. Defaults to sending
+        /// every message.
+        #[serde(default)]
+        filter: Option<ProposalHookFilter>,
+    },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever support
+    /// for a proposal changes. If a consumer contract errors when
+    /// handling a hook message it will be removed from the list of
+    /// consumers.
+    AddVoteHook {
+        address: String,
+        /// The version of the vote hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+    },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String },
+}
+
+#[govmod_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Gets the governance module's config. Returns `state::Config`.
+    Config {},
+    /// Gets information about a proposal. Returns
+    /// `query::ProposalResponse`.
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been cast in this
+    /// module. Returns `query::ProposalListResponse`.
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been cast in this module
+    /// in decending order of proposal ID. Returns
+    /// `query::ProposalListResponse`.
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this
+    /// module.
+    ProposalCount {},
+    /// Returns a voter's support for a propsal. Returns
+    /// `query::VoteResponse`.
+    GetVote { proposal_id: u64, voter: String },
+    /// Lists the support for a proposal. Returns
+    /// `query::VoteListResponse`.
+    ListVotes {
+        /// The proposal to list the support of.
+        proposal_id: u64,
+        /// The voter to start listing votes after. Ordering is done
+        /// alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of votes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Returns the total amount of voting power a voter has committed
+    /// to proposals. Returns `Uint128`.
+    VoterSupport { voter: String },
+    /// Lists the consumers of proposal hooks for this module in the
+    /// order they were registered. Returns
+    /// indexable_hooks::HooksResponse.
+    ProposalHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the consumers of vote hooks for this module in the order
+    /// they were registered. Returns indexable_hooks::HooksResponse.
+    VoteHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the proposal hooks that have been disabled after failing
+    /// too many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledProposalHooks {},
+    /// Lists the vote hooks that have been disabled after failing too
+    /// many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledVoteHooks {},
+    /// Lists the most recent failed deliveries to a proposal
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    ProposalHookFailures { address: String },
+    /// Lists the most recent failed deliveries to a vote
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    VoteHookFailures { address: String },
+}
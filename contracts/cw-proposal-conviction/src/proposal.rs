@@ -0,0 +1,307 @@
+use cosmwasm_std::{
+    Addr, BlockInfo, CosmosMsg, Decimal, StdResult, Storage, Timestamp, Uint128, Uint256,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use voting::deposit::CheckedDepositInfo;
+use voting::proposal::Proposal;
+use voting::status::Status;
+
+use crate::query::ProposalResponse;
+use crate::state::{CheckedFundingToken, PROPOSAL_COUNT};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ConvictionProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created.
+    pub start_height: u64,
+    /// The address that will receive the requested funds should this
+    /// proposal pass.
+    pub recipient: Addr,
+    /// The amount of `funding_token` requested.
+    pub amount: Uint128,
+    /// The token funding is requested in.
+    pub funding_token: CheckedFundingToken,
+    /// The fraction of this proposal's conviction that is retained
+    /// each block. Fixed when the proposal is created so that config
+    /// changes do not alter how its conviction accumulates.
+    pub decay: Decimal,
+
+    pub status: Status,
+    /// The total amount of voting power currently supporting this
+    /// proposal.
+    pub support: Uint128,
+    /// This proposal's conviction as of `conviction_height`.
+    pub conviction: Uint128,
+    /// The block height at which `conviction` was last computed.
+    pub conviction_height: u64,
+
+    /// Information about the deposit that was sent as part of this
+    /// proposal. None if no deposit.
+    pub deposit_info: Option<CheckedDepositInfo>,
+    /// The timestamp at which this proposal was created.
+    pub created: Timestamp,
+    /// The timestamp at which this proposal's status last
+    /// changed. As proposals pass once their conviction crosses a
+    /// threshold, which may happen without any interaction with this
+    /// contract, this field is not updated when a proposal passes.
+    pub last_updated: Timestamp,
+}
+
+impl Proposal for ConvictionProposal {
+    fn proposer(&self) -> Addr {
+        self.proposer.clone()
+    }
+    fn deposit_info(&self) -> Option<CheckedDepositInfo> {
+        self.deposit_info.clone()
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl ConvictionProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. The proposal's conviction and status are
+    /// brought up to date as of BLOCK.
+    pub fn into_response(
+        mut self,
+        block: &BlockInfo,
+        id: u64,
+        threshold: Option<Uint128>,
+    ) -> ProposalResponse {
+        self.update_conviction(block.height);
+        self.status = self.current_status(block, threshold);
+        ProposalResponse {
+            id,
+            proposal: self,
+            threshold,
+        }
+    }
+
+    /// Gets the message that pays out this proposal's requested
+    /// funds.
+    pub fn transfer_msg(&self) -> StdResult<CosmosMsg> {
+        self.funding_token
+            .get_transfer_msg(&self.recipient, self.amount)
+    }
+
+    /// Computes this proposal's conviction at HEIGHT.
+    ///
+    /// Each block conviction decays by the proposal's decay and grows
+    /// by the
+    /// proposal's support, so after `t` blocks of constant support
+    /// `s` conviction is `c * decay^t + s * (1 - decay^t) / (1 -
+    /// decay)`. Conviction approaches `s / (1 - decay)` as `t` grows.
+    pub fn conviction_at(&self, height: u64) -> Uint128 {
+        let decay = self.decay;
+        let elapsed = height.saturating_sub(self.conviction_height);
+        // Decay is less than one so this can not overflow, and for
+        // any reasonable decay has long since reached zero when
+        // `elapsed` no longer fits in a u32.
+        let retained = decay
+            .checked_pow(u32::try_from(elapsed).unwrap_or(u32::MAX))
+            .unwrap_or_default();
+        let accumulated = self.support.multiply_ratio(
+            (Decimal::one() - retained).atomics(),
+            (Decimal::one() - decay).atomics(),
+        );
+        self.conviction * retained + accumulated
+    }
+
+    /// Brings this proposal's conviction up to date as of HEIGHT. This
+    /// must be called before this proposal's support is changed.
+    pub fn update_conviction(&mut self, height: u64) {
+        self.conviction = self.conviction_at(height);
+        self.conviction_height = height;
+    }
+
+    /// Gets the current status of the proposal. An open proposal has
+    /// passed once its conviction reaches THRESHOLD. A THRESHOLD of
+    /// None means that the proposal can not currently pass.
+    pub fn current_status(&self, block: &BlockInfo, threshold: Option<Uint128>) -> Status {
+        match threshold {
+            Some(threshold)
+                if self.status == Status::Open && self.conviction_at(block.height) >= threshold =>
+            {
+                Status::Passed
+            }
+            _ => self.status,
+        }
+    }
+}
+
+/// Computes the conviction a proposal requesting AMOUNT from a
+/// treasury holding FUNDS must reach in order to pass.
+///
+/// The threshold is
+/// `weight * total_power / ((1 - decay) * (max_ratio - amount / funds)^2)`.
+/// A proposal with the support of the entire DAO will eventually
+/// reach a conviction of `total_power / (1 - decay)`, so requests for
+/// a small fraction of the treasury require little support while
+/// requests approaching `max_ratio` require nearly unbounded
+/// support. Returns None if the request is for `max_ratio` or more of
+/// the treasury, in which case the proposal can not pass.
+pub fn conviction_threshold(
+    total_power: Uint128,
+    funds: Uint128,
+    amount: Uint128,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Option<Uint128> {
+    if funds.is_zero() {
+        return None;
+    }
+    let ratio = Decimal::from_ratio(amount, funds);
+    if ratio >= max_ratio {
+        return None;
+    }
+    let remaining = max_ratio - ratio;
+    let denominator = (Decimal::one() - decay) * remaining * remaining;
+    if denominator.is_zero() {
+        return None;
+    }
+    let threshold = Uint256::from(total_power) * Uint256::from(weight.atomics())
+        / Uint256::from(denominator.atomics());
+    Uint128::try_from(threshold).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+
+    fn proposal(support: Uint128, conviction_height: u64, decay: Decimal) -> ConvictionProposal {
+        let env = mock_env();
+        ConvictionProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: conviction_height,
+            recipient: Addr::unchecked("recipient"),
+            amount: Uint128::new(10),
+            funding_token: CheckedFundingToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            decay,
+            status: Status::Open,
+            support,
+            conviction: Uint128::zero(),
+            conviction_height,
+            deposit_info: None,
+            created: env.block.time,
+            last_updated: env.block.time,
+        }
+    }
+
+    #[test]
+    fn test_conviction_accumulates() {
+        let decay = Decimal::percent(50);
+        let mut prop = proposal(Uint128::new(100), 0, decay);
+
+        assert_eq!(prop.conviction_at(0), Uint128::zero());
+        // 100
+        assert_eq!(prop.conviction_at(1), Uint128::new(100));
+        // 100 * 0.5 + 100
+        assert_eq!(prop.conviction_at(2), Uint128::new(150));
+        // Approaches 100 / (1 - 0.5).
+        assert_eq!(prop.conviction_at(10), Uint128::new(199));
+        assert_eq!(prop.conviction_at(u64::MAX), Uint128::new(200));
+
+        // Updating conviction in steps is the same as computing it at
+        // once.
+        prop.update_conviction(1);
+        assert_eq!(prop.conviction_at(2), Uint128::new(150));
+
+        // Conviction decays once support is withdrawn.
+        prop.update_conviction(2);
+        prop.support = Uint128::zero();
+        assert_eq!(prop.conviction_at(3), Uint128::new(75));
+        assert_eq!(prop.conviction_at(100), Uint128::zero());
+    }
+
+    #[test]
+    fn test_conviction_threshold() {
+        let decay = Decimal::percent(90);
+        let max_ratio = Decimal::percent(20);
+        let weight = Decimal::percent(1);
+        let total_power = Uint128::new(1_000);
+        let funds = Uint128::new(1_000);
+
+        // 0.01 * 1000 / (0.1 * 0.1^2)
+        assert_eq!(
+            conviction_threshold(
+                total_power,
+                funds,
+                Uint128::new(100),
+                decay,
+                max_ratio,
+                weight
+            ),
+            Some(Uint128::new(10_000))
+        );
+        // Smaller requests need less conviction.
+        assert_eq!(
+            conviction_threshold(
+                total_power,
+                funds,
+                Uint128::zero(),
+                decay,
+                max_ratio,
+                weight
+            ),
+            Some(Uint128::new(2_500))
+        );
+        // Requests at or beyond the max ratio can not pass.
+        assert_eq!(
+            conviction_threshold(
+                total_power,
+                funds,
+                Uint128::new(200),
+                decay,
+                max_ratio,
+                weight
+            ),
+            None
+        );
+        assert_eq!(
+            conviction_threshold(
+                total_power,
+                Uint128::zero(),
+                Uint128::new(1),
+                decay,
+                max_ratio,
+                weight
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_current_status() {
+        let env = mock_env();
+        let decay = Decimal::percent(50);
+        let prop = proposal(Uint128::new(100), env.block.height - 2, decay);
+
+        assert_eq!(
+            prop.current_status(&env.block, Some(Uint128::new(150))),
+            Status::Passed
+        );
+        assert_eq!(
+            prop.current_status(&env.block, Some(Uint128::new(151))),
+            Status::Open
+        );
+        assert_eq!(prop.current_status(&env.block, None), Status::Open);
+    }
+}
@@ -0,0 +1,47 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::ConvictionProposal;
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: ConvictionProposal,
+    /// The conviction the proposal must reach in order to pass given
+    /// the DAO's current treasury and voting power. None if the
+    /// proposal requests too large a fraction of the treasury to
+    /// pass.
+    pub threshold: Option<Uint128>,
+}
+
+/// Information about a voter's support for a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteInfo {
+    /// The address that voted.
+    pub voter: Addr,
+    /// The voting power supporting the proposal.
+    pub power: Uint128,
+}
+
+/// Information about a vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteResponse {
+    /// None if no such vote, Some otherwise.
+    pub vote: Option<VoteInfo>,
+}
+
+/// Information about the votes for a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
+}
+
+/// A list of proposals returned by `ListProposals` and
+/// `ReverseProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
@@ -0,0 +1,143 @@
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, StdResult, Uint128, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+use indexable_hooks::Hooks;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use voting::deposit::CheckedDepositInfo;
+
+use crate::{msg::FundingToken, proposal::ConvictionProposal, ContractError};
+
+/// Counterpart to `FundingToken` which has been validated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckedFundingToken {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+impl FundingToken {
+    /// Converts a funding token into a checked funding token.
+    pub fn into_checked(self, deps: Deps) -> StdResult<CheckedFundingToken> {
+        match self {
+            FundingToken::Native { denom } => Ok(CheckedFundingToken::Native { denom }),
+            FundingToken::Cw20 { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                // Make an info query as a smoke test that we are
+                // indeed working with a token here.
+                let _info: cw20::TokenInfoResponse = deps
+                    .querier
+                    .query_wasm_smart(address.clone(), &cw20::Cw20QueryMsg::TokenInfo {})?;
+                Ok(CheckedFundingToken::Cw20 { address })
+            }
+        }
+    }
+}
+
+impl CheckedFundingToken {
+    /// Queries ADDRESS' balance of this token.
+    pub fn query_balance(&self, deps: Deps, address: &Addr) -> StdResult<Uint128> {
+        match self {
+            CheckedFundingToken::Native { denom } => Ok(deps
+                .querier
+                .query_balance(address.clone(), denom.clone())?
+                .amount),
+            CheckedFundingToken::Cw20 { address: token } => {
+                let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                    token.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )?;
+                Ok(balance.balance)
+            }
+        }
+    }
+
+    /// Gets a message that transfers AMOUNT of this token to
+    /// RECIPIENT.
+    pub fn get_transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            CheckedFundingToken::Native { denom } => BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }
+            .into(),
+            CheckedFundingToken::Cw20 { address } => WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        })
+    }
+}
+
+/// The governance module's configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The token that proposals request funding in.
+    pub funding_token: CheckedFundingToken,
+    /// The fraction of a proposal's conviction that is retained each
+    /// block.
+    pub decay: Decimal,
+    /// The maximum fraction of the DAO's funding token balance that a
+    /// single proposal may request.
+    pub max_ratio: Decimal,
+    /// Scales the conviction needed for proposals to pass.
+    pub weight: Decimal,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// The address of the DAO that this governance module is
+    /// associated with.
+    pub dao: Addr,
+    /// Information about the depost required to create a
+    /// proposal. None if no deposit is required, Some otherwise.
+    pub deposit_info: Option<CheckedDepositInfo>,
+}
+
+impl Config {
+    /// Validates the conviction parameters of this config.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.decay.is_zero() || self.decay >= Decimal::one() {
+            return Err(ContractError::InvalidDecay {});
+        }
+        if self.max_ratio.is_zero() || self.max_ratio > Decimal::one() {
+            return Err(ContractError::InvalidMaxRatio {});
+        }
+        if self.weight.is_zero() {
+            return Err(ContractError::ZeroWeight {});
+        }
+        Ok(())
+    }
+}
+
+/// Support for a proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Ballot {
+    /// The amount of voting power supporting the proposal.
+    pub power: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, ConvictionProposal> = Map::new("proposals");
+pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+/// The total amount of voting power each voter has committed to
+/// proposals.
+pub const VOTER_SUPPORT: Map<Addr, Uint128> = Map::new("voter_support");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks", "proposal_hook_indices");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_indices");
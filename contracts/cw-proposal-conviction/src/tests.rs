@@ -0,0 +1,648 @@
+use cosmwasm_std::{to_binary, Addr, Coin, Decimal, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core::state::ProposalModule;
+use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use testing::helpers::{
+    instantiate_with_cw4_groups_governance, instantiate_with_staked_balances_governance,
+};
+use voting::status::Status;
+
+use crate::{
+    msg::{ExecuteMsg, FundingToken, InstantiateMsg, QueryMsg},
+    query::{ProposalResponse, VoteResponse},
+    ContractError,
+};
+
+const CREATOR_ADDR: &str = "creator";
+const DENOM: &str = "ujuno";
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn cw20_balances_voting() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_balance_voting::contract::execute,
+        cw20_balance_voting::contract::instantiate,
+        cw20_balance_voting::contract::query,
+    )
+    .with_reply(cw20_balance_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn cw_gov_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_core::contract::execute,
+        cw_core::contract::instantiate,
+        cw_core::contract::query,
+    )
+    .with_reply(cw_core::contract::reply);
+    Box::new(contract)
+}
+
+fn default_instantiate() -> InstantiateMsg {
+    InstantiateMsg {
+        funding_token: FundingToken::Native {
+            denom: DENOM.to_string(),
+        },
+        decay: Decimal::percent(90),
+        max_ratio: Decimal::percent(20),
+        weight: Decimal::permille(5),
+        only_members_execute: false,
+        deposit_info: None,
+    }
+}
+
+/// Instantiates a DAO with a conviction proposal module and a
+/// treasury of TREASURY funding tokens. Members "ekez" and "keze"
+/// have 600 and 400 voting power respectively. Returns the DAO and
+/// proposal module addresses.
+fn setup(app: &mut App, instantiate: InstantiateMsg, treasury: u128) -> (Addr, Addr) {
+    let cw20_id = app.store_code(cw20_contract());
+    let core_id = app.store_code(cw_gov_contract());
+    let votemod_id = app.store_code(cw20_balances_voting());
+    let proposal_id = app.store_code(proposal_contract());
+
+    let governance_instantiate = cw_core::msg::InstantiateMsg {
+        dao_uri: None,
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: cw_core::msg::ModuleInstantiateInfo {
+            code_id: votemod_id,
+            msg: to_binary(&cw20_balance_voting::msg::InstantiateMsg {
+                token_info: cw20_balance_voting::msg::TokenInfo::New {
+                    code_id: cw20_id,
+                    label: "DAO DAO governance token".to_string(),
+                    name: "DAO".to_string(),
+                    symbol: "DAO".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: "ekez".to_string(),
+                            amount: Uint128::new(600),
+                        },
+                        Cw20Coin {
+                            address: "keze".to_string(),
+                            amount: Uint128::new(400),
+                        },
+                    ],
+                    marketing: None,
+                },
+            })
+            .unwrap(),
+            admin: cw_core::msg::Admin::CoreContract {},
+            label: "DAO DAO voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![cw_core::msg::ModuleInstantiateInfo {
+            code_id: proposal_id,
+            msg: to_binary(&instantiate).unwrap(),
+            admin: cw_core::msg::Admin::CoreContract {},
+            label: "DAO DAO governance module".to_string(),
+        }],
+        initial_items: None,
+    };
+
+    let core_addr = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &governance_instantiate,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+    mint(app, &core_addr, treasury);
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+
+    (core_addr, proposal_module)
+}
+
+fn mint(app: &mut App, to: &Addr, amount: u128) {
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: to.to_string(),
+        amount: vec![Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(amount),
+        }],
+    }))
+    .unwrap();
+}
+
+fn propose(app: &mut App, proposal_module: &Addr, proposer: &str, amount: u128) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Fund the floob".to_string(),
+            description: "The floob needs funding.".to_string(),
+            recipient: "floob".to_string(),
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn vote(
+    app: &mut App,
+    proposal_module: &Addr,
+    voter: &str,
+    proposal_id: u64,
+    power: u128,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            power: Uint128::new(power),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_proposal(app: &App, proposal_module: &Addr, proposal_id: u64) -> ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+#[test]
+fn test_conviction_passes() {
+    let mut app = App::default();
+    let (core_addr, proposal_module) = setup(&mut app, default_instantiate(), 1000);
+
+    let id = propose(&mut app, &proposal_module, "ekez", 100);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    // 0.005 * 1000 / (0.1 * (0.2 - 0.1)^2)
+    assert_eq!(proposal.threshold, Some(Uint128::new(5000)));
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Members may not commit more than their voting power.
+    let err: ContractError = vote(&mut app, &proposal_module, "keze", id, 500).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientVotingPower { .. }));
+
+    vote(&mut app, &proposal_module, "ekez", id, 600).unwrap();
+    let ballot: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: id,
+                voter: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(ballot.vote.unwrap().power, Uint128::new(600));
+
+    // Conviction approaches 6000 as blocks pass and the proposal
+    // passes once it crosses 5000.
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert!(proposal.proposal.conviction < Uint128::new(5000));
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id: id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(
+        app.wrap().query_balance("floob", DENOM).unwrap().amount,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        app.wrap().query_balance(core_addr, DENOM).unwrap().amount,
+        Uint128::new(900)
+    );
+
+    // Support may be withdrawn once the proposal has executed.
+    vote(&mut app, &proposal_module, "ekez", id, 0).unwrap();
+    let support: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &QueryMsg::VoterSupport {
+                voter: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(support, Uint128::zero());
+}
+
+#[test]
+fn test_support_split_between_proposals() {
+    let mut app = App::default();
+    let (_, proposal_module) = setup(&mut app, default_instantiate(), 1000);
+
+    let first = propose(&mut app, &proposal_module, "ekez", 10);
+    let second = propose(&mut app, &proposal_module, "ekez", 10);
+
+    vote(&mut app, &proposal_module, "ekez", first, 400).unwrap();
+    let err: ContractError = vote(&mut app, &proposal_module, "ekez", second, 300).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower { support, power }
+            if support == Uint128::new(700) && power == Uint128::new(600)
+    ));
+    vote(&mut app, &proposal_module, "ekez", second, 200).unwrap();
+
+    let err: ContractError = vote(&mut app, &proposal_module, "ekez", second, 200).unwrap_err();
+    assert!(matches!(err, ContractError::AlreadyCast { .. }));
+
+    // Moving support between proposals.
+    vote(&mut app, &proposal_module, "ekez", first, 100).unwrap();
+    vote(&mut app, &proposal_module, "ekez", second, 500).unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, first)
+            .proposal
+            .support,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        query_proposal(&app, &proposal_module, second)
+            .proposal
+            .support,
+        Uint128::new(500)
+    );
+}
+
+#[test]
+fn test_support_bounded_by_proposal_start() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        proposal_id,
+        to_binary(&default_instantiate()).unwrap(),
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(600),
+        }]),
+    );
+    mint(&mut app, &core_addr, 1000);
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+
+    let id = propose(&mut app, &proposal_module, "ekez", 100);
+    vote(&mut app, &proposal_module, "ekez", id, 600).unwrap();
+
+    // Move ekez's voting power to ezek.
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let group: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_module, &cw4_voting::msg::QueryMsg::GroupContract {})
+        .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec!["ekez".to_string()],
+            add: vec![cw4::Member {
+                addr: "ezek".to_string(),
+                weight: 600,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // The moved voting power may not support the proposal a second
+    // time.
+    let err: ContractError = vote(&mut app, &proposal_module, "ezek", id, 600).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower { support, power }
+            if support == Uint128::new(600) && power == Uint128::zero()
+    ));
+    // It may support proposals created after it was received.
+    let later = propose(&mut app, &proposal_module, "ezek", 100);
+    vote(&mut app, &proposal_module, "ezek", later, 600).unwrap();
+
+    // Changing the decay does not change the decay of existing
+    // proposals.
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            funding_token: FundingToken::Native {
+                denom: DENOM.to_string(),
+            },
+            decay: Decimal::percent(50),
+            max_ratio: Decimal::percent(20),
+            weight: Decimal::permille(5),
+            only_members_execute: false,
+            dao: core_addr.to_string(),
+            deposit_info: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id).proposal.decay,
+        Decimal::percent(90)
+    );
+    let id = propose(&mut app, &proposal_module, "ezek", 100);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id).proposal.decay,
+        Decimal::percent(50)
+    );
+}
+
+#[test]
+fn test_support_reduced_after_unstaking() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        to_binary(&default_instantiate()).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(600),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(400),
+            },
+        ]),
+    );
+    mint(&mut app, &core_addr, 1000);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = gov_state.proposal_modules[0].address.clone();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            gov_state.voting_module,
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+
+    let first = propose(&mut app, &proposal_module, "ekez", 100);
+    let second = propose(&mut app, &proposal_module, "ekez", 100);
+    vote(&mut app, &proposal_module, "ekez", first, 200).unwrap();
+    vote(&mut app, &proposal_module, "ekez", second, 400).unwrap();
+
+    // Ekez unstakes after supporting the proposals.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking_contract,
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(500),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let update_support = |app: &mut App, proposal_id: u64| -> Result<(), ContractError> {
+        app.execute_contract(
+            Addr::unchecked("keze"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateSupport {
+                proposal_id,
+                voter: "ekez".to_string(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
+
+    // Any address may reduce the support that is no longer backed by
+    // voting power, starting with whichever proposal it chooses.
+    update_support(&mut app, second).unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, second)
+            .proposal
+            .support,
+        Uint128::zero()
+    );
+    update_support(&mut app, first).unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, first)
+            .proposal
+            .support,
+        Uint128::new(100)
+    );
+    let support: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::VoterSupport {
+                voter: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(support, Uint128::new(100));
+
+    // Support within the voter's voting power may not be reduced.
+    let err = update_support(&mut app, first).unwrap_err();
+    assert!(matches!(err, ContractError::SupportWithinPower {}));
+    let err = update_support(&mut app, second).unwrap_err();
+    assert!(matches!(err, ContractError::SupportWithinPower {}));
+
+    // Conviction only accumulates from the remaining support.
+    app.update_block(|b| b.height += 100);
+    assert!(
+        query_proposal(&app, &proposal_module, first)
+            .proposal
+            .conviction
+            <= Uint128::new(1000)
+    );
+}
+
+#[test]
+fn test_threshold_scales_with_treasury() {
+    let mut app = App::default();
+    let (core_addr, proposal_module) = setup(&mut app, default_instantiate(), 1000);
+
+    // Requests for the max ratio of the treasury or more can not
+    // pass.
+    let id = propose(&mut app, &proposal_module, "ekez", 200);
+    vote(&mut app, &proposal_module, "ekez", id, 600).unwrap();
+    vote(&mut app, &proposal_module, "keze", id, 400).unwrap();
+    app.update_block(|b| b.height += 100);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.threshold, None);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Once the treasury grows the proposal is a smaller fraction of
+    // it and the same conviction is enough for it to pass.
+    mint(&mut app, &core_addr, 1000);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    // 0.005 * 1000 / (0.1 * (0.2 - 0.1)^2)
+    assert_eq!(proposal.threshold, Some(Uint128::new(5000)));
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_close() {
+    let mut app = App::default();
+    let (core_addr, proposal_module) = setup(&mut app, default_instantiate(), 1000);
+
+    let id = propose(&mut app, &proposal_module, "ekez", 100);
+    vote(&mut app, &proposal_module, "keze", id, 400).unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            proposal_module.clone(),
+            &ExecuteMsg::Close { proposal_id: id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Close { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id).proposal.status,
+        Status::Closed
+    );
+
+    // Support can not be added to closed proposals, but may be
+    // withdrawn from them.
+    let err: ContractError = vote(&mut app, &proposal_module, "ekez", id, 100).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+    vote(&mut app, &proposal_module, "keze", id, 0).unwrap();
+
+    // The DAO may close proposals.
+    let id = propose(&mut app, &proposal_module, "ekez", 100);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::Close { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id).proposal.status,
+        Status::Closed
+    );
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let instantiate = |msg: InstantiateMsg, app: &mut App| -> ContractError {
+        app.instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &msg,
+            &[],
+            "conviction",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    let err = instantiate(
+        InstantiateMsg {
+            decay: Decimal::one(),
+            ..default_instantiate()
+        },
+        &mut app,
+    );
+    assert!(matches!(err, ContractError::InvalidDecay {}));
+
+    let err = instantiate(
+        InstantiateMsg {
+            max_ratio: Decimal::zero(),
+            ..default_instantiate()
+        },
+        &mut app,
+    );
+    assert!(matches!(err, ContractError::InvalidMaxRatio {}));
+
+    let err = instantiate(
+        InstantiateMsg {
+            weight: Decimal::zero(),
+            ..default_instantiate()
+        },
+        &mut app,
+    );
+    assert!(matches!(err, ContractError::ZeroWeight {}));
+}
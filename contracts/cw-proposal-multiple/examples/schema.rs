@@ -9,7 +9,7 @@ use cw_proposal_multiple::{
     query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
     state::Config,
 };
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{DisabledHooksResponse, HookFailuresResponse, HooksResponse};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -56,5 +56,25 @@ fn main() {
         "ProposalHooksResponse",
     );
     export_schema_with_title(&schema_for!(HooksResponse), &out_dir, "VoteHooksResponse");
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledProposalHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledVoteHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "ProposalHookFailuresResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "VoteHookFailuresResponse",
+    );
     export_schema_with_title(&schema_for!(VoteResponse), &out_dir, "GetVoteResponse");
 }
@@ -1,21 +1,27 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
-    StdResult, Storage, SubMsg, WasmMsg,
+    entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw_core_interface::voting::IsActiveResponse;
 use cw_storage_plus::Bound;
 use cw_utils::Duration;
-use indexable_hooks::Hooks;
-use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use indexable_hooks::{HookOptions, Hooks};
+use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks, ProposalHookFilter};
 
-use vote_hooks::new_vote_hooks;
+use vote_hooks::{new_vote_hooks, VoteHookMsgV2};
 use voting::{
-    deposit::{get_deposit_msg, get_return_deposit_msg, DepositInfo},
-    proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
+    curve::{CurvedPower, VotingPowerCurve},
+    deposit::{
+        check_native_deposit_paid, get_deposit_msg, get_refund_deposit_msgs,
+        get_return_deposit_msgs, DepositInfo,
+    },
+    proposal::{validate_rationale, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{mask_proposal_execution_proposal_id, TaggedReplyId},
     status::Status,
+    timelock::validate_timelock,
+    veto::{CheckedVetoer, Vetoer},
     voting::{
         get_total_power, get_voting_power, validate_voting_period, MultipleChoiceVote,
         MultipleChoiceVotes,
@@ -31,7 +37,7 @@ use crate::{
     ContractError,
 };
 
-use crate::state::{Ballot, VoteInfo, BALLOTS, PROPOSALS};
+use crate::state::{add_ranked_ballot, remove_ranked_ballot, Ballot, VoteInfo, BALLOTS, PROPOSALS};
 
 pub const CONTRACT_NAME: &str = "crates.io:cw-proposal-multiple";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -46,6 +52,7 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.voting_strategy.validate()?;
+    msg.voting_power_curve.validate()?;
 
     let dao = info.sender;
     let deposit_info = msg
@@ -55,6 +62,8 @@ pub fn instantiate(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
+    let (timelock, vetoer) =
+        validate_timelock_and_vetoer(deps.as_ref(), msg.timelock, msg.vetoer, max_voting_period)?;
 
     let config = Config {
         voting_strategy: msg.voting_strategy,
@@ -65,6 +74,9 @@ pub fn instantiate(
         dao,
         deposit_info,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        voting_power_curve: msg.voting_power_curve,
+        timelock,
+        vetoer,
     };
 
     // Initialize proposal count to zero.
@@ -76,6 +88,23 @@ pub fn instantiate(
         .add_attribute("dao", config.dao))
 }
 
+/// Validates that the timelock has the same units as the max voting
+/// period and that a vetoer is only set if there is a timelock during
+/// which it may veto proposals.
+fn validate_timelock_and_vetoer(
+    deps: Deps,
+    timelock: Option<Duration>,
+    vetoer: Option<Vetoer>,
+    max_voting_period: Duration,
+) -> Result<(Option<Duration>, Option<CheckedVetoer>), ContractError> {
+    let timelock = validate_timelock(timelock, max_voting_period)?;
+    let vetoer = vetoer.map(|vetoer| vetoer.into_checked(deps)).transpose()?;
+    if vetoer.is_some() && timelock.is_none() {
+        return Err(ContractError::VetoerWithoutTimelock {});
+    }
+    Ok((timelock, vetoer))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -88,10 +117,16 @@ pub fn execute(
             title,
             description,
             choices,
-        } => execute_propose(deps, env, info.sender, title, description, choices),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        } => execute_propose(deps, env, info, title, description, choices),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Withdraw { proposal_id } => execute_withdraw(deps, env, info, proposal_id),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             voting_strategy,
             min_voting_period,
@@ -101,6 +136,9 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
         } => execute_update_config(
             deps,
             info,
@@ -112,14 +150,49 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
+        ),
+        ExecuteMsg::AddProposalHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+            filter,
+        } => execute_add_proposal_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+            filter,
         ),
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
-        }
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+        } => execute_add_vote_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+        ),
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
@@ -129,12 +202,14 @@ pub fn execute(
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
-    sender: Addr,
+    info: MessageInfo,
     title: String,
     description: String,
     options: MultipleChoiceOptions,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    check_native_deposit_paid(&config.deposit_info, &info)?;
+    let sender = info.sender;
 
     let voting_module: Addr = deps
         .querier
@@ -165,6 +240,11 @@ pub fn execute_propose(
 
     let expiration = config.max_voting_period.after(&env.block);
     let total_power = get_total_power(deps.as_ref(), config.dao, None)?;
+    let curved_power = if config.voting_power_curve.is_linear() {
+        None
+    } else {
+        Some(CurvedPower::new(config.voting_power_curve, total_power))
+    };
 
     let proposal = {
         // Limit mutability to this block.
@@ -175,10 +255,15 @@ pub fn execute_propose(
             start_height: env.block.height,
             min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
             expiration,
+            timelock: config.timelock,
+            timelock_expiration: None,
             voting_strategy: config.voting_strategy,
-            total_power,
+            total_power: config.voting_power_curve.apply(total_power),
+            curved_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            // A new proposal has no ballots.
+            ranked_ballots: Some(vec![]),
             allow_revoting: config.allow_revoting,
             deposit_info: config.deposit_info.clone(),
             choices: checked_multiple_choice_options,
@@ -217,7 +302,13 @@ pub fn execute_propose(
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
     let deposit_msg = get_deposit_msg(&config.deposit_info, &env.contract.address, &sender)?;
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id)?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        &env.contract.address,
+        &proposal.msgs(),
+    )?;
     Ok(Response::default()
         .add_messages(deposit_msg)
         .add_submessages(hooks)
@@ -233,14 +324,17 @@ pub fn execute_vote(
     info: MessageInfo,
     proposal_id: u64,
     vote: MultipleChoiceVote,
+    rationale: Option<String>,
 ) -> Result<Response<Empty>, ContractError> {
+    let rationale = validate_rationale(rationale)?;
     let config = CONFIG.load(deps.storage)?;
     let mut prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    prop.load_ranked_ballots_for_status(deps.storage, proposal_id, &env.block)?;
 
     // Check that this is a valid vote.
-    if vote.option_id as usize >= prop.choices.len() {
+    if !prop.is_valid_vote(&vote) {
         return Err(ContractError::InvalidVote {});
     }
 
@@ -257,7 +351,9 @@ pub fn execute_vote(
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
+    let vote_weight = prop.vote_weight(vote_power);
 
+    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, info.sender.clone()))?;
     BALLOTS.update(
         deps.storage,
         (proposal_id, info.sender.clone()),
@@ -271,11 +367,11 @@ pub fn execute_vote(
                         Err(ContractError::AlreadyCast {})
                     } else {
                         // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power)?;
+                        prop.remove_vote(&current_ballot.vote, current_ballot.power)?;
                         Ok(Ballot {
-                            power: vote_power,
-                            vote,
+                            power: vote_weight,
+                            vote: vote.clone(),
+                            rationale: rationale.clone(),
                         })
                     }
                 } else {
@@ -283,31 +379,49 @@ pub fn execute_vote(
                 }
             }
             None => Ok(Ballot {
-                vote,
-                power: vote_power,
+                vote: vote.clone(),
+                power: vote_weight,
+                rationale: rationale.clone(),
             }),
         },
     )?;
 
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power)?;
-    prop.update_status(&env.block)?;
+    if let VotingStrategy::RankedChoice { .. } = prop.voting_strategy {
+        if let Some(ballot) = &ballot {
+            remove_ranked_ballot(deps.storage, proposal_id, &ballot.vote, ballot.power)?;
+        }
+        add_ranked_ballot(deps.storage, proposal_id, &vote, vote_weight)?;
+    }
+    prop.add_vote(&vote, vote_weight)?;
+    if ballot.is_none() {
+        prop.add_voter(vote_power);
+    }
+    prop.load_ranked_ballots_for_status(deps.storage, proposal_id, &env.block)?;
+    prop.update_status_after_vote(&env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
-        old_status.to_string(),
-        new_status.to_string(),
+        old_status,
+        new_status,
+        &env.contract.address,
+        &prop.msgs(),
     )?;
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
         deps.storage,
-        proposal_id,
-        info.sender.to_string(),
-        vote.to_string(),
+        VoteHookMsgV2::NewVote {
+            proposal_id,
+            voter: info.sender.clone(),
+            vote: vote.clone(),
+            power: vote_power,
+            rationale,
+            module: env.contract.address.clone(),
+        },
     )?;
     Ok(Response::default()
         .add_submessages(change_hooks)
@@ -341,6 +455,9 @@ pub fn execute_execute(
     let mut prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    // The winning option of a ranked choice proposal is decided by
+    // its runoff, which is needed even if it has already passed.
+    prop.load_ranked_ballots(deps.storage, proposal_id)?;
 
     // Check here that the proposal is passed. Allow it to be
     // executed even if it is expired so long as it passed during its
@@ -350,6 +467,11 @@ pub fn execute_execute(
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
+    if let Some(expiration) = prop.timelock_expiration {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::Timelocked { expiration });
+        }
+    }
 
     prop.status = Status::Executed;
     // Update proposal's last updated timestamp.
@@ -358,59 +480,61 @@ pub fn execute_execute(
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
     let refund_message = match &prop.deposit_info {
-        Some(deposit_info) => get_return_deposit_msg(deposit_info, &prop.proposer)?,
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, true)?
+        }
         None => vec![],
     };
 
-    let vote_result = prop.calculate_vote_result()?;
-    match vote_result {
-        VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
-        VoteResult::SingleWinner(winning_choice) => {
-            let response = match winning_choice.msgs {
-                Some(msgs) => {
-                    if !msgs.is_empty() {
-                        let execute_message = WasmMsg::Execute {
-                            contract_addr: config.dao.to_string(),
-                            msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
-                                msgs,
-                            })?,
-                            funds: vec![],
-                        };
-                        match config.close_proposal_on_execution_failure {
-                            true => {
-                                let masked_proposal_id =
-                                    mask_proposal_execution_proposal_id(proposal_id);
-                                Response::default().add_submessage(SubMsg::reply_on_error(
-                                    execute_message,
-                                    masked_proposal_id,
-                                ))
-                            }
-                            false => Response::default().add_message(execute_message),
-                        }
-                    } else {
-                        Response::default()
-                    }
-                }
-                None => Response::default(),
-            };
-
-            let hooks = proposal_status_changed_hooks(
-                PROPOSAL_HOOKS,
-                deps.storage,
-                proposal_id,
-                old_status.to_string(),
-                prop.status.to_string(),
-            )?;
-
-            Ok(response
-                .add_messages(refund_message)
-                .add_submessages(hooks)
-                .add_attribute("action", "execute")
-                .add_attribute("sender", info.sender)
-                .add_attribute("proposal_id", proposal_id.to_string())
-                .add_attribute("dao", config.dao))
+    let msgs: Vec<CosmosMsg> = match prop.calculate_vote_result()? {
+        VoteResult::Tie => return Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
+        VoteResult::SingleWinner(winning_choice) => winning_choice.msgs.unwrap_or_default(),
+        // The messages of every winning option of an approval vote are
+        // executed, in order of the options.
+        VoteResult::Winners(mut winners) => {
+            winners.sort_by_key(|choice| choice.index);
+            winners
+                .into_iter()
+                .filter_map(|choice| choice.msgs)
+                .flatten()
+                .collect()
         }
-    }
+    };
+    let response = if !msgs.is_empty() {
+        let execute_message = WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        };
+        match config.close_proposal_on_execution_failure {
+            true => {
+                let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+                Response::default()
+                    .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+            }
+            false => Response::default().add_message(execute_message),
+        }
+    } else {
+        Response::default()
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs(),
+    )?;
+
+    Ok(response
+        .add_messages(refund_message)
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
 }
 
 pub fn execute_close(
@@ -420,6 +544,7 @@ pub fn execute_close(
     proposal_id: u64,
 ) -> Result<Response<Empty>, ContractError> {
     let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    prop.load_ranked_ballots(deps.storage, proposal_id)?;
     let config = CONFIG.load(deps.storage)?;
 
     prop.update_status(&env.block)?;
@@ -430,14 +555,7 @@ pub fn execute_close(
     let old_status = prop.status;
     let refund_message = match &prop.deposit_info {
         Some(deposit_info) => {
-            let receiver = if deposit_info.refund_failed_proposals {
-                &prop.proposer
-            } else {
-                // If we aren't refunding failed proposals then return
-                // the depost to the DAO treasury on close.
-                &config.dao
-            };
-            get_return_deposit_msg(deposit_info, receiver)?
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, false)?
         }
         None => vec![],
     };
@@ -451,8 +569,10 @@ pub fn execute_close(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
-        old_status.to_string(),
-        prop.status.to_string(),
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs(),
     )?;
 
     Ok(Response::default()
@@ -463,6 +583,114 @@ pub fn execute_close(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<Empty>, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+    prop.load_ranked_ballots_for_status(deps.storage, proposal_id, &env.block)?;
+    if prop.current_status(&env.block)? != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    if !prop.votes.total().is_zero() {
+        return Err(ContractError::WithdrawAfterVote {});
+    }
+
+    let old_status = prop.status;
+
+    let refund_message = match &prop.deposit_info {
+        Some(deposit_info) => get_return_deposit_msgs(deposit_info, &prop.proposer)?,
+        None => vec![],
+    };
+
+    prop.status = Status::Withdrawn;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_messages(refund_message)
+        .add_attribute("action", "withdraw")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<Empty>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_vetoer = match &config.vetoer {
+        Some(vetoer) => vetoer.is_vetoer(deps.as_ref(), &config.dao, &info.sender)?,
+        None => false,
+    };
+    if !is_vetoer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    prop.load_ranked_ballots_for_status(deps.storage, proposal_id, &env.block)?;
+    prop.update_status(&env.block)?;
+    if !prop.is_timelocked(&env.block) {
+        return Err(ContractError::NotTimelocked {});
+    }
+
+    let old_status = prop.status;
+
+    // Vetoed proposals are treated as failed proposals for the
+    // purposes of deposit refunds.
+    let refund_message = match &prop.deposit_info {
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, false)?
+        }
+        None => vec![],
+    };
+
+    prop.status = Status::Vetoed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_messages(refund_message)
+        .add_attribute("action", "veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
@@ -475,6 +703,9 @@ pub fn execute_update_config(
     dao: String,
     deposit_info: Option<DepositInfo>,
     close_proposal_on_execution_failure: bool,
+    voting_power_curve: VotingPowerCurve,
+    timelock: Option<Duration>,
+    vetoer: Option<Vetoer>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -484,6 +715,7 @@ pub fn execute_update_config(
     }
 
     voting_strategy.validate()?;
+    voting_power_curve.validate()?;
 
     let dao = deps.api.addr_validate(&dao)?;
     let deposit_info = deposit_info
@@ -492,6 +724,8 @@ pub fn execute_update_config(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
+    let (timelock, vetoer) =
+        validate_timelock_and_vetoer(deps.as_ref(), timelock, vetoer, max_voting_period)?;
 
     CONFIG.save(
         deps.storage,
@@ -504,6 +738,9 @@ pub fn execute_update_config(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
         },
     )?;
 
@@ -517,6 +754,8 @@ pub fn execute_add_proposal_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    options: HookOptions,
+    filter: Option<ProposalHookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -526,7 +765,11 @@ pub fn execute_add_proposal_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    let options = match filter {
+        Some(filter) => filter.apply(deps.api, options)?,
+        None => options,
+    };
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address, options)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -559,6 +802,7 @@ pub fn execute_add_vote_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    options: HookOptions,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -568,7 +812,7 @@ pub fn execute_add_vote_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(VOTE_HOOKS, deps.storage, validated_address, options)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -600,9 +844,10 @@ pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
     validated_address: Addr,
+    options: HookOptions,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook_with_options(storage, validated_address, options)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -644,8 +889,34 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_before,
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
-        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
-        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalHooks { start_after, limit } => to_binary(
+            &PROPOSAL_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::VoteHooks { start_after, limit } => to_binary(
+            &VOTE_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::DisabledProposalHooks {} => {
+            to_binary(&PROPOSAL_HOOKS.query_disabled_hooks(deps)?)
+        }
+        QueryMsg::DisabledVoteHooks {} => to_binary(&VOTE_HOOKS.query_disabled_hooks(deps)?),
+        QueryMsg::ProposalHookFailures { address } => to_binary(
+            &PROPOSAL_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookFailures { address } => {
+            to_binary(&VOTE_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?)
+        }
     }
 }
 
@@ -655,7 +926,8 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let mut proposal = PROPOSALS.load(deps.storage, id)?;
+    proposal.load_ranked_ballots(deps.storage, id)?;
     to_binary(&proposal.into_response(&env.block, id)?)
 }
 
@@ -672,7 +944,10 @@ pub fn query_list_proposals(
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, mut proposal)| {
+            proposal.load_ranked_ballots(deps.storage, id)?;
+            proposal.into_response(&env.block, id)
+        })
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -691,7 +966,10 @@ pub fn query_reverse_proposals(
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, mut proposal)| {
+            proposal.load_ranked_ballots(deps.storage, id)?;
+            proposal.into_response(&env.block, id)
+        })
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -709,6 +987,7 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         voter,
         vote: ballot.vote,
         power: ballot.power,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -735,6 +1014,7 @@ pub fn query_list_votes(
                 voter,
                 vote: ballot.vote,
                 power: ballot.power,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -764,12 +1044,16 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
             Ok(Response::new().add_attribute("proposal execution failed", proposal_id.to_string()))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
-            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed proposal hook", format!("{addr}:{idx}")))
+            let (addr, outcome) =
+                PROPOSAL_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} proposal hook"), format!("{addr}:{idx}")))
         }
         TaggedReplyId::FailedVoteHook(idx) => {
-            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
+            let (addr, outcome) =
+                VOTE_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} vote hook"), format!("{addr}:{idx}")))
         }
     }
 }
@@ -778,5 +1062,16 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Deposit info stored before refund policies were added has a
+    // `refund_failed_proposals` bool which is read as the equivalent
+    // policy. Resave the config so that it is stored with the
+    // policy. Proposals are read the same way and are left as is.
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+    // Hooks were stored as a single list before they had stable
+    // indices.
+    PROPOSAL_HOOKS.migrate_legacy_hooks(deps.storage)?;
+    VOTE_HOOKS.migrate_legacy_hooks(deps.storage)?;
     Ok(Response::default())
 }
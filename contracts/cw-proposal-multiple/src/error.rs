@@ -1,6 +1,5 @@
-use std::u64;
-
 use cosmwasm_std::StdError;
+use cw_utils::Expiration;
 use indexable_hooks::HookError;
 use thiserror::Error;
 use voting::{reply::error::TagError, threshold::ThresholdError};
@@ -73,9 +72,27 @@ pub enum ContractError {
     #[error("Invalid vote selected.")]
     InvalidVote {},
 
+    #[error("Approval voting must allow at least one selection.")]
+    ZeroMaxSelections {},
+
+    #[error("Approval voting must have at least one winner.")]
+    ZeroWinners {},
+
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
+    #[error("Proposal is timelocked until ({expiration})")]
+    Timelocked { expiration: Expiration },
+
+    #[error("Only proposals in their timelock may be vetoed")]
+    NotTimelocked {},
+
+    #[error("A vetoer may only be set if proposals are timelocked")]
+    VetoerWithoutTimelock {},
+
+    #[error("Proposals may not be withdrawn once they have been voted on")]
+    WithdrawAfterVote {},
+
     #[error("{0}")]
     Tag(#[from] TagError),
 }
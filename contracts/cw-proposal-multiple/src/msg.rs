@@ -1,7 +1,11 @@
 use cw_utils::Duration;
+use indexable_hooks::{HookFailurePolicy, HookVersion};
+use proposal_hooks::ProposalHookFilter;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use voting::{deposit::DepositInfo, voting::MultipleChoiceVote};
+use voting::{
+    curve::VotingPowerCurve, deposit::DepositInfo, veto::Vetoer, voting::MultipleChoiceVote,
+};
 
 use crate::{state::MultipleChoiceOptions, voting_strategy::VotingStrategy};
 use cw_core_macros::govmod_query;
@@ -39,6 +43,23 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The curve applied to voting power before votes are
+    /// counted. For example, `root { degree: 2 }` enables quadratic
+    /// voting. Proposals using a non-linear curve may not complete
+    /// until they expire as the weight of outstanding votes is not
+    /// known. The curved total power is estimated as described on
+    /// `CurvedPower`.
+    #[serde(default)]
+    pub voting_power_curve: VotingPowerCurve,
+    /// The amount of time after a proposal's voting period ends
+    /// before it may be executed, even if it passed early. This gives
+    /// members a chance to react to passed proposals before they are
+    /// executed. Must have the same units as `max_voting_period`.
+    pub timelock: Option<Duration>,
+    /// Who may veto passed proposals during their timelock, for
+    /// example this DAO's parent DAO. May only be set if `timelock`
+    /// is set.
+    pub vetoer: Option<Vetoer>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -60,6 +81,11 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         /// The senders position on the proposal.
         vote: MultipleChoiceVote,
+        /// An optional explanation of the vote, at most
+        /// `MAX_RATIONALE_LENGTH` bytes long. Stored alongside the
+        /// ballot and included in vote hook messages.
+        #[serde(default)]
+        rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
@@ -74,6 +100,20 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Withdraws a proposal. May only be called by the proposal's
+    /// proposer while the proposal is open and has not been voted
+    /// on. The proposal deposit is refunded in full.
+    Withdraw {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+    },
+    /// Vetoes a passed proposal during its timelock. May only be
+    /// called by the vetoer. The proposal deposit is handled as if
+    /// the proposal had been closed.
+    Veto {
+        /// The ID of the proposal to veto.
+        proposal_id: u64,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal voting strategy. This will only apply
@@ -112,15 +152,55 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// The curve applied to voting power before votes are
+        /// counted. This will only apply to proposals created after
+        /// the config update.
+        #[serde(default)]
+        voting_power_curve: VotingPowerCurve,
+        /// The amount of time after a proposal's voting period ends
+        /// before it may be executed. This will only apply to
+        /// proposals created after the config update.
+        timelock: Option<Duration>,
+        /// Who may veto passed proposals during their timelock.
+        /// Applies to all outstanding and future proposals.
+        vetoer: Option<Vetoer>,
     },
     AddProposalHook {
         address: String,
+        /// The version of the proposal hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+        /// Limits the messages sent to ADDRESS. Defaults to sending
+        /// every message.
+        #[serde(default)]
+        filter: Option<ProposalHookFilter>,
     },
     RemoveProposalHook {
         address: String,
     },
     AddVoteHook {
         address: String,
+        /// The version of the vote hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
     },
     RemoveVoteHook {
         address: String,
@@ -156,8 +236,22 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u64>,
     },
-    ProposalHooks {},
-    VoteHooks {},
+    ProposalHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    VoteHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    DisabledProposalHooks {},
+    DisabledVoteHooks {},
+    ProposalHookFailures {
+        address: String,
+    },
+    VoteHookFailures {
+        address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
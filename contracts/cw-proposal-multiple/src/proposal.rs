@@ -1,17 +1,21 @@
-use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Timestamp, Uint128};
-use cw_utils::Expiration;
+use cosmwasm_std::{
+    Addr, BlockInfo, CosmosMsg, StdError, StdResult, Storage, Timestamp, Uint128, Uint256,
+};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use voting::{
+    curve::CurvedPower,
     deposit::CheckedDepositInfo,
     proposal::Proposal,
     status::Status,
-    voting::{does_vote_count_pass, MultipleChoiceVotes},
+    timelock::{block_expiration, passed_at, remaining},
+    voting::{does_vote_count_pass, MultipleChoiceVote, MultipleChoiceVotes, RankedBallots},
 };
 
 use crate::{
     query::ProposalResponse,
-    state::{CheckedMultipleChoiceOption, MultipleChoiceOptionType},
+    state::{load_ranked_ballots, CheckedMultipleChoiceOption, MultipleChoiceOptionType},
     voting_strategy::VotingStrategy,
 };
 
@@ -26,12 +30,33 @@ pub struct MultipleChoiceProposal {
     /// None.
     pub min_voting_period: Option<Expiration>,
     pub expiration: Expiration,
+    /// The amount of time this proposal must wait after passing
+    /// before it may be executed. None if the proposal is not
+    /// timelocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timelock: Option<Duration>,
+    /// The time at which this proposal's timelock ends. Set once the
+    /// proposal has passed to `timelock` after the point at which it
+    /// passed. The proposal may not be executed until then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timelock_expiration: Option<Expiration>,
     pub choices: Vec<CheckedMultipleChoiceOption>,
     pub status: Status,
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
+    /// Uncurved voting power information if votes on this proposal
+    /// are weighted by a non-linear voting power curve. If set,
+    /// `votes` and `total_power` are curved weights and
+    /// `total_power` is recomputed after each vote.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curved_power: Option<CurvedPower>,
     pub votes: MultipleChoiceVotes,
+    /// The ballots of a ranked choice proposal grouped by their order
+    /// of preference. Not stored with the proposal, see
+    /// `load_ranked_ballots`.
+    #[serde(skip)]
+    pub ranked_ballots: Option<Vec<RankedBallots>>,
     pub allow_revoting: bool,
     /// Information about the deposit that was sent as part of this
     /// proposal. None if no deposit.
@@ -46,9 +71,21 @@ pub struct MultipleChoiceProposal {
 
 pub enum VoteResult {
     SingleWinner(CheckedMultipleChoiceOption),
+    /// The winners of an approval vote. Empty if no option won.
+    Winners(Vec<CheckedMultipleChoiceOption>),
     Tie,
 }
 
+/// The tally of a single round of an instant runoff.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RunoffRound {
+    /// The voting power held by each option this round. The index
+    /// corresponds to the option. Eliminated options hold none.
+    pub vote_weights: Vec<Uint128>,
+    /// The options that were eliminated at the end of this round.
+    pub eliminated: Vec<u32>,
+}
+
 impl Proposal for MultipleChoiceProposal {
     fn proposer(&self) -> Addr {
         self.proposer.clone()
@@ -71,7 +108,111 @@ impl MultipleChoiceProposal {
     /// information.
     pub fn into_response(mut self, block: &BlockInfo, id: u64) -> StdResult<ProposalResponse> {
         self.update_status(block)?;
-        Ok(ProposalResponse { id, proposal: self })
+        let runoff = match self.voting_strategy {
+            VotingStrategy::SingleChoice { .. } | VotingStrategy::Approval { .. } => None,
+            VotingStrategy::RankedChoice { .. } => Some(self.calculate_runoff()?.0),
+        };
+        let timelock_remaining = match self.timelock_expiration {
+            Some(expiration) if self.status == Status::Passed => remaining(expiration, block),
+            _ => None,
+        };
+        Ok(ProposalResponse {
+            id,
+            proposal: self,
+            runoff,
+            timelock_remaining,
+        })
+    }
+
+    /// Returns the messages of every choice in this proposal.
+    pub fn msgs(&self) -> Vec<CosmosMsg> {
+        self.choices
+            .iter()
+            .filter_map(|choice| choice.msgs.clone())
+            .flatten()
+            .collect()
+    }
+
+    /// Returns true if `vote` selects valid options for this
+    /// proposal's voting strategy.
+    pub fn is_valid_vote(&self, vote: &MultipleChoiceVote) -> bool {
+        let num_choices = self.choices.len() as u32;
+        match self.voting_strategy {
+            VotingStrategy::SingleChoice { .. } => {
+                vote.option_id < num_choices && vote.rankings.is_empty()
+            }
+            VotingStrategy::RankedChoice { .. } => {
+                let preferences = vote.preferences();
+                preferences.iter().enumerate().all(|(idx, option_id)| {
+                    *option_id < num_choices && !preferences[..idx].contains(option_id)
+                })
+            }
+            VotingStrategy::Approval { max_selections, .. } => {
+                let approvals = vote.preferences();
+                // Approving of "none of the above" alongside another
+                // option is contradictory.
+                let approves_none = approvals.iter().any(|option_id| {
+                    self.choices
+                        .get(*option_id as usize)
+                        .map(|choice| choice.option_type == MultipleChoiceOptionType::None)
+                        .unwrap_or_default()
+                });
+                approvals.len() <= max_selections as usize
+                    && !(approves_none && approvals.len() > 1)
+                    && approvals.iter().enumerate().all(|(idx, option_id)| {
+                        *option_id < num_choices && !approvals[..idx].contains(option_id)
+                    })
+            }
+        }
+    }
+
+    /// Adds a vote to the proposal's tally. Ranked votes count towards
+    /// their first preference, their full ordering must be recorded
+    /// with `add_ranked_ballot`.
+    pub fn add_vote(&mut self, vote: &MultipleChoiceVote, power: Uint128) -> StdResult<()> {
+        match self.voting_strategy {
+            VotingStrategy::SingleChoice { .. } | VotingStrategy::RankedChoice { .. } => {
+                self.votes.add_vote(vote, power)
+            }
+            VotingStrategy::Approval { .. } => self.votes.add_approval_vote(vote, power),
+        }
+    }
+
+    /// Removes a vote that was previously added to the proposal's
+    /// tally.
+    pub fn remove_vote(&mut self, vote: &MultipleChoiceVote, power: Uint128) -> StdResult<()> {
+        match self.voting_strategy {
+            VotingStrategy::SingleChoice { .. } | VotingStrategy::RankedChoice { .. } => {
+                self.votes.remove_vote(vote, power)
+            }
+            VotingStrategy::Approval { .. } => self.votes.remove_approval_vote(vote, power),
+        }
+    }
+
+    /// Loads the ballots of a ranked choice proposal with ID from
+    /// storage. Must be called before its runoff is calculated.
+    pub fn load_ranked_ballots(&mut self, storage: &dyn Storage, id: u64) -> StdResult<()> {
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            self.ranked_ballots = Some(load_ranked_ballots(storage, id)?);
+        }
+        Ok(())
+    }
+
+    /// Loads the ballots of a ranked choice proposal with ID only if
+    /// its status at BLOCK depends on the outcome of its runoff, so
+    /// that the ballots are not read on every vote. Proposals that
+    /// are no longer open must use `load_ranked_ballots` before their
+    /// result is calculated.
+    pub fn load_ranked_ballots_for_status(
+        &mut self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+    ) -> StdResult<()> {
+        if self.status == Status::Open && !self.is_outcome_pending(block) {
+            self.load_ranked_ballots(storage, id)?;
+        }
+        Ok(())
     }
 
     /// Gets the current status of the proposal.
@@ -87,17 +228,88 @@ impl MultipleChoiceProposal {
         }
     }
 
-    /// Sets a proposals status to its current status.
+    /// Sets a proposals status to its current status. Must not be
+    /// used after voting on the proposal, see
+    /// `update_status_after_vote`.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
+        self.set_status(block, |prop| {
+            passed_at(prop.min_voting_period, prop.expiration, block, |at| {
+                prop.is_passed(at)
+            })
+        })
+    }
+
+    /// Sets a proposal's status to its current status after a vote
+    /// has been cast on it. If the proposal has passed it passed
+    /// because of the vote.
+    pub fn update_status_after_vote(&mut self, block: &BlockInfo) -> StdResult<()> {
+        self.set_status(block, |prop| Ok(block_expiration(&prop.expiration, block)))
+    }
+
+    /// Sets a proposal's status to its current status and, if it has
+    /// just passed, starts its timelock from the point returned by
+    /// PASSED_AT.
+    fn set_status<F>(&mut self, block: &BlockInfo, passed_at: F) -> StdResult<()>
+    where
+        F: FnOnce(&Self) -> StdResult<Expiration>,
+    {
         let new_status = self.current_status(block)?;
         // Update last_updated only if status changed.
         if new_status != self.status {
             self.last_updated = block.time
         }
+        if new_status == Status::Passed && self.status == Status::Open {
+            if let Some(timelock) = self.timelock {
+                self.timelock_expiration = Some((passed_at(self)? + timelock)?);
+            }
+        }
         self.status = new_status;
         Ok(())
     }
 
+    /// Returns true if this proposal has passed but may not be
+    /// executed yet as its timelock has not ended. The proposal's
+    /// status must have been updated.
+    pub fn is_timelocked(&self, block: &BlockInfo) -> bool {
+        self.status == Status::Passed
+            && self
+                .timelock_expiration
+                .is_some_and(|expiration| !expiration.is_expired(block))
+    }
+
+    /// Returns the weight of a vote cast with POWER voting power.
+    pub fn vote_weight(&self, power: Uint128) -> Uint128 {
+        match &self.curved_power {
+            Some(curved_power) => curved_power.curve.apply(power),
+            None => power,
+        }
+    }
+
+    /// Records that an address with POWER voting power has voted for
+    /// the first time and recomputes the proposal's total power if
+    /// its votes are curved. Must be called after the address' vote
+    /// has been added to the proposal.
+    pub fn add_voter(&mut self, power: Uint128) {
+        if let Some(curved_power) = &mut self.curved_power {
+            curved_power.add_voter(power);
+            self.total_power = curved_power.total_power(self.votes.total());
+        }
+    }
+
+    /// Returns true if votes may change the outcome of this proposal
+    /// in ways that can not be known until it expires. This is the
+    /// case if revoting is enabled or votes are weighted by a
+    /// non-linear curve, in which case the weight of outstanding
+    /// votes is unknown. The outcome of a ranked choice proposal is
+    /// also pending until all voting power has been cast, as
+    /// outstanding ballots can change which options are eliminated.
+    fn is_outcome_pending(&self, block: &BlockInfo) -> bool {
+        let ranked_pending = matches!(self.voting_strategy, VotingStrategy::RankedChoice { .. })
+            && self.votes.total() < self.total_power;
+        (self.allow_revoting || self.curved_power.is_some() || ranked_pending)
+            && !self.expiration.is_expired(block)
+    }
+
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail). Passing in the case of multiple choice proposals
@@ -105,9 +317,9 @@ impl MultipleChoiceProposal {
     /// one of the options that is not "None of the above"
     /// has won the most votes, and there is no tie.
     pub fn is_passed(&self, block: &BlockInfo) -> StdResult<bool> {
-        // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed or votes are curved nothing is
+        // known until the proposal has expired.
+        if self.is_outcome_pending(block) {
             return Ok(false);
         }
         // If the min voting period is set and not expired the
@@ -121,6 +333,12 @@ impl MultipleChoiceProposal {
             }
         }
 
+        if let VotingStrategy::Approval { .. } = self.voting_strategy {
+            let winners = self.approval_winners();
+            return Ok(!winners.is_empty()
+                && (self.expiration.is_expired(block) || self.are_winners_final(&winners)));
+        }
+
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
             self.votes.total(),
@@ -130,7 +348,7 @@ impl MultipleChoiceProposal {
             let vote_result = self.calculate_vote_result()?;
             match vote_result {
                 // Proposal is not passed if there is a tie.
-                VoteResult::Tie => return Ok(false),
+                VoteResult::Tie | VoteResult::Winners(_) => return Ok(false),
                 VoteResult::SingleWinner(winning_choice) => {
                     // Proposal is not passed if winning choice is None.
                     if winning_choice.option_type != MultipleChoiceOptionType::None {
@@ -150,12 +368,30 @@ impl MultipleChoiceProposal {
     }
 
     pub fn is_rejected(&self, block: &BlockInfo) -> StdResult<bool> {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed or votes are curved and the
+        // proposal is not expired no information is known.
+        if self.is_outcome_pending(block) {
             return Ok(false);
         }
 
+        if let VotingStrategy::Approval { .. } = self.voting_strategy {
+            // Before expiration an approval vote is rejected once no
+            // option can win, either because all voting power has
+            // been cast or because no option can gain more approvals
+            // than "None of the above".
+            let remaining = self.total_power - self.votes.total();
+            let none_weight = self.none_of_the_above_weight();
+            let none_unbeatable = self
+                .choices
+                .iter()
+                .filter(|choice| choice.option_type == MultipleChoiceOptionType::Standard)
+                .all(|choice| {
+                    none_weight > self.votes.vote_weights[choice.index as usize] + remaining
+                });
+            return Ok(self.approval_winners().is_empty()
+                && (self.expiration.is_expired(block) || remaining.is_zero() || none_unbeatable));
+        }
+
         let vote_result = self.calculate_vote_result()?;
         match vote_result {
             // Proposal is rejected if there is a tie, and either the proposal is expired or
@@ -165,6 +401,7 @@ impl MultipleChoiceProposal {
                     self.expiration.is_expired(block) || self.total_power == self.votes.total();
                 Ok(rejected)
             }
+            VoteResult::Winners(winners) => Ok(winners.is_empty()),
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
@@ -198,9 +435,11 @@ impl MultipleChoiceProposal {
         }
     }
 
-    /// Find the option with the highest vote weight, and note if there is a tie.
+    /// Find the option with the highest vote weight, and note if there
+    /// is a tie. Approval votes return all of their winners.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
+            VotingStrategy::Approval { .. } => Ok(VoteResult::Winners(self.approval_winners())),
             VotingStrategy::SingleChoice { quorum: _ } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
@@ -230,6 +469,169 @@ impl MultipleChoiceProposal {
                 }
                 Err(StdError::not_found("max vote weight"))
             }
+            VotingStrategy::RankedChoice { quorum: _ } => Ok(self.calculate_runoff()?.1),
+        }
+    }
+
+    /// Returns the winners of an approval vote: up to `winners`
+    /// standard options with the most approvals, each of which was
+    /// approved by a quorum of voting power and by more voting power
+    /// than "None of the above". If options are tied for the last
+    /// winning place they are all left out. Returns no winners for
+    /// other voting strategies.
+    pub fn approval_winners(&self) -> Vec<CheckedMultipleChoiceOption> {
+        let (quorum, winners) = match self.voting_strategy {
+            VotingStrategy::Approval {
+                quorum, winners, ..
+            } => (quorum, winners as usize),
+            _ => return vec![],
+        };
+        let none_weight = self.none_of_the_above_weight();
+        let mut candidates: Vec<(Uint128, &CheckedMultipleChoiceOption)> = self
+            .choices
+            .iter()
+            .filter(|choice| choice.option_type == MultipleChoiceOptionType::Standard)
+            .map(|choice| (self.votes.vote_weights[choice.index as usize], choice))
+            .filter(|(weight, _)| {
+                *weight > none_weight && does_vote_count_pass(*weight, self.total_power, quorum)
+            })
+            .collect();
+        candidates.sort_by_key(|(weight, _)| std::cmp::Reverse(*weight));
+
+        if candidates.len() > winners && candidates[winners].0 == candidates[winners - 1].0 {
+            let cutoff = candidates[winners].0;
+            candidates.retain(|(weight, _)| *weight > cutoff);
+        }
+        candidates
+            .into_iter()
+            .take(winners)
+            .map(|(_, choice)| choice.clone())
+            .collect()
+    }
+
+    /// Returns true if the winners of an approval vote can not change
+    /// with the voting power that is yet to be cast. This is the case
+    /// if all voting power has been cast, or if every place has been
+    /// filled and no other option can catch up with any of the
+    /// winners.
+    fn are_winners_final(&self, winners: &[CheckedMultipleChoiceOption]) -> bool {
+        let remaining = self.total_power - self.votes.total();
+        if remaining.is_zero() {
+            return true;
+        }
+        let places = match self.voting_strategy {
+            VotingStrategy::Approval { winners, .. } => winners as usize,
+            _ => return false,
+        };
+        let lowest_winner = winners
+            .iter()
+            .map(|choice| self.votes.vote_weights[choice.index as usize])
+            .min();
+        let highest_other = self
+            .choices
+            .iter()
+            .filter(|choice| !winners.iter().any(|winner| winner.index == choice.index))
+            .map(|choice| self.votes.vote_weights[choice.index as usize])
+            .max()
+            .unwrap_or_default();
+        match lowest_winner {
+            Some(lowest_winner) => {
+                winners.len() == places && lowest_winner > highest_other + remaining
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the vote weight of this proposal's "None of the above"
+    /// option.
+    fn none_of_the_above_weight(&self) -> Uint128 {
+        self.choices
+            .iter()
+            .find(|choice| choice.option_type == MultipleChoiceOptionType::None)
+            .map(|choice| self.votes.vote_weights[choice.index as usize])
+            .unwrap_or_default()
+    }
+
+    /// Runs an instant runoff over the proposal's ranked ballots. Each
+    /// round every ballot counts towards its most preferred option
+    /// that has not been eliminated. If an option holds a majority of
+    /// the counted ballots it wins, otherwise the option(s) with the
+    /// fewest votes are eliminated and another round is run. If every
+    /// remaining option is tied the runoff ends in a tie.
+    ///
+    /// "None of the above" takes part in the runoff like any other
+    /// option. Ballots ranking it are transferred to their next
+    /// preference if it is eliminated, and the proposal is rejected if
+    /// it wins.
+    ///
+    /// Returns the tally of each round along with the final result.
+    /// The proposal's ranked ballots must have been loaded with
+    /// `load_ranked_ballots`.
+    pub fn calculate_runoff(&self) -> StdResult<(Vec<RunoffRound>, VoteResult)> {
+        let ballots = self
+            .ranked_ballots
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("ranked ballots not loaded"))?;
+        let mut eliminated = vec![false; self.choices.len()];
+        let mut rounds = vec![];
+        loop {
+            let mut vote_weights = vec![Uint128::zero(); self.choices.len()];
+            for ballots in ballots.iter() {
+                // Ballots with all their preferences eliminated are
+                // exhausted and no longer counted.
+                if let Some(option_id) = ballots
+                    .preferences
+                    .iter()
+                    .find(|option_id| !eliminated[**option_id as usize])
+                {
+                    vote_weights[*option_id as usize] += ballots.weight;
+                }
+            }
+            let counted: Uint128 = vote_weights.iter().sum();
+            let remaining: Vec<usize> = (0..self.choices.len())
+                .filter(|idx| !eliminated[*idx])
+                .collect();
+
+            if let Some(winner) = remaining
+                .iter()
+                .find(|idx| vote_weights[**idx].full_mul(2u64) > Uint256::from(counted))
+            {
+                let winner = self.choices[*winner].clone();
+                rounds.push(RunoffRound {
+                    vote_weights,
+                    eliminated: vec![],
+                });
+                return Ok((rounds, VoteResult::SingleWinner(winner)));
+            }
+
+            let min_weight = remaining
+                .iter()
+                .map(|idx| vote_weights[*idx])
+                .min()
+                .unwrap_or_default();
+            let losers: Vec<usize> = remaining
+                .iter()
+                .copied()
+                .filter(|idx| vote_weights[*idx] == min_weight)
+                .collect();
+
+            // Eliminating every remaining option would leave no
+            // winner.
+            if losers.len() == remaining.len() {
+                rounds.push(RunoffRound {
+                    vote_weights,
+                    eliminated: vec![],
+                });
+                return Ok((rounds, VoteResult::Tie));
+            }
+
+            for idx in losers.iter() {
+                eliminated[*idx] = true;
+            }
+            rounds.push(RunoffRound {
+                vote_weights,
+                eliminated: losers.into_iter().map(|idx| idx as u32).collect(),
+            });
         }
     }
 
@@ -239,6 +641,13 @@ impl MultipleChoiceProposal {
         &self,
         winning_choice: &CheckedMultipleChoiceOption,
     ) -> StdResult<bool> {
+        // Outstanding ballots may rank options in any order and
+        // change which options are eliminated, so the outcome of a
+        // runoff is only certain once all voting power has been cast.
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            return Ok(self.votes.total() == self.total_power);
+        }
+
         let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
         if let Some(second_choice_power) = self
             .votes
@@ -273,11 +682,14 @@ impl MultipleChoiceProposal {
 
 #[cfg(test)]
 mod tests {
-    use crate::state::{MultipleChoiceOption, MultipleChoiceOptions};
+    use crate::state::{add_ranked_ballot, MultipleChoiceOption, MultipleChoiceOptions};
 
     use super::*;
 
-    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{
+        testing::{mock_env, MockStorage},
+        Decimal,
+    };
 
     fn create_proposal(
         block: &BlockInfo,
@@ -312,12 +724,16 @@ mod tests {
             proposer: Addr::unchecked("CREATOR"),
             start_height: mock_env().block.height,
             expiration,
+            timelock: None,
+            timelock_expiration: None,
             // The last option that gets added in into_checked is always the none of the above option
             choices: mc_options.into_checked().unwrap().options,
             status: Status::Open,
             voting_strategy,
             total_power,
+            curved_power: None,
             votes,
+            ranked_ballots: None,
             allow_revoting,
             deposit_info: None,
             min_voting_period: None,
@@ -335,6 +751,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -352,6 +769,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(0), Uint128::new(1)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -368,6 +786,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -384,6 +803,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -400,6 +820,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -416,6 +837,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -442,6 +864,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -459,6 +882,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(0), Uint128::new(1)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -475,6 +899,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -491,6 +916,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -507,6 +933,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -523,6 +950,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -548,6 +976,7 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(50), Uint128::new(500)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -573,6 +1002,7 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(10), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -596,6 +1026,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(999999), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -619,6 +1050,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(9888889), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -644,6 +1076,7 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(7), Uint128::new(0), Uint128::new(6)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -681,6 +1114,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(7), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
         let prop = create_proposal(
             &env.block,
@@ -719,6 +1153,7 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(6), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -754,6 +1189,7 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(5), Uint128::new(5), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -796,6 +1232,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(81), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -821,6 +1258,308 @@ mod tests {
         assert!(prop.is_passed(&env.block).unwrap());
     }
 
+    #[test]
+    fn test_ranked_choice_runoff() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+        let mut storage = MockStorage::new();
+        for (option_id, rankings, power) in [(0, vec![], 40), (1, vec![], 35), (2, vec![1], 25)] {
+            let vote = MultipleChoiceVote {
+                option_id,
+                rankings,
+            };
+            prop.add_vote(&vote, Uint128::new(power)).unwrap();
+            add_ranked_ballot(&mut storage, 1, &vote, Uint128::new(power)).unwrap();
+        }
+        prop.load_ranked_ballots(&storage, 1).unwrap();
+
+        // Option 0 has the most first preferences but loses once the
+        // "none of the above" ballots transfer to option 1.
+        let (rounds, result) = prop.calculate_runoff().unwrap();
+        assert_eq!(
+            rounds,
+            vec![
+                RunoffRound {
+                    vote_weights: vec![Uint128::new(40), Uint128::new(35), Uint128::new(25)],
+                    eliminated: vec![2],
+                },
+                RunoffRound {
+                    vote_weights: vec![Uint128::new(40), Uint128::new(60), Uint128::zero()],
+                    eliminated: vec![],
+                }
+            ]
+        );
+        assert!(matches!(result, VoteResult::SingleWinner(choice) if choice.index == 1));
+
+        // All voting power has been cast so the result is final.
+        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_ranked_choice_exhausted_ballots() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(150),
+            false,
+            false,
+        );
+        let mut storage = MockStorage::new();
+        for (option_id, power) in [(0, 40), (1, 35), (2, 25)] {
+            let vote = MultipleChoiceVote {
+                option_id,
+                rankings: vec![],
+            };
+            prop.add_vote(&vote, Uint128::new(power)).unwrap();
+            add_ranked_ballot(&mut storage, 1, &vote, Uint128::new(power)).unwrap();
+        }
+        prop.load_ranked_ballots(&storage, 1).unwrap();
+
+        // Ballots that only rank "none of the above" are exhausted
+        // after it is eliminated, leaving option 0 with a majority of
+        // the remaining ballots.
+        let (rounds, result) = prop.calculate_runoff().unwrap();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(
+            rounds[1].vote_weights,
+            vec![Uint128::new(40), Uint128::new(35), Uint128::zero()]
+        );
+        assert!(matches!(result, VoteResult::SingleWinner(choice) if choice.index == 0));
+
+        // Outstanding voting power could still change the result.
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        prop.expiration = Expiration::AtHeight(env.block.height - 1);
+        assert!(prop.is_passed(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_ranked_choice_tie() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+        let mut storage = MockStorage::new();
+        for (option_id, power) in [(0, 50), (1, 50)] {
+            let vote = MultipleChoiceVote {
+                option_id,
+                rankings: vec![],
+            };
+            prop.add_vote(&vote, Uint128::new(power)).unwrap();
+            add_ranked_ballot(&mut storage, 1, &vote, Uint128::new(power)).unwrap();
+        }
+        prop.load_ranked_ballots(&storage, 1).unwrap();
+
+        let (rounds, result) = prop.calculate_runoff().unwrap();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].eliminated, vec![2]);
+        assert!(matches!(result, VoteResult::Tie));
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(prop.is_rejected(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_ranked_choice_valid_votes() {
+        let env = mock_env();
+        let ranked = create_proposal(
+            &env.block,
+            VotingStrategy::RankedChoice {
+                quorum: voting::threshold::PercentageThreshold::Majority {},
+            },
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+        let single = create_proposal(
+            &env.block,
+            VotingStrategy::SingleChoice {
+                quorum: voting::threshold::PercentageThreshold::Majority {},
+            },
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        let vote = |option_id, rankings| MultipleChoiceVote {
+            option_id,
+            rankings,
+        };
+        assert!(ranked.is_valid_vote(&vote(0, vec![2, 1])));
+        assert!(ranked.is_valid_vote(&vote(1, vec![])));
+        assert!(!ranked.is_valid_vote(&vote(0, vec![1, 0])));
+        assert!(!ranked.is_valid_vote(&vote(0, vec![3])));
+        assert!(single.is_valid_vote(&vote(2, vec![])));
+        assert!(!single.is_valid_vote(&vote(0, vec![1])));
+    }
+
+    #[test]
+    fn test_approval_votes() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Approval {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+            max_selections: 2,
+            winners: 1,
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(10),
+            false,
+            false,
+        );
+
+        let vote = |option_id, rankings| MultipleChoiceVote {
+            option_id,
+            rankings,
+        };
+        assert!(prop.is_valid_vote(&vote(0, vec![1])));
+        assert!(prop.is_valid_vote(&vote(2, vec![])));
+        // Too many selections.
+        assert!(!prop.is_valid_vote(&vote(0, vec![1, 2])));
+        // Duplicate selections.
+        assert!(!prop.is_valid_vote(&vote(1, vec![1])));
+        // None of the above alongside another option.
+        assert!(!prop.is_valid_vote(&vote(0, vec![2])));
+
+        prop.add_vote(&vote(0, vec![1]), Uint128::new(4)).unwrap();
+        prop.add_vote(&vote(1, vec![]), Uint128::new(2)).unwrap();
+
+        // Both options were approved by a majority of voting power,
+        // but option 1 has more approvals.
+        assert_eq!(prop.votes.total(), Uint128::new(6));
+        assert!(
+            matches!(prop.calculate_vote_result().unwrap(), VoteResult::Winners(winners) if winners.len() == 1 && winners[0].index == 1)
+        );
+        // Option 0 could still catch up with the outstanding voting
+        // power.
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        prop.add_vote(&vote(1, vec![]), Uint128::new(3)).unwrap();
+        assert_eq!(
+            prop.votes.vote_weights,
+            vec![Uint128::new(4), Uint128::new(9), Uint128::zero()]
+        );
+        assert!(prop.is_passed(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_approval_multiple_winners() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Approval {
+            quorum: voting::threshold::PercentageThreshold::Percent(Decimal::percent(20)),
+            max_selections: 3,
+            winners: 2,
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(4),
+            Uint128::new(10),
+            true,
+            false,
+        );
+        prop.choices = MultipleChoiceOptions {
+            options: (1..=3)
+                .map(|idx| MultipleChoiceOption {
+                    description: format!("multiple choice option {}", idx),
+                    msgs: None,
+                })
+                .collect(),
+        }
+        .into_checked()
+        .unwrap()
+        .options;
+
+        let vote = |option_id, rankings| MultipleChoiceVote {
+            option_id,
+            rankings,
+        };
+        let winners = |prop: &MultipleChoiceProposal| -> Vec<u32> {
+            prop.approval_winners()
+                .into_iter()
+                .map(|choice| choice.index)
+                .collect()
+        };
+
+        prop.add_vote(&vote(0, vec![1]), Uint128::new(4)).unwrap();
+        prop.add_vote(&vote(0, vec![2]), Uint128::new(3)).unwrap();
+        assert_eq!(winners(&prop), vec![0, 1]);
+
+        prop.add_vote(&vote(2, vec![]), Uint128::new(1)).unwrap();
+        assert_eq!(
+            prop.votes.vote_weights[..3],
+            [Uint128::new(7), Uint128::new(4), Uint128::new(4)]
+        );
+        // Options 1 and 2 are tied for the second place so neither
+        // wins.
+        assert_eq!(winners(&prop), vec![0]);
+        assert!(prop.is_passed(&env.block).unwrap());
+
+        prop.add_vote(&vote(1, vec![]), Uint128::new(1)).unwrap();
+        assert_eq!(winners(&prop), vec![0, 1]);
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::Winners(winners) if winners.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_approval_tie_rejected() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Approval {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+            max_selections: 2,
+            winners: 1,
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(10),
+            false,
+            false,
+        );
+        let vote = MultipleChoiceVote {
+            option_id: 0,
+            rankings: vec![1],
+        };
+        prop.add_vote(&vote, Uint128::new(10)).unwrap();
+
+        // Both options are tied for the only winning place and all
+        // voting power has been cast.
+        assert!(prop.approval_winners().is_empty());
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(prop.is_rejected(&env.block).unwrap());
+    }
+
     #[test]
     fn test_percentage_revote_rejection() {
         // Revoting being allowed means that proposals may not be
@@ -834,6 +1573,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(90), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
@@ -849,6 +1589,7 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
         let prop = create_proposal(
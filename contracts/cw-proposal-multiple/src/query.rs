@@ -1,8 +1,9 @@
 use crate::{
-    proposal::MultipleChoiceProposal,
+    proposal::{MultipleChoiceProposal, RunoffRound},
     state::{Config, VoteInfo},
 };
 use cosmwasm_std::Uint128;
+use cw_utils::Duration;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,13 @@ pub struct ProposalListResponse {
 pub struct ProposalResponse {
     pub id: u64,
     pub proposal: MultipleChoiceProposal,
+    /// The tally of each round of the instant runoff for ranked
+    /// choice proposals. None for other voting strategies.
+    pub runoff: Option<Vec<RunoffRound>>,
+    /// The amount of time remaining until the proposal may be
+    /// executed if it has passed and is in its timelock. None
+    /// otherwise.
+    pub timelock_remaining: Option<Duration>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -1,11 +1,16 @@
 use crate::{proposal::MultipleChoiceProposal, voting_strategy::VotingStrategy, ContractError};
-use cosmwasm_std::{Addr, CosmosMsg, Empty, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use indexable_hooks::Hooks;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use voting::{deposit::CheckedDepositInfo, voting::MultipleChoiceVote};
+use voting::{
+    curve::VotingPowerCurve,
+    deposit::CheckedDepositInfo,
+    veto::CheckedVetoer,
+    voting::{MultipleChoiceVote, RankedBallots},
+};
 
 pub const MAX_NUM_CHOICES: u32 = 10;
 const NONE_OPTION_DESCRIPTION: &str = "None of the above";
@@ -47,6 +52,18 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The curve applied to voting power before votes are
+    /// counted. Linear unless configured otherwise.
+    #[serde(default)]
+    pub voting_power_curve: VotingPowerCurve,
+    /// The amount of time after a proposal's voting period ends
+    /// before it may be executed. None if proposals may be executed
+    /// as soon as they pass.
+    #[serde(default)]
+    pub timelock: Option<Duration>,
+    /// Who may veto passed proposals during their timelock.
+    #[serde(default)]
+    pub vetoer: Option<CheckedVetoer>,
 }
 
 /// Information about a vote that was cast.
@@ -58,6 +75,8 @@ pub struct VoteInfo {
     pub vote: MultipleChoiceVote,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// The voter's explanation of their vote, if they gave one.
+    pub rationale: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -145,14 +164,89 @@ pub struct Ballot {
     pub power: Uint128,
     /// The position.
     pub vote: MultipleChoiceVote,
+    /// The voter's explanation of their vote, if they gave one.
+    #[serde(default)]
+    pub rationale: Option<String>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, MultipleChoiceProposal> = Map::new("proposals");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
-pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
-pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The ballots cast on ranked choice proposals grouped by their order
+/// of preference. Keyed by proposal ID and the ballots' option IDs
+/// from most to least preferred, with one byte per option. Stored
+/// separately from the proposal so that the cost of voting does not
+/// grow with the number of distinct rankings.
+pub const RANKED_BALLOTS: Map<(u64, Vec<u8>), Uint128> = Map::new("ranked_ballots");
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks", "proposal_hook_indices");
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_indices");
+
+fn ranked_ballot_key(vote: &MultipleChoiceVote) -> Vec<u8> {
+    // Option IDs are less than `MAX_NUM_CHOICES` and fit in a byte.
+    vote.preferences()
+        .into_iter()
+        .map(|option_id| option_id as u8)
+        .collect()
+}
+
+/// Adds a ranked choice ballot with WEIGHT to the ranked ballots of
+/// proposal PROPOSAL_ID.
+pub fn add_ranked_ballot(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    vote: &MultipleChoiceVote,
+    weight: Uint128,
+) -> StdResult<()> {
+    RANKED_BALLOTS.update(
+        storage,
+        (proposal_id, ranked_ballot_key(vote)),
+        |total| -> StdResult<_> {
+            total
+                .unwrap_or_default()
+                .checked_add(weight)
+                .map_err(StdError::overflow)
+        },
+    )?;
+    Ok(())
+}
+
+/// Removes a ballot previously added with `add_ranked_ballot`.
+pub fn remove_ranked_ballot(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    vote: &MultipleChoiceVote,
+    weight: Uint128,
+) -> StdResult<()> {
+    let key = (proposal_id, ranked_ballot_key(vote));
+    let remaining = RANKED_BALLOTS
+        .load(storage, key.clone())?
+        .checked_sub(weight)
+        .map_err(StdError::overflow)?;
+    if remaining.is_zero() {
+        RANKED_BALLOTS.remove(storage, key);
+    } else {
+        RANKED_BALLOTS.save(storage, key, &remaining)?;
+    }
+    Ok(())
+}
+
+/// Loads the ranked ballots of proposal PROPOSAL_ID.
+pub fn load_ranked_ballots(
+    storage: &dyn Storage,
+    proposal_id: u64,
+) -> StdResult<Vec<RankedBallots>> {
+    RANKED_BALLOTS
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(preferences, weight)| RankedBallots {
+                preferences: preferences.into_iter().map(u32::from).collect(),
+                weight,
+            })
+        })
+        .collect()
+}
 
 mod tests {
 
@@ -1,25 +1,31 @@
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env},
-    to_binary, Addr, Binary, CosmosMsg, Decimal, Empty, Timestamp, Uint128, WasmMsg,
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Empty, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw20::Cw20Coin;
 use cw20_staked_balance_voting::msg::ActiveThreshold;
 use cw_core::state::ProposalModule;
-use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 use cw_utils::Duration;
 use indexable_hooks::HooksResponse;
 use rand::{prelude::SliceRandom, Rng};
 use voting::{
-    deposit::{CheckedDepositInfo, DepositInfo, DepositToken},
+    curve::VotingPowerCurve,
+    deposit::{
+        CheckedDepositInfo, CheckedDepositToken, DepositInfo, DepositRefundPolicy, DepositToken,
+    },
+    error::VotingError,
+    proposal::MAX_RATIONALE_LENGTH,
     status::Status,
     threshold::{PercentageThreshold, Threshold},
+    veto::Vetoer,
     voting::{MultipleChoiceVote, MultipleChoiceVotes},
 };
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    proposal::MultipleChoiceProposal,
+    proposal::{MultipleChoiceProposal, RunoffRound},
     query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
     state::{Config, MultipleChoiceOption, MultipleChoiceOptions, VoteInfo, MAX_NUM_CHOICES},
     voting_strategy::VotingStrategy,
@@ -158,6 +164,9 @@ where
         deposit_info,
         voting_strategy,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
 
     let governance_addr = setup_governance(
@@ -187,7 +196,9 @@ where
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
     if let Some(CheckedDepositInfo {
-        ref token, deposit, ..
+        token: CheckedDepositToken::Cw20(ref token),
+        deposit,
+        ..
     }) = config.deposit_info
     {
         app.execute_contract(
@@ -242,7 +253,8 @@ where
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: position,
+                vote: position.clone(),
+                rationale: None,
             },
             &[],
         );
@@ -277,6 +289,7 @@ where
                             }
                             None => weight,
                         },
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -335,7 +348,10 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -351,7 +367,10 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -372,7 +391,10 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 10 },
+            position: MultipleChoiceVote {
+                option_id: 10,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::No,
         }],
@@ -392,8 +414,11 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
-            weight: Uint128::new(u128::max_value()),
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            weight: Uint128::new(u128::MAX),
             should_execute: ShouldExecute::Yes,
         }],
         VotingStrategy::SingleChoice {
@@ -408,14 +433,20 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bob".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
-                weight: Uint128::new(u128::max_value() - 1),
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
+                weight: Uint128::new(u128::MAX - 1),
                 should_execute: ShouldExecute::Yes,
             },
         ],
@@ -436,13 +467,19 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bob".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
@@ -463,8 +500,11 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 }, // the last index is none of the above
-            weight: Uint128::new(u64::max_value().into()),
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            }, // the last index is none of the above
+            weight: Uint128::new(u64::MAX.into()),
             should_execute: ShouldExecute::Yes,
         }],
         VotingStrategy::SingleChoice {
@@ -479,8 +519,11 @@ where
         do_votes(
             vec![TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 2 },
-                weight: Uint128::new(u64::max_value().into()),
+                position: MultipleChoiceVote {
+                    option_id: 2,
+                    rankings: vec![],
+                },
+                weight: Uint128::new(u64::MAX.into()),
                 should_execute: ShouldExecute::Yes,
             }],
             VotingStrategy::SingleChoice {
@@ -504,7 +547,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(1),
             should_execute: ShouldExecute::Yes,
         }],
@@ -519,7 +565,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -536,7 +585,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 1 },
+            position: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
             weight: Uint128::new(9999999),
             should_execute: ShouldExecute::Yes,
         }],
@@ -552,7 +604,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(1),
             should_execute: ShouldExecute::Yes,
         }],
@@ -573,13 +628,19 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(2),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(2),
                 should_execute: ShouldExecute::No,
             },
@@ -609,13 +670,19 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
@@ -633,13 +700,19 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
@@ -660,7 +733,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(60),
             should_execute: ShouldExecute::Yes,
         }],
@@ -676,7 +752,10 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(60),
             should_execute: ShouldExecute::Yes,
         }],
@@ -704,7 +783,7 @@ where
         let one_sum: u64 = one.iter().sum();
         let none_sum: u64 = none.iter().sum();
 
-        let mut sums = vec![zero_sum, one_sum, none_sum];
+        let mut sums = [zero_sum, one_sum, none_sum];
         sums.sort_unstable();
 
         // If none of the above wins or there is a tie between second and first choice.
@@ -719,7 +798,10 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("zero_{}", idx),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -728,7 +810,10 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("one_{}", idx),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -738,7 +823,10 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("none_{}", idx),
-                position: MultipleChoiceVote { option_id: 2 },
+                position: MultipleChoiceVote {
+                    option_id: 2,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -776,6 +864,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -814,6 +905,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
 
     assert_eq!(config, expected);
@@ -856,13 +950,18 @@ fn test_propose() {
         proposer: Addr::unchecked(CREATOR_ADDR),
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
+        timelock: None,
+        timelock_expiration: None,
         choices: checked_options.options,
         status: Status::Open,
         voting_strategy,
         total_power: Uint128::new(100_000_000),
+        curved_power: None,
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
+            power_cast: None,
         },
+        ranked_ballots: None,
         allow_revoting: false,
         deposit_info: None,
         min_voting_period: None,
@@ -887,6 +986,9 @@ fn test_propose_wrong_num_choices() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -924,6 +1026,9 @@ fn test_propose_wrong_num_choices() {
     let expected = Config {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1084,6 +1189,9 @@ fn test_migrate() {
         max_voting_period: Duration::Time(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
@@ -1143,6 +1251,9 @@ fn test_proposal_count_initialized_to_zero() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
@@ -1185,6 +1296,9 @@ fn test_no_early_pass_with_min_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1244,7 +1358,11 @@ fn test_no_early_pass_with_min_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -1279,6 +1397,9 @@ fn test_propose_with_messages() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
@@ -1315,6 +1436,9 @@ fn test_propose_with_messages() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period: cw_utils::Duration::Height(20),
         only_members_execute: false,
         allow_revoting: false,
@@ -1358,7 +1482,11 @@ fn test_propose_with_messages() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -1405,6 +1533,9 @@ fn test_min_duration_units_missmatch() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -1438,6 +1569,9 @@ fn test_min_duration_larger_than_proposal_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -1470,6 +1604,9 @@ fn test_min_duration_same_as_proposal_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1529,7 +1666,11 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -1548,7 +1689,11 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 2 },
+            vote: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -1578,12 +1723,15 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     let deposit_info = Some(DepositInfo {
         token: DepositToken::VotingModuleToken {},
         deposit: Uint128::new(1),
-        refund_failed_proposals: true,
+        refund_policy: DepositRefundPolicy::Always {},
     });
 
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1623,9 +1771,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     assert_eq!(
         config.deposit_info,
         Some(CheckedDepositInfo {
-            token: expected_token,
+            token: CheckedDepositToken::Cw20(expected_token),
             deposit: Uint128::new(1),
-            refund_failed_proposals: true
+            refund_policy: DepositRefundPolicy::Always {}
         })
     )
 }
@@ -1661,6 +1809,9 @@ fn test_different_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1669,7 +1820,7 @@ fn test_different_token_proposal_deposit() {
                 address: cw20_addr.to_string(),
             },
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         voting_strategy,
     };
@@ -1722,7 +1873,7 @@ fn test_bad_token_proposal_deposit() {
             address: votemod_addr.to_string(),
         },
         deposit: Uint128::new(1),
-        refund_failed_proposals: true,
+        refund_policy: DepositRefundPolicy::Always {},
     });
 
     let quorum = PercentageThreshold::Percent(Decimal::percent(10));
@@ -1731,6 +1882,9 @@ fn test_bad_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1757,12 +1911,15 @@ fn test_take_proposal_deposit() {
     let deposit_info = Some(DepositInfo {
         token: DepositToken::VotingModuleToken {},
         deposit: Uint128::new(1),
-        refund_failed_proposals: true,
+        refund_policy: DepositRefundPolicy::Always {},
     });
 
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1794,11 +1951,14 @@ fn test_take_proposal_deposit() {
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
     let CheckedDepositInfo {
-        token,
+        token: CheckedDepositToken::Cw20(token),
         deposit,
-        refund_failed_proposals,
-    } = govmod_config.deposit_info.unwrap();
-    assert!(refund_failed_proposals);
+        refund_policy,
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
+    assert_eq!(refund_policy, DepositRefundPolicy::Always {});
     assert_eq!(deposit, Uint128::new(1));
 
     let options = vec![
@@ -1875,7 +2035,10 @@ fn test_deposit_return_on_execute() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1887,7 +2050,7 @@ fn test_deposit_return_on_execute() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
         true,
     );
@@ -1905,7 +2068,13 @@ fn test_deposit_return_on_execute() {
         .wrap()
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -1950,13 +2119,16 @@ fn test_deposit_return_zero() {
     let deposit_info = Some(DepositInfo {
         token: DepositToken::VotingModuleToken {},
         deposit: Uint128::new(0),
-        refund_failed_proposals: false,
+        refund_policy: DepositRefundPolicy::OnlyPassed {},
     });
 
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1982,7 +2154,13 @@ fn test_deposit_return_zero() {
         .wrap()
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
 
     // Execute the proposal
     app.execute_contract(
@@ -2013,13 +2191,19 @@ fn test_query_list_votes() {
         vec![
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "note".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
                 weight: Uint128::new(20),
                 should_execute: ShouldExecute::Yes,
             },
@@ -2057,13 +2241,21 @@ fn test_query_list_votes() {
     let expected = vec![
         VoteInfo {
             voter: Addr::unchecked("blue"),
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             power: Uint128::new(10),
+            rationale: None,
         },
         VoteInfo {
             voter: Addr::unchecked("note"),
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
             power: Uint128::new(20),
+            rationale: None,
         },
     ];
 
@@ -2076,7 +2268,10 @@ fn test_invalid_quorum() {
     let (_app, _governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2096,7 +2291,10 @@ fn test_cant_vote_executed_or_closed() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2133,7 +2331,11 @@ fn test_cant_vote_executed_or_closed() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -2143,7 +2345,10 @@ fn test_cant_vote_executed_or_closed() {
     let (mut app, _governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2171,7 +2376,11 @@ fn test_cant_vote_executed_or_closed() {
         govmod,
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -2188,13 +2397,16 @@ fn test_cant_propose_zero_power() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         deposit_info: Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         voting_strategy,
     };
@@ -2242,7 +2454,9 @@ fn test_cant_propose_zero_power() {
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
     if let Some(CheckedDepositInfo {
-        ref token, deposit, ..
+        token: CheckedDepositToken::Cw20(ref token),
+        deposit,
+        ..
     }) = config.deposit_info
     {
         app.execute_contract(
@@ -2296,7 +2510,10 @@ fn test_cant_vote_not_registered() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2308,7 +2525,7 @@ fn test_cant_vote_not_registered() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         false,
     );
@@ -2329,7 +2546,11 @@ fn test_cant_vote_not_registered() {
             govmod,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                rationale: None,
             },
             &[],
         )
@@ -2342,59 +2563,136 @@ fn test_cant_vote_not_registered() {
 }
 
 #[test]
-fn test_cant_execute_not_member() {
-    // Create proposal with only_members_execute: true
-    let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
-
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let quorum = PercentageThreshold::Majority {};
+fn test_ranked_choice_runoff() {
+    let (app, governance_addr) = do_test_votes_cw20_balances(
+        vec![
+            TestMultipleChoiceVote {
+                voter: "blue".to_string(),
+                position: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                weight: Uint128::new(40),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestMultipleChoiceVote {
+                voter: "green".to_string(),
+                position: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![0],
+                },
+                weight: Uint128::new(35),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestMultipleChoiceVote {
+                voter: "red".to_string(),
+                position: MultipleChoiceVote {
+                    option_id: 2,
+                    rankings: vec![1, 0],
+                },
+                weight: Uint128::new(25),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestMultipleChoiceVote {
+                voter: "red".to_string(),
+                position: MultipleChoiceVote {
+                    option_id: 2,
+                    rankings: vec![2],
+                },
+                weight: Uint128::new(25),
+                should_execute: ShouldExecute::No,
+            },
+        ],
+        VotingStrategy::RankedChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        Status::Passed,
+        None,
+        None,
+        false,
+    );
 
-    let voting_strategy = VotingStrategy::SingleChoice { quorum };
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    let instantiate = InstantiateMsg {
-        min_voting_period: None,
-        close_proposal_on_execution_failure: true,
-        max_voting_period,
-        only_members_execute: true,
-        allow_revoting: false,
-        deposit_info: None,
-        voting_strategy,
-    };
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    // "None of the above" is eliminated in the first round and its
+    // ballots transfer to option 1, which overtakes option 0.
+    assert_eq!(
+        proposal.runoff,
+        Some(vec![
+            RunoffRound {
+                vote_weights: vec![Uint128::new(40), Uint128::new(35), Uint128::new(25)],
+                eliminated: vec![2],
+            },
+            RunoffRound {
+                vote_weights: vec![Uint128::new(40), Uint128::new(60), Uint128::zero()],
+                eliminated: vec![],
+            },
+        ])
+    );
+}
 
-    let governance_addr = instantiate_with_cw20_balances_governance(
+#[test]
+fn test_ranked_choice_revoting() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
         &mut app,
         govmod_id,
-        to_binary(&instantiate).unwrap(),
-        Some(vec![Cw20Coin {
-            address: "blue".to_string(),
-            amount: Uint128::new(10),
-        }]),
+        to_binary(&InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: true,
+            deposit_info: None,
+            voting_strategy: VotingStrategy::RankedChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        })
+        .unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(50),
+            },
+        ]),
     );
-
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            governance_addr.clone(),
+            core_addr,
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
             },
         )
         .unwrap();
+    let proposal_module = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(governance_modules.len(), 1);
-
-    let gov_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
-        .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-
-    assert_eq!(governance_modules.len(), 1);
-    let govmod = governance_modules.into_iter().next().unwrap().address;
-
-    // Create proposal
     let options = vec![
         MultipleChoiceOption {
             description: "multiple choice option 1".to_string(),
@@ -2405,76 +2703,335 @@ fn test_cant_execute_not_member() {
             msgs: None,
         },
     ];
-
-    let mc_options = MultipleChoiceOptions { options };
-
     app.execute_contract(
-        Addr::unchecked("blue"),
-        govmod.clone(),
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
         &ExecuteMsg::Propose {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
-            choices: mc_options,
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Proposal should pass after this vote
-    app.execute_contract(
-        Addr::unchecked("blue"),
-        govmod.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            choices: MultipleChoiceOptions { options },
         },
         &[],
     )
     .unwrap();
 
-    // Execute should error as blue2 is not a member
-    let err = app
-        .execute_contract(
-            Addr::unchecked("blue2"),
-            govmod,
-            &ExecuteMsg::Execute { proposal_id: 1 },
+    let mut vote = |voter: &str, option_id: u32, rankings: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id,
+                    rankings,
+                },
+                rationale: None,
+            },
             &[],
         )
-        .unwrap_err();
-
-    assert!(matches!(
-        err.downcast().unwrap(),
-        ContractError::Unauthorized {}
-    ))
+        .unwrap();
+    };
+    vote("a-1", 0, vec![]);
+    vote("a-2", 1, vec![0]);
+    vote("a-3", 2, vec![1]);
+    // a-3 changes their second preference, so their ballot transfers
+    // to option 0 once "none of the above" is eliminated.
+    vote("a-3", 2, vec![0]);
+
+    app.update_block(|b| b.height += 6);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.runoff,
+        Some(vec![
+            RunoffRound {
+                vote_weights: vec![Uint128::new(100), Uint128::new(60), Uint128::new(50)],
+                eliminated: vec![2],
+            },
+            RunoffRound {
+                vote_weights: vec![Uint128::new(150), Uint128::new(60), Uint128::zero()],
+                eliminated: vec![],
+            },
+        ])
+    );
 }
 
 #[test]
-fn test_close_open_proposal() {
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestMultipleChoiceVote {
-            voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        VotingStrategy::SingleChoice {
-            quorum: PercentageThreshold::Majority {},
-        },
-        Status::Open,
-        Some(Uint128::new(100)),
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
-        false,
+fn test_ranked_choice_execute() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            deposit_info: None,
+            voting_strategy: VotingStrategy::RankedChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        })
+        .unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(60),
+            },
+        ]),
     );
-
-    let gov_state: cw_core::query::DumpStateResponse = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
-        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(
+            core_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
+    let proposal_module = governance_modules.into_iter().next().unwrap().address;
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions { options },
+        },
+        &[],
+    )
+    .unwrap();
+    for (voter, option_id, rankings) in [("a-1", 0, vec![1]), ("a-2", 1, vec![0])] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id,
+                    rankings,
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // All voting power has been cast so the proposal passes before
+    // it expires and its ballots are no longer loaded when voting.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_single_choice_rejects_rankings() {
+    do_votes_cw20_balances(
+        vec![TestMultipleChoiceVote {
+            voter: "blue".to_string(),
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![1],
+            },
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::No,
+        }],
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        Status::Open,
+        None,
+        false,
+    );
+}
+
+#[test]
+fn test_cant_execute_not_member() {
+    // Create proposal with only_members_execute: true
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let quorum = PercentageThreshold::Majority {};
+
+    let voting_strategy = VotingStrategy::SingleChoice { quorum };
+
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        max_voting_period,
+        only_members_execute: true,
+        allow_revoting: false,
+        deposit_info: None,
+        voting_strategy,
+    };
+
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![Cw20Coin {
+            address: "blue".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod = governance_modules.into_iter().next().unwrap().address;
+
+    // Create proposal
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+
+    let mc_options = MultipleChoiceOptions { options };
+
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: mc_options,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Proposal should pass after this vote
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Execute should error as blue2 is not a member
+    let err = app
+        .execute_contract(
+            Addr::unchecked("blue2"),
+            govmod,
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized {}
+    ))
+}
+
+#[test]
+fn test_close_open_proposal() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestMultipleChoiceVote {
+            voter: "blue".to_string(),
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_policy: DepositRefundPolicy::Always {},
+        }),
+        false,
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
 
     assert_eq!(governance_modules.len(), 1);
     let govmod = governance_modules.into_iter().next().unwrap().address;
@@ -2507,7 +3064,13 @@ fn test_close_open_proposal() {
         .wrap()
         .query_wasm_smart(govmod, &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -2528,7 +3091,10 @@ fn test_no_refund_failed_proposal() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2540,7 +3106,7 @@ fn test_no_refund_failed_proposal() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
         false,
     );
@@ -2572,7 +3138,13 @@ fn test_no_refund_failed_proposal() {
         .wrap()
         .query_wasm_smart(govmod, &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -2592,7 +3164,10 @@ fn test_zero_deposit() {
     do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2604,7 +3179,7 @@ fn test_zero_deposit() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(0),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
         true,
     );
@@ -2618,7 +3193,10 @@ fn test_deposit_return_on_close() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2628,7 +3206,7 @@ fn test_deposit_return_on_close() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         false,
     );
@@ -2645,7 +3223,13 @@ fn test_deposit_return_on_close() {
         .wrap()
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -2694,6 +3278,9 @@ fn test_execute_expired_proposal() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2756,7 +3343,11 @@ fn test_execute_expired_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -2819,7 +3410,10 @@ fn test_update_config() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2831,7 +3425,7 @@ fn test_update_config() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
         false,
     );
@@ -2870,6 +3464,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2890,6 +3487,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2911,6 +3511,9 @@ fn test_update_config() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period: cw_utils::Duration::Height(10),
         only_members_execute: false,
         allow_revoting: false,
@@ -2930,6 +3533,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2946,7 +3552,10 @@ fn test_no_return_if_no_refunds() {
     let (mut app, governance_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2958,7 +3567,7 @@ fn test_no_return_if_no_refunds() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
         true,
     );
@@ -2975,7 +3584,13 @@ fn test_no_return_if_no_refunds() {
         .wrap()
         .query_wasm_smart(govmod.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
 
     // Close the proposal, this should cause the deposit to be
     // refunded.
@@ -3011,6 +3626,9 @@ fn test_query_list_proposals() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -3102,19 +3720,26 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
+            timelock: None,
+            timelock_expiration: None,
             choices: checked_options.options.clone(),
             status: Status::Open,
             voting_strategy: voting_strategy.clone(),
             total_power: Uint128::new(100),
+            curved_power: None,
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
+                power_cast: None,
             },
+            ranked_ballots: None,
             allow_revoting: false,
             deposit_info: None,
             min_voting_period: None,
             created: current_block.time,
             last_updated: current_block.time,
         },
+        runoff: None,
+        timelock_remaining: None,
     };
     assert_eq!(proposals_forward.proposals[0], expected);
 
@@ -3148,19 +3773,26 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
+            timelock: None,
+            timelock_expiration: None,
             choices: checked_options.options,
             status: Status::Open,
             voting_strategy,
             total_power: Uint128::new(100),
+            curved_power: None,
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
+                power_cast: None,
             },
+            ranked_ballots: None,
             allow_revoting: false,
             deposit_info: None,
             min_voting_period: None,
             created: current_block.time,
             last_updated: current_block.time,
         },
+        runoff: None,
+        timelock_remaining: None,
     };
     assert_eq!(proposals_forward.proposals[0], expected);
     assert_eq!(proposals_backward.proposals[1], expected);
@@ -3180,6 +3812,9 @@ fn test_hooks() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -3216,18 +3851,34 @@ fn test_hooks() {
     // Expect no hooks
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod.clone(), &QueryMsg::ProposalHooks {})
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 0);
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod.clone(), &QueryMsg::VoteHooks {})
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 0);
 
     let msg = ExecuteMsg::AddProposalHook {
         address: "some_addr".to_string(),
+        version: None,
+        failure_policy: None,
+        gas_limit: None,
+        filter: None,
     };
 
     // Expect error as sender is not DAO
@@ -3242,7 +3893,13 @@ fn test_hooks() {
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod.clone(), &QueryMsg::ProposalHooks {})
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
@@ -3279,6 +3936,9 @@ fn test_hooks() {
 
     let msg = ExecuteMsg::AddVoteHook {
         address: "some_addr".to_string(),
+        version: None,
+        failure_policy: None,
+        gas_limit: None,
     };
 
     // Expect error as sender is not DAO
@@ -3293,7 +3953,13 @@ fn test_hooks() {
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod.clone(), &QueryMsg::VoteHooks {})
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
@@ -3338,6 +4004,9 @@ fn test_active_threshold_absolute() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -3476,6 +4145,9 @@ fn test_active_threshold_percent() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -3615,6 +4287,9 @@ fn test_active_threshold_none() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -3764,6 +4439,9 @@ fn test_revoting() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
         })
         .unwrap(),
         Some(vec![
@@ -3821,7 +4499,11 @@ fn test_revoting() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -3833,7 +4515,11 @@ fn test_revoting() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -3870,7 +4556,11 @@ fn test_revoting() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
@@ -3891,11 +4581,8 @@ fn test_revoting() {
     assert_eq!(proposal.proposal.votes.vote_weights[1], Uint128::new(0),);
 }
 
-/// Tests that revoting is stored at a per-proposal level.
-/// Proposals created while revoting is enabled should not
-/// have it disabled if a config change turns if off.
 #[test]
-fn test_allow_revoting_config_changes() {
+fn test_approval_revoting() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
     let core_addr = instantiate_with_staked_balances_governance(
@@ -3907,10 +4594,15 @@ fn test_allow_revoting_config_changes() {
             only_members_execute: false,
             allow_revoting: true,
             deposit_info: None,
-            voting_strategy: VotingStrategy::SingleChoice {
+            voting_strategy: VotingStrategy::Approval {
                 quorum: PercentageThreshold::Majority {},
+                max_selections: 2,
+                winners: 1,
             },
             close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
         })
         .unwrap(),
         Some(vec![
@@ -3928,7 +4620,7 @@ fn test_allow_revoting_config_changes() {
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            core_addr.clone(),
+            core_addr,
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
@@ -3947,42 +4639,49 @@ fn test_allow_revoting_config_changes() {
             msgs: None,
         },
     ];
-    let mc_options = MultipleChoiceOptions { options };
-
-    // Create a basic proposal with 2 options that allows revoting
     app.execute_contract(
         Addr::unchecked("a-1"),
         proposal_module.clone(),
         &ExecuteMsg::Propose {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
-            choices: mc_options.clone(),
+            choices: MultipleChoiceOptions { options },
         },
         &[],
     )
     .unwrap();
 
-    // Disable revoting
-    app.execute_contract(
-        core_addr.clone(),
-        proposal_module.clone(),
-        &ExecuteMsg::UpdateConfig {
-            min_voting_period: None,
-            max_voting_period: Duration::Height(6),
-            only_members_execute: false,
-            allow_revoting: false,
-            dao: core_addr.to_string(),
-            deposit_info: None,
-            voting_strategy: VotingStrategy::SingleChoice {
-                quorum: PercentageThreshold::Majority {},
+    let mut vote = |voter: &str, option_id: u32, rankings: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id,
+                    rankings,
+                },
+                rationale: None,
             },
-            close_proposal_on_execution_failure: false,
-        },
-        &[],
-    )
-    .unwrap();
+            &[],
+        )
+    };
+
+    // Selecting more than `max_selections` options is not allowed.
+    let err = vote("a-1", 0, vec![1, 2]).unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::InvalidVote {}
+    ));
+
+    // vote_weights: [100_000_000, 200_000_000, 0]
+    vote("a-1", 0, vec![1]).unwrap();
+    vote("a-2", 1, vec![]).unwrap();
+    // a-1 withdraws their approval of option 2 and a-2 approves of
+    // both options. vote_weights: [200_000_000, 100_000_000, 0]
+    vote("a-1", 0, vec![]).unwrap();
+    vote("a-2", 1, vec![0]).unwrap();
 
-    // Assert that proposal_id: 1 still allows revoting
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
@@ -3990,76 +4689,37 @@ fn test_allow_revoting_config_changes() {
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    assert!(proposal.proposal.allow_revoting);
-
-    app.execute_contract(
-        Addr::unchecked("a-1"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
-        },
-        &[],
-    )
-    .unwrap();
-    app.execute_contract(
-        Addr::unchecked("a-1"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
-        },
-        &[],
-    )
-    .unwrap();
-
-    // New proposals should not allow revoting
-    app.execute_contract(
-        Addr::unchecked("a-2"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "A very complex text proposal".to_string(),
-            description: "A very complex text proposal".to_string(),
-            choices: mc_options,
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked("a-2"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 2,
-            vote: MultipleChoiceVote { option_id: 0 },
-        },
-        &[],
-    )
-    .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(200_000_000),
+            Uint128::new(100_000_000),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.proposal.votes.total(), Uint128::new(200_000_000));
 
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked("a-2"),
-            proposal_module,
-            &ExecuteMsg::Vote {
-                proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 1 },
-            },
-            &[],
-        )
-        .unwrap_err()
-        .downcast()
+    app.update_block(|b| b.height += 6);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-
-    assert!(matches!(err, ContractError::AlreadyVoted {}));
+    assert_eq!(proposal.proposal.status, Status::Passed);
 }
 
-/// Tests that we error if a revote casts the same vote as the
-/// previous vote.
 #[test]
-fn test_revoting_same_vote_twice() {
+fn test_quadratic_voting() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
+    let mut initial_balances = vec![Cw20Coin {
+        address: "whale".to_string(),
+        amount: Uint128::new(900),
+    }];
+    initial_balances.extend((0..4).map(|i| Cw20Coin {
+        address: format!("minnow-{}", i),
+        amount: Uint128::new(100),
+    }));
     let core_addr = instantiate_with_staked_balances_governance(
         &mut app,
         govmod_id,
@@ -4067,24 +4727,18 @@ fn test_revoting_same_vote_twice() {
             min_voting_period: None,
             max_voting_period: Duration::Height(6),
             only_members_execute: false,
-            allow_revoting: true,
+            allow_revoting: false,
             deposit_info: None,
             voting_strategy: VotingStrategy::SingleChoice {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Root { degree: 2 },
+            timelock: None,
+            vetoer: None,
         })
         .unwrap(),
-        Some(vec![
-            Cw20Coin {
-                address: "a-1".to_string(),
-                amount: Uint128::new(100_000_000),
-            },
-            Cw20Coin {
-                address: "a-2".to_string(),
-                amount: Uint128::new(100_000_000),
-            },
-        ]),
+        Some(initial_balances),
     );
 
     let governance_modules: Vec<ProposalModule> = app
@@ -4109,56 +4763,124 @@ fn test_revoting_same_vote_twice() {
             msgs: None,
         },
     ];
-    let mc_options = MultipleChoiceOptions { options };
-
-    // Create a basic proposal with 2 options that allows revoting
     app.execute_contract(
-        Addr::unchecked("a-1"),
+        Addr::unchecked("whale"),
         proposal_module.clone(),
         &ExecuteMsg::Propose {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
-            choices: mc_options,
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Cast a vote
-    app.execute_contract(
-        Addr::unchecked("a-1"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            choices: MultipleChoiceOptions { options },
         },
         &[],
     )
     .unwrap();
 
-    // Revote for the same option as currently voted
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked("a-1"),
-            proposal_module,
+    let mut vote = |voter: &str, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote {
+                    option_id,
+                    rankings: vec![],
+                },
+                rationale: None,
             },
             &[],
         )
-        .unwrap_err()
-        .downcast()
+        .unwrap()
+    };
+    // The whale holds a majority of voting power, but with quadratic
+    // voting has a weight of 30 to the minnows' combined 40.
+    vote("whale", 0);
+    for i in 0..4 {
+        vote(&format!("minnow-{}", i), 1);
+    }
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
         .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.total_power, Uint128::new(70));
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![Uint128::new(30), Uint128::new(40), Uint128::zero()]
+    );
 
-    // Can't cast the same vote twice.
-    assert!(matches!(err, ContractError::AlreadyCast {}));
+    app.update_block(|b| b.height += 6);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
 }
 
-/// Tests that revoting into a non-existing vote option
-/// does not invalidate the initial vote
 #[test]
-fn test_invalid_revote_does_not_invalidate_initial_vote() {
+fn test_approval_zero_max_selections() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 0,
+            winners: 1,
+        },
+        close_proposal_on_execution_failure: false,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+    };
+    let err = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::ZeroMaxSelections {}
+    ));
+
+    let instantiate = InstantiateMsg {
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 1,
+            winners: 0,
+        },
+        ..instantiate
+    };
+    let err = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::ZeroWinners {}
+    ));
+}
+
+#[test]
+fn test_approval_multiple_winners_executed() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
     let core_addr = instantiate_with_staked_balances_governance(
@@ -4168,30 +4890,38 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
             min_voting_period: None,
             max_voting_period: Duration::Height(6),
             only_members_execute: false,
-            allow_revoting: true,
+            allow_revoting: false,
             deposit_info: None,
-            voting_strategy: VotingStrategy::SingleChoice {
-                quorum: PercentageThreshold::Majority {},
+            voting_strategy: VotingStrategy::Approval {
+                quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+                max_selections: 2,
+                winners: 2,
             },
             close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
         })
         .unwrap(),
         Some(vec![
             Cw20Coin {
                 address: "a-1".to_string(),
-                amount: Uint128::new(100_000_000),
+                amount: Uint128::new(100),
             },
             Cw20Coin {
                 address: "a-2".to_string(),
-                amount: Uint128::new(100_000_000),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(50),
             },
         ]),
     );
-
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            core_addr,
+            core_addr.clone(),
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
@@ -4200,194 +4930,1185 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         .unwrap();
     let proposal_module = governance_modules.into_iter().next().unwrap().address;
 
-    let options = vec![
-        MultipleChoiceOption {
-            description: "multiple choice option 1".to_string(),
-            msgs: None,
-        },
-        MultipleChoiceOption {
-            description: "multiple choice option 2".to_string(),
-            msgs: None,
-        },
-    ];
-    let mc_options = MultipleChoiceOptions { options };
-
-    // Create a basic proposal with 2 options
+    // Each option sets an item in the DAO when executed.
+    let set_item = |key: &str| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: core_addr.to_string(),
+            msg: to_binary(&cw_core::msg::ExecuteMsg::SetItem {
+                key: key.to_string(),
+                addr: "a-1".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+    let options = (0..3)
+        .map(|idx| MultipleChoiceOption {
+            description: format!("option {}", idx),
+            msgs: Some(vec![set_item(&format!("option-{}", idx))]),
+        })
+        .collect();
     app.execute_contract(
         Addr::unchecked("a-1"),
         proposal_module.clone(),
         &ExecuteMsg::Propose {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
-            choices: mc_options,
+            choices: MultipleChoiceOptions { options },
         },
         &[],
     )
     .unwrap();
 
-    // a-1 votes, vote_weights: [100_000_000, 0]
+    let mut vote = |voter: &str, option_id: u32, rankings: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id,
+                    rankings,
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    vote("a-1", 0, vec![1]);
+    vote("a-2", 1, vec![2]);
+    vote("a-3", 0, vec![]);
+
+    // All voting power has been cast and options 0 and 1 have the
+    // most approvals.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(150),
+            Uint128::new(160),
+            Uint128::new(60),
+            Uint128::zero()
+        ]
+    );
+
     app.execute_contract(
         Addr::unchecked("a-1"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+        proposal_module,
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let item = |key: &str| -> Option<String> {
+        let res: cw_core::query::GetItemResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &cw_core::msg::QueryMsg::GetItem {
+                    key: key.to_string(),
+                },
+            )
+            .unwrap();
+        res.item
+    };
+    assert_eq!(item("option-0"), Some("a-1".to_string()));
+    assert_eq!(item("option-1"), Some("a-1".to_string()));
+    assert_eq!(item("option-2"), None);
+}
+
+/// Tests that revoting is stored at a per-proposal level.
+/// Proposals created while revoting is enabled should not
+/// have it disabled if a config change turns if off.
+#[test]
+fn test_allow_revoting_config_changes() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: true,
+            deposit_info: None,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        })
+        .unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+        ]),
+    );
+
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = governance_modules.into_iter().next().unwrap().address;
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let mc_options = MultipleChoiceOptions { options };
+
+    // Create a basic proposal with 2 options that allows revoting
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: mc_options.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Disable revoting
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            deposit_info: None,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Assert that proposal_id: 1 still allows revoting
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(proposal.proposal.allow_revoting);
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // New proposals should not allow revoting
+    app.execute_contract(
+        Addr::unchecked("a-2"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A very complex text proposal".to_string(),
+            description: "A very complex text proposal".to_string(),
+            choices: mc_options,
         },
         &[],
     )
     .unwrap();
 
-    // a-2 votes, vote_weights: [100_000_000, 100_000_000]
     app.execute_contract(
         Addr::unchecked("a-2"),
         proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-2"),
+            proposal_module,
+            &ExecuteMsg::Vote {
+                proposal_id: 2,
+                vote: MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+}
+
+/// Tests that we error if a revote casts the same vote as the
+/// previous vote.
+#[test]
+fn test_revoting_same_vote_twice() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: true,
+            deposit_info: None,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        })
+        .unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+        ]),
+    );
+
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = governance_modules.into_iter().next().unwrap().address;
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let mc_options = MultipleChoiceOptions { options };
+
+    // Create a basic proposal with 2 options that allows revoting
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: mc_options,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Cast a vote
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
     .unwrap();
 
-    app.update_block(next_block);
-
-    // Assert that both vote options have equal vote weights at some block
-    let proposal: ProposalResponse = app
+    // Revote for the same option as currently voted
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            proposal_module,
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    // Can't cast the same vote twice.
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+}
+
+/// Tests that revoting into a non-existing vote option
+/// does not invalidate the initial vote
+#[test]
+fn test_invalid_revote_does_not_invalidate_initial_vote() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: true,
+            deposit_info: None,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+        })
+        .unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+        ]),
+    );
+
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = governance_modules.into_iter().next().unwrap().address;
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let mc_options = MultipleChoiceOptions { options };
+
+    // Create a basic proposal with 2 options
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: mc_options,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // a-1 votes, vote_weights: [100_000_000, 0]
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // a-2 votes, vote_weights: [100_000_000, 100_000_000]
+    app.execute_contract(
+        Addr::unchecked("a-2"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(next_block);
+
+    // Assert that both vote options have equal vote weights at some block
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights[0],
+        Uint128::new(100_000_000),
+    );
+    assert_eq!(
+        proposal.proposal.votes.vote_weights[1],
+        Uint128::new(100_000_000),
+    );
+
+    // Time passes..
+    app.update_block(|b| b.height += 3);
+
+    // Last moment a-2 has a change of mind and attempts
+    // to vote for a non-existing option
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-2"),
+            proposal_module,
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: 99,
+                    rankings: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    // Assert that prior votes remained the same
+    assert_eq!(
+        proposal.proposal.votes.vote_weights[0],
+        Uint128::new(100_000_000),
+    );
+    assert_eq!(
+        proposal.proposal.votes.vote_weights[1],
+        Uint128::new(100_000_000),
+    );
+    assert!(matches!(err, ContractError::InvalidVote {}));
+}
+
+#[test]
+fn test_return_deposit_to_dao_on_proposal_failure() {
+    let (mut app, core_addr) = do_test_votes_cw20_balances(
+        vec![TestMultipleChoiceVote {
+            voter: "blue".to_string(),
+            position: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
+        }),
+        false,
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_modules = core_state.proposal_modules;
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_multiple = proposal_modules.into_iter().next().unwrap().address;
+
+    // Make the proposal expire. It has now failed.
+    app.update_block(|block| block.height += 10);
+
+    // Close the proposal, this should work as the proposal is now
+    // open and expired.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        proposal_multiple.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Check that a refund was issued to the DAO.
+    let proposal_config: Config = app
+        .wrap()
+        .query_wasm_smart(proposal_multiple, &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = proposal_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: core_addr.into_string(),
+            },
+        )
+        .unwrap();
+
+    // Deposit should now belong to the DAO.
+    assert_eq!(balance.balance, Uint128::new(1));
+}
+
+#[test]
+fn test_close_failed_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let quorum = PercentageThreshold::Majority {};
+    let voting_strategy = VotingStrategy::SingleChoice { quorum };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        max_voting_period,
+        voting_strategy,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+    };
+
+    let governance_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        None,
+        None,
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = govmod_config.dao;
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Stake some tokens so we can propose
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(2000),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let msg = cw20::Cw20ExecuteMsg::Burn {
+        amount: Uint128::new(2000),
+    };
+    let binary_msg = to_binary(&msg).unwrap();
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "Burn or burn".to_string(),
+            msgs: Some(vec![WasmMsg::Execute {
+                contract_addr: token_contract.to_string(),
+                msg: binary_msg,
+                funds: vec![],
+            }
+            .into()]),
+        },
+        MultipleChoiceOption {
+            description: "Don't burn".to_string(),
+            msgs: None,
+        },
+    ];
+
+    let mc_options = MultipleChoiceOptions { options };
+
+    // Overburn tokens
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            choices: mc_options.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Vote on proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Update block
+    let timestamp = Timestamp::from_seconds(300_000_000);
+    app.update_block(|block| block.time = timestamp);
+
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let failed: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+
+    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
+    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+    // With disabled feature
+    // Disable feature first
+    {
+        let original: Config = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "Disable closing failed proposals".to_string(),
+                description: "We want to re-execute failed proposals".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![
+                        MultipleChoiceOption {
+                            description: "Disable closing failed proposals".to_string(),
+                            msgs: Some(vec![WasmMsg::Execute {
+                                contract_addr: govmod_single.to_string(),
+                                msg: to_binary(&ExecuteMsg::UpdateConfig {
+                                    voting_strategy: VotingStrategy::SingleChoice { quorum },
+                                    max_voting_period: original.max_voting_period,
+                                    min_voting_period: original.min_voting_period,
+                                    only_members_execute: original.only_members_execute,
+                                    allow_revoting: false,
+                                    dao: original.dao.to_string(),
+                                    deposit_info: None,
+                                    close_proposal_on_execution_failure: false,
+                                    voting_power_curve: VotingPowerCurve::Linear {},
+                                    timelock: None,
+                                    vetoer: None,
+                                })
+                                .unwrap(),
+                                funds: vec![],
+                            }
+                            .into()]),
+                        },
+                        MultipleChoiceOption {
+                            description: "Don't disable".to_string(),
+                            msgs: None,
+                        },
+                    ],
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Vote on proposal
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 2,
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Execute proposal
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 2 },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Overburn tokens (again), this time without reverting
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            choices: mc_options,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Vote on proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 3 },
+        &[],
+    )
+    .expect_err("Should be sub overflow");
+
+    // Status should still be passed
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 3 })
+        .unwrap();
+
+    // not reverted
+    assert_eq!(updated.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_no_double_refund_on_execute_fail_and_close() {
+    let mut app = App::default();
+    let proposal_module_id = app.store_code(proposal_contract());
+
+    let voting_strategy = VotingStrategy::SingleChoice {
+        quorum: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        voting_strategy,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            // Important to set to true here as we want to be sure
+            // that we don't get a second refund on close. Refunds on
+            // close only happen if this is true.
+            refund_policy: DepositRefundPolicy::Always {},
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+    };
+
+    let core_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        proposal_module_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            // One token for sending to the DAO treasury, one token
+            // for staking, one token for paying the proposal deposit.
+            amount: Uint128::new(3),
+        }]),
+        None,
+    );
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+
+    let proposal_config: Config = app
+        .wrap()
+        .query_wasm_smart(proposal_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = proposal_config.dao;
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Stake a token so we can propose.
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(1),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Send some tokens to the proposal module so it has the ability
+    // to double refund if the code is buggy.
+    let msg = cw20::Cw20ExecuteMsg::Transfer {
+        recipient: proposal_single.to_string(),
+        amount: Uint128::new(1),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+
+    let msg = cw20::Cw20ExecuteMsg::Burn {
+        amount: Uint128::new(2000),
+    };
+    let binary_msg = to_binary(&msg).unwrap();
+
+    // Increase allowance to pay the proposal deposit.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: proposal_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let choices = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "Burning more tokens, than dao treasury have".to_string(),
+                msgs: Some(vec![WasmMsg::Execute {
+                    contract_addr: token_contract.to_string(),
+                    msg: binary_msg,
+                    funds: vec![],
+                }
+                .into()]),
+            },
+            MultipleChoiceOption {
+                description: "hi there".to_string(),
+                msgs: None,
+            },
+        ],
+    };
+    // proposal to overburn tokens
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            choices,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Vote on proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Update block
+    let timestamp = Timestamp::from_seconds(300_000_000);
+    app.update_block(|block| block.time = timestamp);
+
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let failed: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module.clone(),
+            proposal_single.clone(),
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Open);
-    assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
-        Uint128::new(100_000_000),
-    );
-    assert_eq!(
-        proposal.proposal.votes.vote_weights[1],
-        Uint128::new(100_000_000),
-    );
 
-    // Time passes..
-    app.update_block(|b| b.height += 3);
+    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
+    assert_eq!(failed.proposal.last_updated, app.block_info().time);
 
-    // Last moment a-2 has a change of mind and attempts
-    // to vote for a non-existing option
+    // Check that our deposit has been refunded.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token_contract.to_string(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(balance.balance, Uint128::new(1));
+
+    // Close the proposal - this should fail as it was executed.
     let err: ContractError = app
         .execute_contract(
-            Addr::unchecked("a-2"),
-            proposal_module,
-            &ExecuteMsg::Vote {
-                proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 99 },
-            },
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_single,
+            &ExecuteMsg::Close { proposal_id: 1 },
             &[],
         )
         .unwrap_err()
         .downcast()
         .unwrap();
-    // Assert that prior votes remained the same
-    assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
-        Uint128::new(100_000_000),
-    );
-    assert_eq!(
-        proposal.proposal.votes.vote_weights[1],
-        Uint128::new(100_000_000),
-    );
-    assert!(matches!(err, ContractError::InvalidVote {}));
-}
-
-#[test]
-fn test_return_deposit_to_dao_on_proposal_failure() {
-    let (mut app, core_addr) = do_test_votes_cw20_balances(
-        vec![TestMultipleChoiceVote {
-            voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        VotingStrategy::SingleChoice {
-            quorum: PercentageThreshold::Majority {},
-        },
-        Status::Open,
-        Some(Uint128::new(100)),
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: false,
-        }),
-        false,
-    );
-
-    let core_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
-        .unwrap();
-    let proposal_modules = core_state.proposal_modules;
-
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_multiple = proposal_modules.into_iter().next().unwrap().address;
-
-    // Make the proposal expire. It has now failed.
-    app.update_block(|block| block.height += 10);
 
-    // Close the proposal, this should work as the proposal is now
-    // open and expired.
-    app.execute_contract(
-        Addr::unchecked("keze"),
-        proposal_multiple.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
 
-    // Check that a refund was issued to the DAO.
-    let proposal_config: Config = app
-        .wrap()
-        .query_wasm_smart(proposal_multiple, &QueryMsg::Config {})
-        .unwrap();
-    let CheckedDepositInfo { token, .. } = proposal_config.deposit_info.unwrap();
+    // Check that our deposit was not refunded a second time on close.
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            token,
+            token_contract.to_string(),
             &cw20::Cw20QueryMsg::Balance {
-                address: core_addr.into_string(),
+                address: CREATOR_ADDR.to_string(),
             },
         )
         .unwrap();
 
-    // Deposit should now belong to the DAO.
     assert_eq!(balance.balance, Uint128::new(1));
 }
 
 #[test]
-fn test_close_failed_proposal() {
+fn test_timestamp_updated() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
-    let quorum = PercentageThreshold::Majority {};
-    let voting_strategy = VotingStrategy::SingleChoice { quorum };
+    let voting_strategy = VotingStrategy::SingleChoice {
+        quorum: PercentageThreshold::Majority {},
+    };
     let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        max_voting_period,
         voting_strategy,
+        max_voting_period,
         min_voting_period: None,
         only_members_execute: false,
-        allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        allow_revoting: false,
     };
 
-    let governance_addr = instantiate_with_staking_active_threshold(
+    let governance_addr = instantiate_with_cw20_balances_governance(
         &mut app,
         govmod_id,
         to_binary(&instantiate).unwrap(),
-        None,
-        None,
+        Some(vec![
+            Cw20Coin {
+                address: "voter".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "voter2".to_string(),
+                amount: Uint128::new(2),
+            },
+        ]),
     );
+
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
@@ -4399,110 +6120,119 @@ fn test_close_failed_proposal() {
         )
         .unwrap();
 
-    assert_eq!(governance_modules.len(), 1);
     let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let dao = govmod_config.dao;
-    let voting_module: Addr = app
-        .wrap()
-        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
-        .unwrap();
-    let staking_contract: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module.clone(),
-            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
-        )
-        .unwrap();
-    let token_contract: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
-        )
-        .unwrap();
-
-    // Stake some tokens so we can propose
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(2000),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
-    };
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &msg,
-        &[],
-    )
-    .unwrap();
-    app.update_block(next_block);
-
-    let msg = cw20::Cw20ExecuteMsg::Burn {
-        amount: Uint128::new(2000),
-    };
-    let binary_msg = to_binary(&msg).unwrap();
-
     let options = vec![
         MultipleChoiceOption {
-            description: "Burn or burn".to_string(),
-            msgs: Some(vec![WasmMsg::Execute {
-                contract_addr: token_contract.to_string(),
-                msg: binary_msg,
-                funds: vec![],
-            }
-            .into()]),
+            description: "Option 1".to_string(),
+            msgs: None,
         },
         MultipleChoiceOption {
-            description: "Don't burn".to_string(),
+            description: "Option 2".to_string(),
             msgs: None,
         },
     ];
 
     let mc_options = MultipleChoiceOptions { options };
 
-    // Overburn tokens
+    // Create 2 proposals.
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("voter"),
         govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple burn tokens proposal".to_string(),
-            description: "Burning more tokens, than dao treasury have".to_string(),
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
             choices: mc_options.clone(),
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            choices: mc_options,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let created_1: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    let current_block = app.block_info();
+
+    // Verify created and last updated
+    assert_eq!(created_1.proposal.created, current_block.time);
+    assert_eq!(created_1.proposal.last_updated, current_block.time);
+
+    let created_2: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+
+    // Verify created and last updated
+    assert_eq!(created_2.proposal.created, current_block.time);
+    assert_eq!(created_2.proposal.last_updated, current_block.time);
+
+    // Update block
+    let timestamp = Timestamp::from_seconds(300_000_000);
+    app.update_block(|block| block.time = timestamp);
+
     // Vote on proposal
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("voter"),
         govmod_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
     .unwrap();
 
+    // Expect that last_updated changed because of status change
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+
+    assert_eq!(updated.proposal.last_updated, app.block_info().time);
+    assert_eq!(updated.proposal.status, Status::Passed);
+
     // Update block
-    let timestamp = Timestamp::from_seconds(300_000_000);
+    let timestamp = Timestamp::from_seconds(500_000_000);
     app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
 
     // Execute proposal
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("voter"),
         govmod_single.clone(),
         &ExecuteMsg::Execute { proposal_id: 1 },
         &[],
     )
     .unwrap();
 
-    let failed: ProposalResponse = app
+    // Status should have changed to 'Executed'
+    let updated: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
@@ -4510,385 +6240,553 @@ fn test_close_failed_proposal() {
         )
         .unwrap();
 
-    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
-    assert_eq!(failed.proposal.last_updated, app.block_info().time);
-    // With disabled feature
-    // Disable feature first
-    {
-        let original: Config = app
-            .wrap()
-            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-            .unwrap();
-
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &ExecuteMsg::Propose {
-                title: "Disable closing failed proposals".to_string(),
-                description: "We want to re-execute failed proposals".to_string(),
-                choices: MultipleChoiceOptions {
-                    options: vec![
-                        MultipleChoiceOption {
-                            description: "Disable closing failed proposals".to_string(),
-                            msgs: Some(vec![WasmMsg::Execute {
-                                contract_addr: govmod_single.to_string(),
-                                msg: to_binary(&ExecuteMsg::UpdateConfig {
-                                    voting_strategy: VotingStrategy::SingleChoice { quorum },
-                                    max_voting_period: original.max_voting_period,
-                                    min_voting_period: original.min_voting_period,
-                                    only_members_execute: original.only_members_execute,
-                                    allow_revoting: false,
-                                    dao: original.dao.to_string(),
-                                    deposit_info: None,
-                                    close_proposal_on_execution_failure: false,
-                                })
-                                .unwrap(),
-                                funds: vec![],
-                            }
-                            .into()]),
-                        },
-                        MultipleChoiceOption {
-                            description: "Don't disable".to_string(),
-                            msgs: None,
-                        },
-                    ],
-                },
-            },
-            &[],
-        )
-        .unwrap();
-
-        // Vote on proposal
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &ExecuteMsg::Vote {
-                proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 0 },
-            },
-            &[],
-        )
-        .unwrap();
-
-        // Execute proposal
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &ExecuteMsg::Execute { proposal_id: 2 },
-            &[],
-        )
-        .unwrap();
-    }
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Executed);
 
-    // Overburn tokens (again), this time without reverting
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        govmod_single.clone(),
-        &ExecuteMsg::Propose {
-            title: "A simple burn tokens proposal".to_string(),
-            description: "Burning more tokens, than dao treasury have".to_string(),
-            choices: mc_options,
-        },
-        &[],
-    )
-    .unwrap();
+    let timestamp = Timestamp::from_seconds(700_000_000);
+    app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
 
-    // Vote on proposal
+    // Vote no on second proposal
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("voter"),
         govmod_single.clone(),
         &ExecuteMsg::Vote {
-            proposal_id: 3,
-            vote: MultipleChoiceVote { option_id: 0 },
+            proposal_id: 2,
+            vote: MultipleChoiceVote {
+                option_id: 2,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
     .unwrap();
 
-    // Execute proposal
+    // Status should have changed to 'Rejected'
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Rejected);
+
+    let timestamp = Timestamp::from_seconds(900_000_000);
+    app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
+
+    // Close second proposal
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("voter"),
         govmod_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 3 },
+        &ExecuteMsg::Close { proposal_id: 2 },
         &[],
     )
-    .expect_err("Should be sub overflow");
+    .unwrap();
 
-    // Status should still be passed
+    // Status should have changed to 'Closed'
     let updated: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 3 })
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 2 })
         .unwrap();
 
-    // not reverted
-    assert_eq!(updated.proposal.status, Status::Passed);
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Closed);
 }
 
 #[test]
-fn test_no_double_refund_on_execute_fail_and_close() {
-    let mut app = App::default();
-    let proposal_module_id = app.store_code(proposal_contract());
-
-    let voting_strategy = VotingStrategy::SingleChoice {
-        quorum: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let instantiate = InstantiateMsg {
-        voting_strategy,
-        max_voting_period,
+pub fn test_migrate_update_version() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    let config = Config {
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
         min_voting_period: None,
+        max_voting_period: Duration::Time(604800),
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
+        dao: Addr::unchecked("dao"),
+        deposit_info: Some(CheckedDepositInfo {
+            token: CheckedDepositToken::Cw20(Addr::unchecked("token")),
             deposit: Uint128::new(1),
-            // Important to set to true here as we want to be sure
-            // that we don't get a second refund on close. Refunds on
-            // close only happen if this is true.
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
+    // Store the config as it was before refund policies were added.
+    let legacy_config = String::from_utf8(cosmwasm_std::to_vec(&config).unwrap())
+        .unwrap()
+        .replace(
+            r#""refund_policy":{"always":{}}"#,
+            r#""refund_failed_proposals":true"#,
+        );
+    deps.storage.set(b"config", legacy_config.as_bytes());
 
-    let core_addr = instantiate_with_staking_active_threshold(
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(version.contract, CONTRACT_NAME);
+
+    let stored = String::from_utf8(deps.storage.get(b"config").unwrap()).unwrap();
+    assert!(!stored.contains("refund_failed_proposals"));
+    assert_eq!(crate::state::CONFIG.load(&deps.storage).unwrap(), config);
+}
+
+#[test]
+fn test_timelock_and_veto() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: Some(Duration::Height(4)),
+        vetoer: Some(Vetoer::Address {
+            address: "vetoer".to_string(),
+        }),
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
         &mut app,
-        proposal_module_id,
+        govmod_id,
         to_binary(&instantiate).unwrap(),
-        Some(vec![Cw20Coin {
-            address: CREATOR_ADDR.to_string(),
-            // One token for sending to the DAO treasury, one token
-            // for staking, one token for paying the proposal deposit.
-            amount: Uint128::new(3),
-        }]),
         None,
     );
-    let proposal_modules: Vec<ProposalModule> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            core_addr,
+            governance_addr,
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
             },
         )
         .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
-
-    let proposal_config: Config = app
-        .wrap()
-        .query_wasm_smart(proposal_single.clone(), &QueryMsg::Config {})
+    let start_height = app.block_info().height;
+    for proposal_id in 1..=2 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "A simple text proposal".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![
+                        MultipleChoiceOption {
+                            description: "multiple choice option 1".to_string(),
+                            msgs: None,
+                        },
+                        MultipleChoiceOption {
+                            description: "multiple choice option 2".to_string(),
+                            msgs: None,
+                        },
+                    ],
+                },
+            },
+            &[],
+        )
         .unwrap();
-    let dao = proposal_config.dao;
-    let voting_module: Addr = app
-        .wrap()
-        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
         .unwrap();
-    let staking_contract: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module.clone(),
-            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+    }
+
+    // Proposals that pass early are timelocked from when they
+    // passed, not from the end of their voting period.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
-    let token_contract: Addr = app
+    assert!(matches!(
+        err,
+        ContractError::Timelocked { expiration } if expiration == cw_utils::Expiration::AtHeight(start_height + 4)
+    ));
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.timelock_remaining, Some(Duration::Height(4)));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Veto { proposal_id: 1 },
+            &[],
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
 
-    // Stake a token so we can propose.
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(1),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
-    };
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &msg,
+        Addr::unchecked("vetoer"),
+        govmod.clone(),
+        &ExecuteMsg::Veto { proposal_id: 1 },
         &[],
     )
     .unwrap();
-    app.update_block(next_block);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+
+    // Once the timelock ends proposals may no longer be vetoed and
+    // may be executed.
+    app.update_block(|block| block.height += 4);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("vetoer"),
+            govmod.clone(),
+            &ExecuteMsg::Veto { proposal_id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotTimelocked {}));
 
-    // Send some tokens to the proposal module so it has the ability
-    // to double refund if the code is buggy.
-    let msg = cw20::Cw20ExecuteMsg::Transfer {
-        recipient: proposal_single.to_string(),
-        amount: Uint128::new(1),
-    };
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &msg,
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 2 },
         &[],
     )
     .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod, &QueryMsg::Proposal { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
 
-    let msg = cw20::Cw20ExecuteMsg::Burn {
-        amount: Uint128::new(2000),
-    };
-    let binary_msg = to_binary(&msg).unwrap();
+#[test]
+fn test_timelock_starts_when_passed() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    // Increase allowance to pay the proposal deposit.
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-            spender: proposal_single.to_string(),
-            amount: Uint128::new(1),
-            expires: None,
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
         },
-        &[],
-    )
-    .unwrap();
-
-    let choices = MultipleChoiceOptions {
-        options: vec![
-            MultipleChoiceOption {
-                description: "Burning more tokens, than dao treasury have".to_string(),
-                msgs: Some(vec![WasmMsg::Execute {
-                    contract_addr: token_contract.to_string(),
-                    msg: binary_msg,
-                    funds: vec![],
-                }
-                .into()]),
-            },
-            MultipleChoiceOption {
-                description: "hi there".to_string(),
-                msgs: None,
-            },
-        ],
+        min_voting_period: Some(Duration::Height(2)),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: Some(Duration::Height(4)),
+        vetoer: None,
     };
-    // proposal to overburn tokens
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        None,
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
+
+    let start_height = app.block_info().height;
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
+        govmod.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple burn tokens proposal".to_string(),
-            description: "Burning more tokens, than dao treasury have".to_string(),
-            choices,
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![
+                    MultipleChoiceOption {
+                        description: "multiple choice option 1".to_string(),
+                        msgs: None,
+                    },
+                    MultipleChoiceOption {
+                        description: "multiple choice option 2".to_string(),
+                        msgs: None,
+                    },
+                ],
+            },
         },
         &[],
     )
     .unwrap();
-
-    // Vote on proposal
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
+        govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
     .unwrap();
 
-    // Update block
-    let timestamp = Timestamp::from_seconds(300_000_000);
-    app.update_block(|block| block.time = timestamp);
+    // The proposal passed once its min voting period ended, even
+    // though nothing updated its status then.
+    app.update_block(|block| block.height += 5);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.timelock_remaining, Some(Duration::Height(1)));
 
-    // Execute proposal
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::Timelocked { expiration } if expiration == cw_utils::Expiration::AtHeight(start_height + 6)
+    ));
+
+    app.update_block(next_block);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
+        govmod,
         &ExecuteMsg::Execute { proposal_id: 1 },
         &[],
     )
     .unwrap();
+}
 
-    let failed: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            proposal_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+#[test]
+fn test_vetoer_without_timelock() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: Some(Vetoer::Address {
+            address: "vetoer".to_string(),
+        }),
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal-multiple",
+            None,
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert!(matches!(err, ContractError::VetoerWithoutTimelock {}));
+}
 
-    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
-    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+#[test]
+fn test_native_proposal_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    // Check that our deposit has been refunded.
-    let balance: cw20::BalanceResponse = app
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
+        }),
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        None,
+    );
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            token_contract.to_string(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: CREATOR_ADDR.to_string(),
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
             },
         )
         .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(balance.balance, Uint128::new(1));
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(10),
+        }],
+    }))
+    .unwrap();
 
-    // Close the proposal - this should fail as it was executed.
+    let propose = ExecuteMsg::Propose {
+        title: "A simple text proposal".to_string(),
+        description: "A simple text proposal".to_string(),
+        choices: MultipleChoiceOptions {
+            options: vec![
+                MultipleChoiceOption {
+                    description: "multiple choice option 1".to_string(),
+                    msgs: None,
+                },
+                MultipleChoiceOption {
+                    description: "multiple choice option 2".to_string(),
+                    msgs: None,
+                },
+            ],
+        },
+    };
     let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            proposal_single,
-            &ExecuteMsg::Close { proposal_id: 1 },
-            &[],
-        )
+        .execute_contract(Addr::unchecked(CREATOR_ADDR), govmod.clone(), &propose, &[])
         .unwrap_err()
         .downcast()
         .unwrap();
+    assert!(matches!(err, ContractError::VotingError(_)));
 
-    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &propose,
+        &[Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(10),
+        }],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(CREATOR_ADDR, "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
 
-    // Check that our deposit was not refunded a second time on close.
-    let balance: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            token_contract.to_string(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: CREATOR_ADDR.to_string(),
+    // Passed proposals have their deposits refunded on execution.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
             },
-        )
-        .unwrap();
-
-    assert_eq!(balance.balance, Uint128::new(1));
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod,
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(CREATOR_ADDR, "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(10));
 }
 
 #[test]
-fn test_timestamp_updated() {
+fn test_withdraw_proposal() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
-    let voting_strategy = VotingStrategy::SingleChoice {
-        quorum: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        voting_strategy,
-        max_voting_period,
-        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
         only_members_execute: false,
-        deposit_info: None,
+        // Revoting keeps voted on proposals open.
+        allow_revoting: true,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Never {},
+        }),
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
         close_proposal_on_execution_failure: true,
-        allow_revoting: false,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
     };
-
     let governance_addr = instantiate_with_cw20_balances_governance(
         &mut app,
         govmod_id,
         to_binary(&instantiate).unwrap(),
-        Some(vec![
-            Cw20Coin {
-                address: "voter".to_string(),
-                amount: Uint128::new(3),
-            },
-            Cw20Coin {
-                address: "voter2".to_string(),
-                amount: Uint128::new(2),
-            },
-        ]),
+        None,
     );
-
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
@@ -4899,183 +6797,232 @@ fn test_timestamp_updated() {
             },
         )
         .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
 
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    let options = vec![
-        MultipleChoiceOption {
-            description: "Option 1".to_string(),
-            msgs: None,
-        },
-        MultipleChoiceOption {
-            description: "Option 2".to_string(),
-            msgs: None,
-        },
-    ];
-
-    let mc_options = MultipleChoiceOptions { options };
-
-    // Create 2 proposals.
-    app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            choices: mc_options.clone(),
-        },
-        &[],
-    )
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(20),
+        }],
+    }))
     .unwrap();
 
-    app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            choices: mc_options,
+    let propose = ExecuteMsg::Propose {
+        title: "A simple text proposal".to_string(),
+        description: "A simple text proposal".to_string(),
+        choices: MultipleChoiceOptions {
+            options: vec![
+                MultipleChoiceOption {
+                    description: "multiple choice option 1".to_string(),
+                    msgs: None,
+                },
+                MultipleChoiceOption {
+                    description: "multiple choice option 2".to_string(),
+                    msgs: None,
+                },
+            ],
         },
-        &[],
-    )
-    .unwrap();
-
-    let created_1: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+    };
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &propose,
+            &[Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(10),
+            }],
         )
         .unwrap();
-    let current_block = app.block_info();
+    }
 
-    // Verify created and last updated
-    assert_eq!(created_1.proposal.created, current_block.time);
-    assert_eq!(created_1.proposal.last_updated, current_block.time);
+    let withdraw = |app: &mut App, sender: &str, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            govmod.clone(),
+            &ExecuteMsg::Withdraw { proposal_id },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
 
-    let created_2: ProposalResponse = app
+    // Only the proposer may withdraw a proposal.
+    let err = withdraw(&mut app, "ekez", 1).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Withdrawn proposals have their deposits refunded in full.
+    withdraw(&mut app, CREATOR_ADDR, 1).unwrap();
+    let balance = app.wrap().query_balance(CREATOR_ADDR, "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(10));
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 2 },
-        )
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Withdrawn);
 
-    // Verify created and last updated
-    assert_eq!(created_2.proposal.created, current_block.time);
-    assert_eq!(created_2.proposal.last_updated, current_block.time);
-
-    // Update block
-    let timestamp = Timestamp::from_seconds(300_000_000);
-    app.update_block(|block| block.time = timestamp);
+    let err = withdraw(&mut app, CREATOR_ADDR, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
 
-    // Vote on proposal
+    // Proposals may not be withdrawn once voted on.
     app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
         &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            proposal_id: 2,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: None,
         },
         &[],
     )
     .unwrap();
+    let err = withdraw(&mut app, CREATOR_ADDR, 2).unwrap_err();
+    assert!(matches!(err, ContractError::WithdrawAfterVote {}));
+}
 
-    // Expect that last_updated changed because of status change
-    let updated: ProposalResponse = app
+#[test]
+fn test_vote_rationale() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: true,
+        deposit_info: None,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        None,
+    );
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(updated.proposal.last_updated, app.block_info().time);
-    assert_eq!(updated.proposal.status, Status::Passed);
-
-    // Update block
-    let timestamp = Timestamp::from_seconds(500_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
-
-    // Execute proposal
     app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![
+                    MultipleChoiceOption {
+                        description: "multiple choice option 1".to_string(),
+                        msgs: None,
+                    },
+                    MultipleChoiceOption {
+                        description: "multiple choice option 2".to_string(),
+                        msgs: None,
+                    },
+                ],
+            },
+        },
         &[],
     )
     .unwrap();
 
-    // Status should have changed to 'Executed'
-    let updated: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+    // Rationales longer than the maximum are rejected.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    rankings: vec![],
+                },
+                rationale: Some("a".repeat(MAX_RATIONALE_LENGTH as usize + 1)),
+            },
+            &[],
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::RationaleTooLong { .. })
+    ));
 
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Executed);
-
-    let timestamp = Timestamp::from_seconds(700_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
-
-    // Vote no on second proposal
     app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
         &ExecuteMsg::Vote {
-            proposal_id: 2,
-            vote: MultipleChoiceVote { option_id: 2 },
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 0,
+                rankings: vec![],
+            },
+            rationale: Some("Option 1 is cheaper.".to_string()),
         },
         &[],
     )
     .unwrap();
 
-    // Status should have changed to 'Rejected'
-    let updated: ProposalResponse = app
+    let vote: VoteResponse = app
         .wrap()
         .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 2 },
+            govmod.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: CREATOR_ADDR.to_string(),
+            },
         )
         .unwrap();
+    assert_eq!(
+        vote.vote.unwrap().rationale,
+        Some("Option 1 is cheaper.".to_string())
+    );
 
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Rejected);
-
-    let timestamp = Timestamp::from_seconds(900_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
-
-    // Close second proposal
+    // Revoting replaces the rationale along with the vote.
     app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 2 },
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote {
+                option_id: 1,
+                rankings: vec![],
+            },
+            rationale: None,
+        },
         &[],
     )
     .unwrap();
 
-    // Status should have changed to 'Closed'
-    let updated: ProposalResponse = app
+    let votes: VoteListResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 2 })
+        .query_wasm_smart(
+            govmod,
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
-
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Closed);
-}
-
-#[test]
-pub fn test_migrate_update_version() {
-    let mut deps = mock_dependencies();
-    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
-    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
-    let version = cw2::get_contract_version(&deps.storage).unwrap();
-    assert_eq!(version.version, CONTRACT_VERSION);
-    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(votes.votes.len(), 1);
+    assert_eq!(votes.votes[0].rationale, None);
 }
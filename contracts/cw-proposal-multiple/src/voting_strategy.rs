@@ -1,24 +1,69 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use voting::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
+use voting::threshold::{validate_quorum, PercentageThreshold};
+
+use crate::ContractError;
 
 /// Determines the way votes are counted.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters rank the options in order of preference and the winner
+    /// is selected by instant runoff. Each round the option(s) with
+    /// the fewest votes are eliminated and their ballots transfer to
+    /// the next preference until an option holds a majority of the
+    /// remaining ballots. "None of the above" is ranked like any other
+    /// option: ballots ranking it transfer onward if it is eliminated,
+    /// and the proposal is rejected if it wins the runoff.
+    RankedChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters may approve of any number of options, up to
+    /// `max_selections`, and their full voting power is counted
+    /// towards each of them. The `winners` options with the most
+    /// approvals win, so long as each was approved by a quorum of
+    /// voting power and by more voting power than "None of the
+    /// above". The messages of every winning option are executed.
+    ///
+    /// Options tied for the last winning place are all left out so
+    /// that no more than `winners` options ever win.
+    Approval {
+        quorum: PercentageThreshold,
+        max_selections: u32,
+        winners: u32,
+    },
 }
 
 impl VotingStrategy {
-    pub fn validate(&self) -> Result<(), ThresholdError> {
+    pub fn validate(&self) -> Result<(), ContractError> {
         match self {
-            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::SingleChoice { quorum } | VotingStrategy::RankedChoice { quorum } => {
+                Ok(validate_quorum(quorum)?)
+            }
+            VotingStrategy::Approval {
+                quorum,
+                max_selections,
+                winners,
+            } => {
+                if *max_selections == 0 {
+                    return Err(ContractError::ZeroMaxSelections {});
+                }
+                if *winners == 0 {
+                    return Err(ContractError::ZeroWinners {});
+                }
+                Ok(validate_quorum(quorum)?)
+            }
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
-            VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::RankedChoice { quorum }
+            | VotingStrategy::Approval { quorum, .. } => *quorum,
         }
     }
 }
@@ -0,0 +1,81 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+use cw_core_interface::voting::InfoResponse;
+use cw_proposal_optimistic::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{
+        ProposalListResponse, ProposalResponse, ProposerListResponse, VoteListResponse,
+        VoteResponse,
+    },
+    state::Config,
+};
+use indexable_hooks::{DisabledHooksResponse, HookFailuresResponse, HooksResponse};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+
+    export_schema(&schema_for!(InfoResponse), &out_dir);
+    export_schema(&schema_for!(ProposalResponse), &out_dir);
+    export_schema(&schema_for!(VoteResponse), &out_dir);
+
+    // Auto TS code generation expects the query return type as QueryNameResponse
+    // Here we map query resonses to the correct name
+    export_schema_with_title(&schema_for!(Config), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(ProposalListResponse),
+        &out_dir,
+        "ListProposalsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(VoteListResponse),
+        &out_dir,
+        "ListVotesResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ProposerListResponse),
+        &out_dir,
+        "ListProposersResponse",
+    );
+    export_schema_with_title(&schema_for!(u64), &out_dir, "ProposalCountResponse");
+    export_schema_with_title(
+        &schema_for!(ProposalListResponse),
+        &out_dir,
+        "ReverseProposalsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HooksResponse),
+        &out_dir,
+        "ProposalHooksResponse",
+    );
+    export_schema_with_title(&schema_for!(HooksResponse), &out_dir, "VoteHooksResponse");
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledProposalHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledVoteHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "ProposalHookFailuresResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "VoteHookFailuresResponse",
+    );
+    export_schema_with_title(&schema_for!(VoteResponse), &out_dir, "GetVoteResponse");
+}
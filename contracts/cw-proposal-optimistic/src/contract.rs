@@ -0,0 +1,805 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::IsActiveResponse;
+use cw_storage_plus::Bound;
+use cw_utils::Duration;
+use indexable_hooks::{HookOptions, Hooks};
+use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks, ProposalHookFilter};
+use vote_hooks::{new_vote_hooks, VoteHookMsgV2};
+
+use voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use voting::reply::{mask_proposal_execution_proposal_id, TaggedReplyId};
+use voting::status::Status;
+use voting::threshold::Threshold;
+use voting::voting::{get_total_power, get_voting_power, Vote, Votes};
+
+use crate::proposal::OptimisticProposal;
+use crate::state::{Config, PROPOSERS};
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    proposal::advance_proposal_id,
+    query::{
+        ProposalListResponse, ProposalResponse, ProposerListResponse, VoteInfo, VoteListResponse,
+        VoteResponse,
+    },
+    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cw-proposal-optimistic";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    msg.veto_threshold.validate()?;
+    validate_challenge_period(msg.challenge_period)?;
+
+    let dao = info.sender;
+    let config = Config {
+        veto_threshold: msg.veto_threshold,
+        challenge_period: msg.challenge_period,
+        only_members_execute: msg.only_members_execute,
+        close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        dao: dao.clone(),
+    };
+
+    for proposer in msg.proposers {
+        let proposer = deps.api.addr_validate(&proposer)?;
+        PROPOSERS.save(deps.storage, proposer, &Empty {})?;
+    }
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+/// A challenge period of zero would cause proposals to pass as soon
+/// as they are created, leaving no opportunity for a veto.
+fn validate_challenge_period(challenge_period: Duration) -> Result<(), ContractError> {
+    match challenge_period {
+        Duration::Height(0) | Duration::Time(0) => Err(ContractError::ZeroChallengePeriod {}),
+        _ => Ok(()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+        } => execute_propose(deps, env, info.sender, title, description, msgs),
+        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            veto_threshold,
+            challenge_period,
+            only_members_execute,
+            close_proposal_on_execution_failure,
+            dao,
+        } => execute_update_config(
+            deps,
+            info,
+            veto_threshold,
+            challenge_period,
+            only_members_execute,
+            close_proposal_on_execution_failure,
+            dao,
+        ),
+        ExecuteMsg::UpdateProposers { to_add, to_remove } => {
+            execute_update_proposers(deps, info, to_add, to_remove)
+        }
+        ExecuteMsg::AddProposalHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+            filter,
+        } => execute_add_proposal_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+            filter,
+        ),
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_proposal_hook(deps, env, info, address)
+        }
+        ExecuteMsg::AddVoteHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+        } => execute_add_vote_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+        ),
+        ExecuteMsg::RemoveVoteHook { address } => {
+            execute_remove_vote_hook(deps, env, info, address)
+        }
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let voting_module: Addr = deps
+        .querier
+        .query_wasm_smart(config.dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true });
+
+    if !active_resp.active {
+        return Err(ContractError::InactiveDao {});
+    }
+
+    // Only whitelisted proposers may create proposals. Proposers
+    // need not have voting power.
+    if !PROPOSERS.has(deps.storage, sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let expiration = config.challenge_period.after(&env.block);
+    let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
+
+    let proposal = OptimisticProposal {
+        title,
+        description,
+        proposer: sender.clone(),
+        start_height: env.block.height,
+        expiration,
+        veto_threshold: config.veto_threshold,
+        total_power,
+        msgs,
+        status: Status::Open,
+        votes: Votes::zero(),
+        created: env.block.time,
+        last_updated: env.block.time,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    // Limit the size of proposals so that they may always be
+    // queried. See `cw-proposal-single` for details.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        &env.contract.address,
+        &proposal.msgs,
+    )?;
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.only_members_execute {
+        let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?;
+        if power.is_zero() {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    prop.status = Status::Executed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let response = {
+        if !prop.msgs.is_empty() {
+            let execute_message = WasmMsg::Execute {
+                contract_addr: config.dao.to_string(),
+                msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
+                    msgs: prop.msgs.clone(),
+                })?,
+                funds: vec![],
+            };
+            match config.close_proposal_on_execution_failure {
+                true => {
+                    let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+                    Response::default()
+                        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+                }
+                false => Response::default().add_message(execute_message),
+            }
+        } else {
+            Response::default()
+        }
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+    Ok(response
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let vote_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        config.dao,
+        Some(prop.start_height),
+    )?;
+    if vote_power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    BALLOTS.update(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        |bal| match bal {
+            Some(_) => Err(ContractError::AlreadyVoted {}),
+            None => Ok(Ballot {
+                power: vote_power,
+                vote,
+            }),
+        },
+    )?;
+
+    let old_status = prop.status;
+
+    prop.votes.add_vote(vote, vote_power);
+    prop.update_status(&env.block);
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let new_status = prop.status;
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        new_status,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        VoteHookMsgV2::NewVote {
+            proposal_id,
+            voter: info.sender.clone(),
+            vote,
+            power: vote_power,
+            rationale: None,
+            module: env.contract.address.clone(),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("position", vote.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Update status to ensure that proposals which have been vetoed
+    // are moved to "rejected."
+    prop.update_status(&env.block);
+    if prop.status != Status::Rejected {
+        return Err(ContractError::WrongCloseStatus {});
+    }
+
+    let old_status = prop.status;
+
+    prop.status = Status::Closed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    veto_threshold: Threshold,
+    challenge_period: Duration,
+    only_members_execute: bool,
+    close_proposal_on_execution_failure: bool,
+    dao: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    veto_threshold.validate()?;
+    validate_challenge_period(challenge_period)?;
+    let dao = deps.api.addr_validate(&dao)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            veto_threshold,
+            challenge_period,
+            only_members_execute,
+            close_proposal_on_execution_failure,
+            dao,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_proposers(
+    deps: DepsMut,
+    info: MessageInfo,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for proposer in to_add {
+        let proposer = deps.api.addr_validate(&proposer)?;
+        PROPOSERS.save(deps.storage, proposer, &Empty {})?;
+    }
+    for proposer in to_remove {
+        let proposer = deps.api.addr_validate(&proposer)?;
+        PROPOSERS.remove(deps.storage, proposer);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_proposers")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn add_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validated_address: Addr,
+    options: HookOptions,
+) -> Result<(), ContractError> {
+    hooks
+        .add_hook_with_options(storage, validated_address, options)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn remove_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validate_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .remove_hook(storage, validate_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn execute_add_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+    options: HookOptions,
+    filter: Option<ProposalHookFilter>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    let options = match filter {
+        Some(filter) => filter.apply(deps.api, options)?,
+        None => options,
+    };
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address, options)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_add_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+    options: HookOptions,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(VOTE_HOOKS, deps.storage, validated_address, options)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_vote_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_vote_hook")
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ProposalCount {} => query_proposal_count(deps),
+        QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListProposers { start_after, limit } => {
+            query_list_proposers(deps, start_after, limit)
+        }
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ProposalHooks { start_after, limit } => to_binary(
+            &PROPOSAL_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::VoteHooks { start_after, limit } => to_binary(
+            &VOTE_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::DisabledProposalHooks {} => {
+            to_binary(&PROPOSAL_HOOKS.query_disabled_hooks(deps)?)
+        }
+        QueryMsg::DisabledVoteHooks {} => to_binary(&VOTE_HOOKS.query_disabled_hooks(deps)?),
+        QueryMsg::ProposalHookFailures { address } => to_binary(
+            &PROPOSAL_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookFailures { address } => {
+            to_binary(&VOTE_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&config)
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.into_response(&env.block, id))
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, OptimisticProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .collect();
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let max = start_before.map(Bound::exclusive);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, OptimisticProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .collect();
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
+    let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
+    to_binary(&proposal_count)
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let vote = ballot.map(|ballot| VoteInfo {
+        voter,
+        vote: ballot.vote,
+        power: ballot.power,
+    });
+    to_binary(&VoteResponse { vote })
+}
+
+pub fn query_list_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let votes = BALLOTS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, ballot) = item?;
+            Ok(VoteInfo {
+                voter,
+                vote: ballot.vote,
+                power: ballot.power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoteListResponse { votes })
+}
+
+pub fn query_list_proposers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let proposers = PROPOSERS
+        .keys(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposerListResponse { proposers })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+                Some(mut prop) => {
+                    prop.status = Status::ExecutionFailed;
+                    // Update proposal's last updated timestamp.
+                    prop.last_updated = env.block.time;
+                    Ok(prop)
+                }
+                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
+            })?;
+            Ok(Response::new().add_attribute("proposal execution failed", proposal_id.to_string()))
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let (addr, outcome) =
+                PROPOSAL_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} proposal hook"), format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedVoteHook(idx) => {
+            let (addr, outcome) =
+                VOTE_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} vote hook"), format!("{addr}:{idx}")))
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::StdError;
+use indexable_hooks::HookError;
+use thiserror::Error;
+use voting::reply::error::TagError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    HookError(#[from] HookError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{0}")]
+    ThresholdError(#[from] voting::threshold::ThresholdError),
+
+    #[error("Challenge period must be greater than zero")]
+    ZeroChallengePeriod {},
+
+    #[error("No such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("Proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("Proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("Not registered to vote (no voting power) at time of proposal creation.")]
+    NotRegistered {},
+
+    #[error("Already voted. This proposal does not support revoting.")]
+    AlreadyVoted {},
+
+    #[error("Proposal is not in 'passed' state.")]
+    NotPassed {},
+
+    #[error("Only rejected proposals may be closed.")]
+    WrongCloseStatus {},
+
+    #[error("The DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error("{0}")]
+    Tag(#[from] TagError),
+}
@@ -0,0 +1,22 @@
+//! # cw-proposal-optimistic
+//!
+//! An optimistic proposal module for a DAO DAO DAO. Proposals may
+//! only be created by a list of whitelisted proposers and pass
+//! automatically once their challenge period ends unless enough
+//! members vote "no" to veto them. This suits routine proposals, for
+//! example recurring treasury payouts, which the DAO would rather not
+//! hold a full vote on.
+//!
+//! For more information see the README.
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod query;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,233 @@
+use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::Duration;
+use indexable_hooks::{HookFailurePolicy, HookVersion};
+use proposal_hooks::ProposalHookFilter;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw_core_macros::govmod_query;
+use voting::{threshold::Threshold, voting::Vote};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The threshold of "no" votes at which a proposal is vetoed. For
+    /// example, an absolute percentage of 33% will veto proposals
+    /// once a third of the DAO's voting power has voted no.
+    pub veto_threshold: Threshold,
+    /// The amount of time members have to veto a proposal. Proposals
+    /// which have not been vetoed once this has elapsed pass.
+    pub challenge_period: Duration,
+    /// The addresses that may create proposals.
+    pub proposers: Vec<String>,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the governance module. Only addresses
+    /// in the proposer list may create proposals.
+    Propose {
+        /// The title of the proposal.
+        title: String,
+        /// A description of the proposal.
+        description: String,
+        /// The messages that should be executed in response to this
+        /// proposal passing.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Votes on a proposal. Voting power is determined by the DAO's
+    /// voting power module. Only "no" votes count towards vetoing a
+    /// proposal, though all votes count towards quorum.
+    Vote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The senders position on the proposal.
+        vote: Vote,
+    },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes a proposal that has been vetoed.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config.
+    UpdateConfig {
+        /// The threshold of "no" votes at which a proposal is
+        /// vetoed. This will only apply to proposals created after
+        /// the config update.
+        veto_threshold: Threshold,
+        /// The amount of time members have to veto a proposal. This
+        /// will only apply to proposals created after the config
+        /// update.
+        challenge_period: Duration,
+        /// If set to true only members may execute passed
+        /// proposals. Otherwise, any address may execute a passed
+        /// proposal. Applies to all outstanding and future proposals.
+        only_members_execute: bool,
+        /// If set to true proposals will be closed if their execution
+        /// fails. Otherwise, proposals will remain open after execution
+        /// failure.
+        close_proposal_on_execution_failure: bool,
+        /// The address of the DAO that this governance module is
+        /// associated with.
+        dao: String,
+    },
+    /// Adds and removes addresses from the list of addresses that
+    /// may create proposals. Only the DAO may call this method.
+    UpdateProposers {
+        /// Addresses to add to the proposer list.
+        to_add: Vec<String>,
+        /// Addresses to remove from the proposer list.
+        to_remove: Vec<String>,
+    },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook {
+        address: String,
+        /// The version of the proposal hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+        /// Limits the messages sent to ADDRESS. Defaults to sending
+        /// every message.
+        #[serde(default)]
+        filter: Option<ProposalHookFilter>,
+    },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever the a vote
+    /// is cast. If a consumer contract errors when handling a hook
+    /// message it will be removed from the list of consumers.
+    AddVoteHook {
+        address: String,
+        /// The version of the vote hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+    },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String },
+}
+
+#[govmod_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Gets the governance module's config. Returns `state::Config`.
+    Config {},
+    /// Gets information about a proposal. Returns
+    /// `query::ProposalResponse`.
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been cast in this
+    /// module. Returns `query::ProposalListResponse`.
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been cast in this module
+    /// in decending order of proposal ID. Returns
+    /// `query::ProposalListResponse`.
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this
+    /// module.
+    ProposalCount {},
+    /// Returns a voters position on a propsal. Returns
+    /// `query::VoteResponse`.
+    GetVote { proposal_id: u64, voter: String },
+    /// Lists all of the votes that have been cast on a
+    /// proposal. Returns `VoteListResponse`.
+    ListVotes {
+        /// The proposal to list the votes of.
+        proposal_id: u64,
+        /// The voter to start listing votes after. Ordering is done
+        /// alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of votes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Lists the addresses that may create proposals. Returns
+    /// `query::ProposerListResponse`.
+    ListProposers {
+        /// The address to start listing proposers after. Ordering is
+        /// done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of proposers to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the consumers of proposal hooks for this module in the
+    /// order they were registered. Returns
+    /// indexable_hooks::HooksResponse.
+    ProposalHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the consumers of vote hooks for this module in the order
+    /// they were registered. Returns indexable_hooks::HooksResponse.
+    VoteHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the proposal hooks that have been disabled after failing
+    /// too many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledProposalHooks {},
+    /// Lists the vote hooks that have been disabled after failing too
+    /// many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledVoteHooks {},
+    /// Lists the most recent failed deliveries to a proposal
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    ProposalHookFailures { address: String },
+    /// Lists the most recent failed deliveries to a vote
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    VoteHookFailures { address: String },
+}
@@ -0,0 +1,240 @@
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage, Timestamp, Uint128};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use voting::deposit::CheckedDepositInfo;
+use voting::proposal::Proposal;
+use voting::status::Status;
+use voting::threshold::Threshold;
+use voting::voting::{does_vote_count_pass, Votes};
+
+use crate::query::ProposalResponse;
+use crate::state::PROPOSAL_COUNT;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OptimisticProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created. Voting
+    /// power queries should query for voting power at this block
+    /// height.
+    pub start_height: u64,
+    /// The time at which this proposal's challenge period ends. If
+    /// the proposal has not been vetoed by then it passes.
+    pub expiration: Expiration,
+    /// The threshold of "no" votes at which this proposal is vetoed.
+    pub veto_threshold: Threshold,
+    /// The total amount of voting power at the time of this
+    /// proposal's creation.
+    pub total_power: Uint128,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+
+    pub status: Status,
+    pub votes: Votes,
+
+    /// The timestamp at which this proposal was created.
+    pub created: Timestamp,
+    /// The timestamp at which this proposal's status last
+    /// changed. Proposals pass once their challenge period ends,
+    /// which does not require any interaction with this contract, so
+    /// this field is not updated when a proposal passes.
+    pub last_updated: Timestamp,
+}
+
+impl Proposal for OptimisticProposal {
+    fn proposer(&self) -> Addr {
+        self.proposer.clone()
+    }
+    fn deposit_info(&self) -> Option<CheckedDepositInfo> {
+        None
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl OptimisticProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Proposal statuses are only updated on
+    /// vote, execute, and close events so the status is recomputed
+    /// here in case the challenge period has since ended.
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+        self.update_status(block);
+        ProposalResponse { id, proposal: self }
+    }
+
+    /// Gets the current status of the proposal.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        if self.status == Status::Open && self.is_vetoed(block) {
+            Status::Rejected
+        } else if self.status == Status::Open && self.expiration.is_expired(block) {
+            Status::Passed
+        } else {
+            self.status
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        let new_status = self.current_status(block);
+        // Update last_updated only if status changed.
+        if new_status != self.status {
+            self.last_updated = block.time
+        }
+        self.status = new_status
+    }
+
+    /// Returns true if enough "no" votes have been cast to veto this
+    /// proposal. Before the challenge period ends a proposal is
+    /// vetoed if its "no" votes meet the veto threshold out of all
+    /// voting power that has not abstained. Once it ends, quorum
+    /// based thresholds only consider the votes that were cast.
+    pub fn is_vetoed(&self, block: &BlockInfo) -> bool {
+        match self.veto_threshold {
+            Threshold::AbsolutePercentage { percentage } => {
+                let options = self.total_power - self.votes.abstain;
+                does_vote_count_pass(self.votes.no, options, percentage)
+            }
+            Threshold::ThresholdQuorum { threshold, quorum } => {
+                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
+                    return false;
+                }
+                let options = if self.expiration.is_expired(block) {
+                    self.votes.total() - self.votes.abstain
+                } else {
+                    self.total_power - self.votes.abstain
+                };
+                does_vote_count_pass(self.votes.no, options, threshold)
+            }
+            Threshold::AbsoluteCount { threshold } => self.votes.no >= threshold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::mock_env, Decimal};
+    use voting::threshold::PercentageThreshold;
+
+    fn setup_prop(
+        veto_threshold: Threshold,
+        votes: Votes,
+        total_power: Uint128,
+        is_expired: bool,
+    ) -> (OptimisticProposal, BlockInfo) {
+        let block = mock_env().block;
+        let expiration = match is_expired {
+            true => Expiration::AtHeight(block.height - 5),
+            false => Expiration::AtHeight(block.height + 100),
+        };
+        let prop = OptimisticProposal {
+            title: "Demo".to_string(),
+            description: "Info".to_string(),
+            proposer: Addr::unchecked("test"),
+            start_height: 100,
+            expiration,
+            veto_threshold,
+            total_power,
+            msgs: vec![],
+            status: Status::Open,
+            votes,
+            created: block.time,
+            last_updated: block.time,
+        };
+        (prop, block)
+    }
+
+    fn votes(yes: u128, no: u128, abstain: u128) -> Votes {
+        Votes {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            abstain: Uint128::new(abstain),
+        }
+    }
+
+    #[test]
+    fn test_passes_without_veto() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+
+        let (prop, block) = setup_prop(threshold.clone(), votes(0, 0, 0), Uint128::new(100), false);
+        assert_eq!(prop.current_status(&block), Status::Open);
+
+        let (prop, block) = setup_prop(threshold.clone(), votes(0, 0, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Passed);
+
+        // Proposals pass without any yes votes, and exactly half of
+        // voting power voting no is not a majority.
+        let (prop, block) = setup_prop(threshold, votes(0, 50, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Passed);
+    }
+
+    #[test]
+    fn test_absolute_percentage_veto() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(33)),
+        };
+
+        let (prop, block) =
+            setup_prop(threshold.clone(), votes(0, 32, 0), Uint128::new(100), false);
+        assert_eq!(prop.current_status(&block), Status::Open);
+
+        // Proposals may be vetoed before the challenge period ends.
+        let (prop, block) =
+            setup_prop(threshold.clone(), votes(0, 33, 0), Uint128::new(100), false);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+
+        // Abstaining reduces the voting power needed to veto.
+        let (prop, block) = setup_prop(threshold, votes(0, 30, 10), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+    }
+
+    #[test]
+    fn test_threshold_quorum_veto() {
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+        };
+
+        // Quorum not met.
+        let (prop, block) = setup_prop(threshold.clone(), votes(0, 19, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Passed);
+
+        // Quorum met, but the majority of voting power has not voted
+        // no and the challenge period has not ended.
+        let (prop, block) =
+            setup_prop(threshold.clone(), votes(5, 20, 0), Uint128::new(100), false);
+        assert_eq!(prop.current_status(&block), Status::Open);
+
+        // Once the challenge period ends only cast votes count.
+        let (prop, block) = setup_prop(threshold.clone(), votes(5, 20, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+
+        let (prop, block) = setup_prop(threshold, votes(20, 5, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Passed);
+    }
+
+    #[test]
+    fn test_absolute_count_veto() {
+        let threshold = Threshold::AbsoluteCount {
+            threshold: Uint128::new(10),
+        };
+
+        let (prop, block) = setup_prop(threshold.clone(), votes(0, 9, 0), Uint128::new(100), true);
+        assert_eq!(prop.current_status(&block), Status::Passed);
+
+        let (prop, block) = setup_prop(threshold, votes(0, 10, 0), Uint128::new(100), false);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+    }
+}
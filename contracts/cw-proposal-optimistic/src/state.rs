@@ -0,0 +1,52 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+use indexable_hooks::Hooks;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use voting::{threshold::Threshold, voting::Vote};
+
+use crate::proposal::OptimisticProposal;
+
+/// A vote cast for a proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Ballot {
+    /// The amount of voting power behind the vote.
+    pub power: Uint128,
+    /// The position.
+    pub vote: Vote,
+}
+
+/// The governance module's configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The threshold of "no" votes at which a proposal is vetoed.
+    pub veto_threshold: Threshold,
+    /// The amount of time members have to veto a proposal.
+    pub challenge_period: Duration,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+    /// The address of the DAO that this governance module is
+    /// associated with.
+    pub dao: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, OptimisticProposal> = Map::new("proposals");
+pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+/// The addresses that may create proposals.
+pub const PROPOSERS: Map<Addr, Empty> = Map::new("proposers");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks", "proposal_hook_indices");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_indices");
@@ -0,0 +1,404 @@
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core::state::ProposalModule;
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use cw_utils::Duration;
+use voting::{
+    status::Status,
+    threshold::{PercentageThreshold, Threshold, ThresholdError},
+    voting::Vote,
+};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalResponse, ProposerListResponse},
+    ContractError,
+};
+
+const CREATOR_ADDR: &str = "creator";
+const PROPOSER_ADDR: &str = "ops";
+const DENOM: &str = "ujuno";
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn cw20_balances_voting() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_balance_voting::contract::execute,
+        cw20_balance_voting::contract::instantiate,
+        cw20_balance_voting::contract::query,
+    )
+    .with_reply(cw20_balance_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn cw_gov_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_core::contract::execute,
+        cw_core::contract::instantiate,
+        cw_core::contract::query,
+    )
+    .with_reply(cw_core::contract::reply);
+    Box::new(contract)
+}
+
+fn default_instantiate() -> InstantiateMsg {
+    InstantiateMsg {
+        veto_threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(33)),
+        },
+        challenge_period: Duration::Height(10),
+        proposers: vec![PROPOSER_ADDR.to_string()],
+        only_members_execute: false,
+        close_proposal_on_execution_failure: true,
+    }
+}
+
+/// Instantiates a DAO with an optimistic proposal module and a
+/// treasury of 1000 native tokens. Members "ekez" and "keze" have
+/// 600 and 400 voting power respectively. Returns the DAO and
+/// proposal module addresses.
+fn setup(app: &mut App, instantiate: InstantiateMsg) -> (Addr, Addr) {
+    let cw20_id = app.store_code(cw20_contract());
+    let core_id = app.store_code(cw_gov_contract());
+    let votemod_id = app.store_code(cw20_balances_voting());
+    let proposal_id = app.store_code(proposal_contract());
+
+    let governance_instantiate = cw_core::msg::InstantiateMsg {
+        dao_uri: None,
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: cw_core::msg::ModuleInstantiateInfo {
+            code_id: votemod_id,
+            msg: to_binary(&cw20_balance_voting::msg::InstantiateMsg {
+                token_info: cw20_balance_voting::msg::TokenInfo::New {
+                    code_id: cw20_id,
+                    label: "DAO DAO governance token".to_string(),
+                    name: "DAO".to_string(),
+                    symbol: "DAO".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: "ekez".to_string(),
+                            amount: Uint128::new(600),
+                        },
+                        Cw20Coin {
+                            address: "keze".to_string(),
+                            amount: Uint128::new(400),
+                        },
+                    ],
+                    marketing: None,
+                },
+            })
+            .unwrap(),
+            admin: cw_core::msg::Admin::CoreContract {},
+            label: "DAO DAO voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![cw_core::msg::ModuleInstantiateInfo {
+            code_id: proposal_id,
+            msg: to_binary(&instantiate).unwrap(),
+            admin: cw_core::msg::Admin::CoreContract {},
+            label: "DAO DAO governance module".to_string(),
+        }],
+        initial_items: None,
+    };
+
+    let core_addr = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &governance_instantiate,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: vec![Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(1000),
+        }],
+    }))
+    .unwrap();
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+
+    (core_addr, proposal_module)
+}
+
+/// Creates a proposal paying AMOUNT to "floob".
+fn propose(
+    app: &mut App,
+    proposal_module: &Addr,
+    proposer: &str,
+    amount: u128,
+) -> Result<u64, ContractError> {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Pay the floob".to_string(),
+            description: "Monthly floob payment.".to_string(),
+            msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "floob".to_string(),
+                amount: vec![Coin {
+                    denom: DENOM.to_string(),
+                    amount: Uint128::new(amount),
+                }],
+            })],
+        },
+        &[],
+    )
+    .map_err(|e| e.downcast::<ContractError>().unwrap())?;
+    Ok(app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::ProposalCount {})
+        .unwrap())
+}
+
+fn vote(
+    app: &mut App,
+    proposal_module: &Addr,
+    voter: &str,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote { proposal_id, vote },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn execute(
+    app: &mut App,
+    proposal_module: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_status(app: &App, proposal_module: &Addr, proposal_id: u64) -> Status {
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id })
+        .unwrap();
+    proposal.proposal.status
+}
+
+#[test]
+fn test_passes_after_challenge_period() {
+    let mut app = App::default();
+    let (_, proposal_module) = setup(&mut app, default_instantiate());
+
+    // Members not in the proposer list may not propose.
+    let err = propose(&mut app, &proposal_module, "ekez", 100).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Proposers need not have voting power.
+    let id = propose(&mut app, &proposal_module, PROPOSER_ADDR, 100).unwrap();
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Open);
+
+    // Votes below the veto threshold do not stop the proposal.
+    vote(&mut app, &proposal_module, "ekez", id, Vote::Yes).unwrap();
+    let err = vote(&mut app, &proposal_module, "ekez", id, Vote::No).unwrap_err();
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+
+    let err = execute(&mut app, &proposal_module, "keze", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    app.update_block(|b| b.height += 10);
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Passed);
+    let err = vote(&mut app, &proposal_module, "keze", id, Vote::No).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+
+    execute(&mut app, &proposal_module, "keze", id).unwrap();
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Executed);
+    assert_eq!(
+        app.wrap().query_balance("floob", DENOM).unwrap().amount,
+        Uint128::new(100)
+    );
+}
+
+#[test]
+fn test_veto() {
+    let mut app = App::default();
+    let (_, proposal_module) = setup(&mut app, default_instantiate());
+
+    let id = propose(&mut app, &proposal_module, PROPOSER_ADDR, 100).unwrap();
+
+    // Only members may vote.
+    let err = vote(&mut app, &proposal_module, PROPOSER_ADDR, id, Vote::No).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // Proposals may not be closed unless vetoed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Close { proposal_id: id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // 40% of voting power voting no vetoes the proposal immediately.
+    vote(&mut app, &proposal_module, "keze", id, Vote::No).unwrap();
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Rejected);
+
+    app.update_block(|b| b.height += 10);
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Rejected);
+    let err = execute(&mut app, &proposal_module, "keze", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Close { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_status(&app, &proposal_module, id), Status::Closed);
+}
+
+#[test]
+fn test_execution_failure() {
+    let mut app = App::default();
+    let (_, proposal_module) = setup(&mut app, default_instantiate());
+
+    // The treasury only holds 1000 tokens.
+    let id = propose(&mut app, &proposal_module, PROPOSER_ADDR, 2000).unwrap();
+    app.update_block(|b| b.height += 10);
+    execute(&mut app, &proposal_module, "keze", id).unwrap();
+    assert_eq!(
+        query_status(&app, &proposal_module, id),
+        Status::ExecutionFailed
+    );
+}
+
+#[test]
+fn test_update_proposers() {
+    let mut app = App::default();
+    let (core_addr, proposal_module) = setup(&mut app, default_instantiate());
+
+    let update = ExecuteMsg::UpdateProposers {
+        to_add: vec!["ekez".to_string()],
+        to_remove: vec![PROPOSER_ADDR.to_string()],
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &update,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(core_addr, proposal_module.clone(), &update, &[])
+        .unwrap();
+
+    let proposers: ProposerListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::ListProposers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(proposers.proposers, vec![Addr::unchecked("ekez")]);
+
+    let err = propose(&mut app, &proposal_module, PROPOSER_ADDR, 100).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    propose(&mut app, &proposal_module, "ekez", 100).unwrap();
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let instantiate = |msg: InstantiateMsg, app: &mut App| -> ContractError {
+        app.instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &msg,
+            &[],
+            "optimistic",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    let err = instantiate(
+        InstantiateMsg {
+            challenge_period: Duration::Time(0),
+            ..default_instantiate()
+        },
+        &mut app,
+    );
+    assert!(matches!(err, ContractError::ZeroChallengePeriod {}));
+
+    let err = instantiate(
+        InstantiateMsg {
+            veto_threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::zero(),
+            },
+            ..default_instantiate()
+        },
+        &mut app,
+    );
+    assert!(matches!(
+        err,
+        ContractError::ThresholdError(ThresholdError::ZeroThreshold {})
+    ));
+}
@@ -6,10 +6,14 @@ use cosmwasm_std::Addr;
 use cw_core_interface::voting::InfoResponse;
 use cw_proposal_single::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
+    query::{
+        DelegationResponse, DelegatorListResponse, PendingProposalListResponse,
+        PendingProposalResponse, ProposalListResponse, ProposalResponse,
+        ProposalVersionListResponse, VoteListResponse, VoteResponse,
+    },
     state::Config,
 };
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{DisabledHooksResponse, HookFailuresResponse, HooksResponse};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -25,6 +29,7 @@ fn main() {
     export_schema(&schema_for!(InfoResponse), &out_dir);
     export_schema(&schema_for!(ProposalResponse), &out_dir);
     export_schema(&schema_for!(VoteResponse), &out_dir);
+    export_schema(&schema_for!(DelegationResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
@@ -44,6 +49,16 @@ fn main() {
         &out_dir,
         "ListVotesResponse",
     );
+    export_schema_with_title(
+        &schema_for!(DelegatorListResponse),
+        &out_dir,
+        "ListDelegatorsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ProposalVersionListResponse),
+        &out_dir,
+        "ProposalVersionsResponse",
+    );
     export_schema_with_title(&schema_for!(u64), &out_dir, "ProposalCountResponse");
     export_schema_with_title(
         &schema_for!(ProposalListResponse),
@@ -56,5 +71,35 @@ fn main() {
         "ProposalHooksResponse",
     );
     export_schema_with_title(&schema_for!(HooksResponse), &out_dir, "VoteHooksResponse");
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledProposalHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(DisabledHooksResponse),
+        &out_dir,
+        "DisabledVoteHooksResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "ProposalHookFailuresResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookFailuresResponse),
+        &out_dir,
+        "VoteHookFailuresResponse",
+    );
     export_schema_with_title(&schema_for!(VoteResponse), &out_dir, "GetVoteResponse");
+    export_schema_with_title(
+        &schema_for!(PendingProposalResponse),
+        &out_dir,
+        "PendingProposalResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(PendingProposalListResponse),
+        &out_dir,
+        "ListPendingProposalsResponse",
+    );
 }
@@ -2,35 +2,53 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
+    Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_core_interface::voting::IsActiveResponse;
 use cw_storage_plus::{Bound, Item, Map};
 use cw_utils::{Duration, Expiration};
-use indexable_hooks::Hooks;
-use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use indexable_hooks::{HookOptions, Hooks};
+use proposal_hooks::{
+    new_proposal_hooks, pending_proposal_rejected_hooks, proposal_amended_hooks,
+    proposal_status_changed_hooks, ProposalHookFilter,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use vote_hooks::new_vote_hooks;
+use vote_hooks::{new_vote_hooks, VoteHookMsgV2};
 
-use voting::deposit::{get_deposit_msg, get_return_deposit_msg, CheckedDepositInfo, DepositInfo};
-use voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use voting::curve::{CurvedPower, VotingPowerCurve};
+use voting::deposit::{
+    check_native_deposit_paid, get_deposit_msg, get_refund_deposit_msgs, get_return_deposit_msgs,
+    CheckedDepositInfo, DepositInfo,
+};
+use voting::proposal::{validate_rationale, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
 use voting::reply::{mask_proposal_execution_proposal_id, TaggedReplyId};
 use voting::status::Status;
 use voting::threshold::Threshold;
+use voting::timelock::validate_timelock;
+use voting::veto::{CheckedVetoer, Vetoer};
 use voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
 
-use crate::msg::MigrateMsg;
+use crate::msg::{MigrateMsg, PreProposeInfo};
 use crate::proposal::SingleChoiceProposal;
-use crate::state::Config;
+use crate::state::{Config, PendingProposal, PreProposeConfig, ProposalVersion, PROPOSAL_VERSIONS};
 use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::advance_proposal_id,
+    proposal::{advance_pending_proposal_id, advance_proposal_id},
     query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    query::{
+        DelegationResponse, DelegatorListResponse, ProposalResponse, VoteInfo, VoteListResponse,
+        VoteResponse,
+    },
+    query::{PendingProposalListResponse, PendingProposalResponse},
+    query::{ProposalVersionListResponse, ProposalVersionResponse},
+    state::PENDING_PROPOSALS,
+    state::{
+        Ballot, Delegation, BALLOTS, CONFIG, DELEGATIONS, DELEGATORS, MAX_DELEGATORS,
+        OVERRIDDEN_POWER, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+    },
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw-govmod-single";
@@ -46,6 +64,8 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.threshold.validate()?;
+    msg.voting_power_curve.validate()?;
+    msg.voting_power_curve.validate_threshold(&msg.threshold)?;
 
     let dao = info.sender;
     let deposit_info = msg
@@ -55,6 +75,9 @@ pub fn instantiate(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
+    let (timelock, vetoer) =
+        validate_timelock_and_vetoer(deps.as_ref(), msg.timelock, msg.vetoer, max_voting_period)?;
+    let pre_propose = validate_pre_propose(deps.as_ref(), msg.pre_propose)?;
 
     let config = Config {
         threshold: msg.threshold,
@@ -65,6 +88,10 @@ pub fn instantiate(
         deposit_info,
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        voting_power_curve: msg.voting_power_curve,
+        timelock,
+        vetoer,
+        pre_propose,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -77,6 +104,37 @@ pub fn instantiate(
         .add_attribute("dao", dao))
 }
 
+/// Validates that the timelock has the same units as the max voting
+/// period and that a vetoer is only set if there is a timelock during
+/// which it may veto proposals.
+fn validate_timelock_and_vetoer(
+    deps: Deps,
+    timelock: Option<Duration>,
+    vetoer: Option<Vetoer>,
+    max_voting_period: Duration,
+) -> Result<(Option<Duration>, Option<CheckedVetoer>), ContractError> {
+    let timelock = validate_timelock(timelock, max_voting_period)?;
+    let vetoer = vetoer.map(|vetoer| vetoer.into_checked(deps)).transpose()?;
+    if vetoer.is_some() && timelock.is_none() {
+        return Err(ContractError::VetoerWithoutTimelock {});
+    }
+    Ok((timelock, vetoer))
+}
+
+fn validate_pre_propose(
+    deps: Deps,
+    pre_propose: Option<PreProposeInfo>,
+) -> StdResult<Option<PreProposeConfig>> {
+    pre_propose
+        .map(|info| {
+            Ok(PreProposeConfig {
+                approver: deps.api.addr_validate(&info.approver)?,
+                require_approval_for_members: info.require_approval_for_members,
+            })
+        })
+        .transpose()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -89,10 +147,30 @@ pub fn execute(
             title,
             description,
             msgs,
-        } => execute_propose(deps, env, info.sender, title, description, msgs),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        } => execute_propose(deps, env, info, title, description, msgs),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::Delegate { delegate } => execute_delegate(deps, env, info, delegate),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Withdraw { proposal_id } => execute_withdraw(deps, env, info, proposal_id),
+        ExecuteMsg::Amend {
+            proposal_id,
+            title,
+            description,
+            msgs,
+        } => execute_amend(deps, env, info, proposal_id, title, description, msgs),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::ApprovePendingProposal { pending_id } => {
+            execute_approve_pending_proposal(deps, env, info, pending_id)
+        }
+        ExecuteMsg::RejectPendingProposal { pending_id } => {
+            execute_reject_pending_proposal(deps, env, info, pending_id)
+        }
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -102,6 +180,10 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
+            pre_propose,
         } => execute_update_config(
             deps,
             info,
@@ -113,14 +195,50 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
+            pre_propose,
+        ),
+        ExecuteMsg::AddProposalHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+            filter,
+        } => execute_add_proposal_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+            filter,
         ),
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
-        }
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook {
+            address,
+            version,
+            failure_policy,
+            gas_limit,
+        } => execute_add_vote_hook(
+            deps,
+            env,
+            info,
+            address,
+            HookOptions {
+                version: version.unwrap_or_default(),
+                failure_policy: failure_policy.unwrap_or_default(),
+                gas_limit,
+                ..Default::default()
+            },
+        ),
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
@@ -128,14 +246,16 @@ pub fn execute(
 }
 
 pub fn execute_propose(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    sender: Addr,
+    info: MessageInfo,
     title: String,
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    check_native_deposit_paid(&config.deposit_info, &info)?;
+    let sender = info.sender;
 
     let voting_module: Addr = deps
         .querier
@@ -155,49 +275,123 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    // Check that the sender is a member of the governance contract.
+    // Check that the sender is a member of the governance
+    // contract. Non-members may only submit proposals to the
+    // pre-propose queue.
     let sender_power = get_voting_power(
         deps.as_ref(),
         sender.clone(),
         config.dao.clone(),
         Some(env.block.height),
     )?;
-    if sender_power.is_zero() {
-        return Err(ContractError::Unauthorized {});
+    let requires_approval = match &config.pre_propose {
+        Some(pre_propose) => pre_propose.require_approval_for_members || sender_power.is_zero(),
+        None if sender_power.is_zero() => return Err(ContractError::Unauthorized {}),
+        None => false,
+    };
+
+    let deposit_msg = get_deposit_msg(&config.deposit_info, &env.contract.address, &sender)?;
+    let submission = PendingProposal {
+        title,
+        description,
+        proposer: sender.clone(),
+        msgs,
+        deposit_info: config.deposit_info.clone(),
+        created: env.block.time,
+    };
+
+    if requires_approval {
+        check_proposal_size(&submission)?;
+        let pending_id = advance_pending_proposal_id(deps.storage)?;
+        PENDING_PROPOSALS.save(deps.storage, pending_id, &submission)?;
+        return Ok(Response::default()
+            .add_messages(deposit_msg)
+            .add_attribute("action", "propose_pending")
+            .add_attribute("sender", sender)
+            .add_attribute("pending_id", pending_id.to_string()));
     }
 
+    let (id, proposal) = create_proposal(deps.branch(), &env, config, submission)?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        &env.contract.address,
+        &proposal.msgs,
+    )?;
+    Ok(Response::default()
+        .add_messages(deposit_msg)
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+/// Creates a proposal from SUBMISSION, saves it, and returns its
+/// ID. The proposal's voting period starts at the current block.
+fn create_proposal(
+    deps: DepsMut,
+    env: &Env,
+    config: Config,
+    submission: PendingProposal,
+) -> Result<(u64, SingleChoiceProposal), ContractError> {
+    let PendingProposal {
+        title,
+        description,
+        proposer,
+        msgs,
+        deposit_info,
+        created: _,
+    } = submission;
+
     let expiration = config.max_voting_period.after(&env.block);
 
     let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
+    let curved_power = if config.voting_power_curve.is_linear() {
+        None
+    } else {
+        Some(CurvedPower::new(config.voting_power_curve, total_power))
+    };
 
     let proposal = {
         // Limit mutability to this block.
         let mut proposal = SingleChoiceProposal {
             title,
             description,
-            proposer: sender.clone(),
+            proposer,
             start_height: env.block.height,
             min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
             expiration,
+            timelock: config.timelock,
+            timelock_expiration: None,
             threshold: config.threshold,
-            total_power,
+            total_power: config.voting_power_curve.apply(total_power),
+            curved_power,
             msgs,
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
-            deposit_info: config.deposit_info.clone(),
+            revision: 0,
+            deposit_info,
             created: env.block.time,
             last_updated: env.block.time,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
-        proposal.update_status(&env.block);
+        proposal.update_status(&env.block)?;
         proposal
     };
+    check_proposal_size(&proposal)?;
+
     let id = advance_proposal_id(deps.storage)?;
+    PROPOSALS.save(deps.storage, id, &proposal)?;
 
-    // Limit the size of proposals.
-    //
+    Ok((id, proposal))
+}
+
+/// Limits the size of proposals.
+fn check_proposal_size<T: Serialize>(proposal: &T) -> Result<(), ContractError> {
     // The Juno mainnet has a larger limit for data that can be
     // uploaded as part of an execute message than it does for data
     // that can be queried as part of a query. This means that without
@@ -210,27 +404,105 @@ pub fn execute_propose(
     //
     // `to_vec` is the method used by cosmwasm to convert a struct
     // into it's byte representation in storage.
-    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    //
+    // Pending proposals are checked against the same limit when they
+    // are submitted. As a proposal is slightly larger than the
+    // pending proposal it was created from, approving a pending
+    // proposal very near the limit may fail, in which case it ought
+    // to be rejected.
+    let proposal_size = cosmwasm_std::to_vec(proposal)?.len() as u64;
     if proposal_size > MAX_PROPOSAL_SIZE {
         return Err(ContractError::ProposalTooLarge {
             size: proposal_size,
             max: MAX_PROPOSAL_SIZE,
         });
     }
+    Ok(())
+}
 
-    PROPOSALS.save(deps.storage, id, &proposal)?;
+/// Returns an error if SENDER may not approve or reject pending
+/// proposals. The DAO may always do so, so that pending proposals
+/// may be handled after the pre-propose queue is disabled.
+fn check_approver(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    let is_approver = config
+        .pre_propose
+        .as_ref()
+        .is_some_and(|pre_propose| pre_propose.approver == *sender);
+    if !is_approver && *sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
 
-    let deposit_msg = get_deposit_msg(&config.deposit_info, &env.contract.address, &sender)?;
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id)?;
+pub fn execute_approve_pending_proposal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pending_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_approver(&config, &info.sender)?;
+
+    let pending = PENDING_PROPOSALS
+        .may_load(deps.storage, pending_id)?
+        .ok_or(ContractError::NoSuchPendingProposal { id: pending_id })?;
+    PENDING_PROPOSALS.remove(deps.storage, pending_id);
+
+    let (id, proposal) = create_proposal(deps.branch(), &env, config, pending)?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        &env.contract.address,
+        &proposal.msgs,
+    )?;
     Ok(Response::default()
-        .add_messages(deposit_msg)
         .add_submessages(hooks)
-        .add_attribute("action", "propose")
-        .add_attribute("sender", sender)
+        .add_attribute("action", "approve_pending_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("pending_id", pending_id.to_string())
         .add_attribute("proposal_id", id.to_string())
         .add_attribute("status", proposal.status.to_string()))
 }
 
+pub fn execute_reject_pending_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pending_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_approver(&config, &info.sender)?;
+
+    let pending = PENDING_PROPOSALS
+        .may_load(deps.storage, pending_id)?
+        .ok_or(ContractError::NoSuchPendingProposal { id: pending_id })?;
+    PENDING_PROPOSALS.remove(deps.storage, pending_id);
+
+    // Rejected pending proposals are treated as failed proposals for
+    // the purposes of deposit refunds.
+    let refund_message = match &pending.deposit_info {
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &pending.proposer, &config.dao, false)?
+        }
+        None => vec![],
+    };
+
+    let hooks = pending_proposal_rejected_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        pending_id,
+        &env.contract.address,
+        &pending.msgs,
+    )?;
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_messages(refund_message)
+        .add_attribute("action", "reject_pending_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("pending_id", pending_id.to_string()))
+}
+
 pub fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -253,10 +525,15 @@ pub fn execute_execute(
     // even if it is expired so long as it passed during its voting
     // period.
     let old_status = prop.status;
-    prop.update_status(&env.block);
+    prop.update_status(&env.block)?;
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
+    if let Some(expiration) = prop.timelock_expiration {
+        if !expiration.is_expired(&env.block) {
+            return Err(ContractError::Timelocked { expiration });
+        }
+    }
 
     prop.status = Status::Executed;
     // Update proposal's last updated timestamp.
@@ -265,7 +542,9 @@ pub fn execute_execute(
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
     let refund_message = match prop.deposit_info {
-        Some(deposit_info) => get_return_deposit_msg(&deposit_info, &prop.proposer)?,
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(&deposit_info, &prop.proposer, &config.dao, true)?
+        }
         None => vec![],
     };
 
@@ -273,7 +552,9 @@ pub fn execute_execute(
         if !prop.msgs.is_empty() {
             let execute_message = WasmMsg::Execute {
                 contract_addr: config.dao.to_string(),
-                msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook { msgs: prop.msgs })?,
+                msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
+                    msgs: prop.msgs.clone(),
+                })?,
                 funds: vec![],
             };
             match config.close_proposal_on_execution_failure {
@@ -293,8 +574,10 @@ pub fn execute_execute(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
-        old_status.to_string(),
-        prop.status.to_string(),
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
     )?;
     Ok(response
         .add_messages(refund_message)
@@ -306,12 +589,14 @@ pub fn execute_execute(
 }
 
 pub fn execute_vote(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
     vote: Vote,
+    rationale: Option<String>,
 ) -> Result<Response, ContractError> {
+    let rationale = validate_rationale(rationale)?;
     let config = CONFIG.load(deps.storage)?;
     let mut prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
@@ -323,48 +608,44 @@ pub fn execute_vote(
     let vote_power = get_voting_power(
         deps.as_ref(),
         info.sender.clone(),
-        config.dao,
+        config.dao.clone(),
         Some(prop.start_height),
     )?;
-    if vote_power.is_zero() {
+    let delegated_power =
+        delegated_power(deps.as_ref(), &config, &prop, proposal_id, &info.sender)?;
+    if vote_power.is_zero() && delegated_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(
-        deps.storage,
-        (proposal_id, info.sender.clone()),
-        |bal| match bal {
-            Some(current_ballot) => {
-                if prop.allow_revoting {
-                    if current_ballot.vote == vote {
-                        // Don't allow casting the same vote more than
-                        // once. This seems liable to be confusing
-                        // behavior.
-                        Err(ContractError::AlreadyCast {})
-                    } else {
-                        // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power);
-                        Ok(Ballot {
-                            power: vote_power,
-                            vote,
-                        })
-                    }
-                } else {
-                    Err(ContractError::AlreadyVoted {})
-                }
-            }
-            None => Ok(Ballot {
-                power: vote_power,
-                vote,
-            }),
-        },
-    )?;
-
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power);
-    prop.update_status(&env.block);
+    // Voting directly overrides the vote of the voter's delegate.
+    if load_ballot(deps.storage, &prop, proposal_id, &info.sender)?.is_none() {
+        if let Some(Delegation { delegate }) =
+            DELEGATIONS.may_load_at_height(deps.storage, info.sender.clone(), prop.start_height)?
+        {
+            override_delegation(
+                deps.branch(),
+                &config,
+                &mut prop,
+                proposal_id,
+                delegate,
+                vote_power,
+            )?;
+        }
+    }
+
+    cast_ballot(
+        deps.storage,
+        &mut prop,
+        proposal_id,
+        info.sender.clone(),
+        vote_power,
+        delegated_power,
+        vote,
+        rationale.clone(),
+    )?;
+    prop.update_status_after_vote(&env.block)?;
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
@@ -373,15 +654,22 @@ pub fn execute_vote(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
-        old_status.to_string(),
-        new_status.to_string(),
+        old_status,
+        new_status,
+        &env.contract.address,
+        &prop.msgs,
     )?;
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
         deps.storage,
-        proposal_id,
-        info.sender.to_string(),
-        vote.to_string(),
+        VoteHookMsgV2::NewVote {
+            proposal_id,
+            voter: info.sender.clone(),
+            vote,
+            power: vote_power + delegated_power,
+            rationale,
+            module: env.contract.address.clone(),
+        },
     )?;
 
     Ok(Response::default()
@@ -391,9 +679,244 @@ pub fn execute_vote(
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
+        .add_attribute("delegated_power", delegated_power.to_string())
         .add_attribute("status", prop.status.to_string()))
 }
 
+/// Records VOTER's ballot on a proposal and adds it to the proposal's
+/// votes. POWER is VOTER's own voting power and DELEGATED_POWER the
+/// power delegated to them. A ballot may only be replaced if revoting
+/// is allowed.
+#[allow(clippy::too_many_arguments)]
+fn cast_ballot(
+    storage: &mut dyn Storage,
+    prop: &mut SingleChoiceProposal,
+    proposal_id: u64,
+    voter: Addr,
+    power: Uint128,
+    delegated_power: Uint128,
+    vote: Vote,
+    rationale: Option<String>,
+) -> Result<(), ContractError> {
+    let power = power + delegated_power;
+    let weight = prop.vote_weight(power);
+    let current = load_ballot(storage, prop, proposal_id, &voter)?;
+    if let Some(current) = &current {
+        if !prop.allow_revoting {
+            return Err(ContractError::AlreadyVoted {});
+        }
+        if current.vote == vote {
+            // Don't allow casting the same vote more than
+            // once. This seems liable to be confusing behavior.
+            return Err(ContractError::AlreadyCast {});
+        }
+        // Remove the old vote if this is a re-vote.
+        prop.votes.remove_vote(current.vote, current.power);
+    }
+    BALLOTS.save(
+        storage,
+        (proposal_id, voter),
+        &Ballot {
+            power: weight,
+            vote,
+            delegated_power,
+            rationale,
+            revision: prop.revision,
+        },
+    )?;
+
+    prop.votes.add_vote(vote, weight);
+    if current.is_none() {
+        prop.add_voter(power);
+    }
+    Ok(())
+}
+
+/// Loads VOTER's ballot on PROP, which has ID PROPOSAL_ID. Ballots
+/// cast before PROP was last amended are ignored.
+fn load_ballot(
+    storage: &dyn Storage,
+    prop: &SingleChoiceProposal,
+    proposal_id: u64,
+    voter: &Addr,
+) -> StdResult<Option<Ballot>> {
+    Ok(BALLOTS
+        .may_load(storage, (proposal_id, voter.clone()))?
+        .filter(|ballot| ballot.revision == prop.revision))
+}
+
+/// Returns the voting power that the delegators of DELEGATE at the
+/// start height of PROP had at that height, less the power of those
+/// that have voted on it themselves.
+fn delegated_power(
+    deps: Deps,
+    config: &Config,
+    prop: &SingleChoiceProposal,
+    proposal_id: u64,
+    delegate: &Addr,
+) -> StdResult<Uint128> {
+    let delegators = DELEGATORS
+        .may_load_at_height(deps.storage, delegate.clone(), prop.start_height)?
+        .unwrap_or_default();
+    let mut delegated = Uint128::zero();
+    for delegator in delegators {
+        let power = get_voting_power(deps, delegator, config.dao.clone(), Some(prop.start_height))?;
+        delegated = delegated.checked_add(power)?;
+    }
+    let overridden = OVERRIDDEN_POWER
+        .may_load(deps.storage, (proposal_id, prop.revision, delegate.clone()))?
+        .unwrap_or_default();
+    Ok(delegated.checked_sub(overridden)?)
+}
+
+/// Removes POWER, the voting power of a delegator voting on a
+/// proposal themselves, from the vote of DELEGATE, the delegate they
+/// had delegated to at the proposal's start height, and from any vote
+/// that delegate casts later.
+fn override_delegation(
+    deps: DepsMut,
+    config: &Config,
+    prop: &mut SingleChoiceProposal,
+    proposal_id: u64,
+    delegate: Addr,
+    power: Uint128,
+) -> Result<(), ContractError> {
+    OVERRIDDEN_POWER.update(
+        deps.storage,
+        (proposal_id, prop.revision, delegate.clone()),
+        |overridden| -> StdResult<_> { Ok(overridden.unwrap_or_default().checked_add(power)?) },
+    )?;
+
+    if let Some(mut ballot) = load_ballot(deps.storage, prop, proposal_id, &delegate)? {
+        let own_power = get_voting_power(
+            deps.as_ref(),
+            delegate.clone(),
+            config.dao.clone(),
+            Some(prop.start_height),
+        )?;
+        ballot.delegated_power = ballot
+            .delegated_power
+            .checked_sub(power)
+            .map_err(StdError::from)?;
+        prop.votes.remove_vote(ballot.vote, ballot.power);
+        ballot.power = prop.vote_weight(own_power + ballot.delegated_power);
+        prop.votes.add_vote(ballot.vote, ballot.power);
+        prop.remove_voter_power(power)?;
+        BALLOTS.save(deps.storage, (proposal_id, delegate), &ballot)?;
+    }
+    Ok(())
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+    let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao, None)?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    let height = env.block.height;
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, info.sender.clone())? {
+        remove_delegator(deps.storage, previous.delegate, &info.sender, height)?;
+    }
+    DELEGATIONS.save(
+        deps.storage,
+        info.sender.clone(),
+        &Delegation {
+            delegate: delegate.clone(),
+        },
+        height,
+    )?;
+    let mut delegators = DELEGATORS
+        .may_load(deps.storage, delegate.clone())?
+        .unwrap_or_default();
+    if delegators.len() >= MAX_DELEGATORS {
+        return Err(ContractError::TooManyDelegators {
+            max: MAX_DELEGATORS,
+        });
+    }
+    if let Err(index) = delegators.binary_search(&info.sender) {
+        delegators.insert(index, info.sender.clone());
+    }
+    DELEGATORS.save(deps.storage, delegate.clone(), &delegators, height)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("power", power.to_string()))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegation = DELEGATIONS
+        .may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::NotDelegating {})?;
+    DELEGATIONS.remove(deps.storage, info.sender.clone(), env.block.height)?;
+    remove_delegator(
+        deps.storage,
+        delegation.delegate,
+        &info.sender,
+        env.block.height,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender))
+}
+
+/// Removes DELEGATOR from the delegators of DELEGATE as of HEIGHT.
+fn remove_delegator(
+    storage: &mut dyn Storage,
+    delegate: Addr,
+    delegator: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    let mut delegators = DELEGATORS
+        .may_load(storage, delegate.clone())?
+        .unwrap_or_default();
+    delegators.retain(|current| current != delegator);
+    if delegators.is_empty() {
+        DELEGATORS.remove(storage, delegate, height)
+    } else {
+        DELEGATORS.save(storage, delegate, &delegators, height)
+    }
+}
+
+/// Returns DELEGATOR's delegation at HEIGHT, or currently if HEIGHT
+/// is None.
+fn delegation(
+    storage: &dyn Storage,
+    delegator: Addr,
+    height: Option<u64>,
+) -> StdResult<Option<Delegation>> {
+    match height {
+        Some(height) => DELEGATIONS.may_load_at_height(storage, delegator, height),
+        None => DELEGATIONS.may_load(storage, delegator),
+    }
+}
+
+/// Returns the addresses that had delegated their vote to DELEGATE at
+/// HEIGHT, or currently if HEIGHT is None, in ascending order.
+fn delegators(storage: &dyn Storage, delegate: Addr, height: Option<u64>) -> StdResult<Vec<Addr>> {
+    let delegators = match height {
+        Some(height) => DELEGATORS.may_load_at_height(storage, delegate, height)?,
+        None => DELEGATORS.may_load(storage, delegate)?,
+    };
+    Ok(delegators.unwrap_or_default())
+}
+
 pub fn execute_close(
     deps: DepsMut,
     env: Env,
@@ -405,7 +928,7 @@ pub fn execute_close(
 
     // Update status to ensure that proposals which were open and have
     // expired are moved to "rejected."
-    prop.update_status(&env.block);
+    prop.update_status(&env.block)?;
     if prop.status != Status::Rejected {
         return Err(ContractError::WrongCloseStatus {});
     }
@@ -414,14 +937,7 @@ pub fn execute_close(
 
     let refund_message = match &prop.deposit_info {
         Some(deposit_info) => {
-            let receiver = if deposit_info.refund_failed_proposals {
-                &prop.proposer
-            } else {
-                // If we aren't refunding failed proposals then return
-                // the depost to the DAO treasury on close.
-                &config.dao
-            };
-            get_return_deposit_msg(deposit_info, receiver)?
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, false)?
         }
         None => vec![],
     };
@@ -435,8 +951,10 @@ pub fn execute_close(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
-        old_status.to_string(),
-        prop.status.to_string(),
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
     )?;
 
     Ok(Response::default()
@@ -447,6 +965,200 @@ pub fn execute_close(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    if !prop.votes.total().is_zero() {
+        return Err(ContractError::WithdrawAfterVote {});
+    }
+
+    let old_status = prop.status;
+
+    let refund_message = match &prop.deposit_info {
+        Some(deposit_info) => get_return_deposit_msgs(deposit_info, &prop.proposer)?,
+        None => vec![],
+    };
+
+    prop.status = Status::Withdrawn;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_messages(refund_message)
+        .add_attribute("action", "withdraw")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_amend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+    // Amending an approved proposal would let its proposer replace
+    // its messages without approval.
+    if config.pre_propose.is_some() {
+        return Err(ContractError::AmendWithApprover {});
+    }
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let version = PROPOSAL_VERSIONS
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default()
+        + 1;
+    PROPOSAL_VERSIONS.save(
+        deps.storage,
+        (proposal_id, version),
+        &ProposalVersion {
+            title: std::mem::replace(&mut prop.title, title),
+            description: std::mem::replace(&mut prop.description, description),
+            msgs: std::mem::replace(&mut prop.msgs, msgs),
+            start_height: prop.start_height,
+            votes: std::mem::replace(&mut prop.votes, Votes::zero()),
+            amended: env.block.time,
+        },
+    )?;
+
+    // Ballots cast on the previous version no longer count.
+    prop.revision += 1;
+
+    // Restart voting as if the proposal had just been created. The
+    // proposal's threshold, revoting setting, and voting power curve
+    // are kept.
+    let expiration = config.max_voting_period.after(&env.block);
+    let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
+    prop.start_height = env.block.height;
+    prop.min_voting_period = config.min_voting_period.map(|min| min.after(&env.block));
+    prop.expiration = expiration;
+    prop.timelock = config.timelock;
+    prop.timelock_expiration = None;
+    prop.curved_power = prop
+        .curved_power
+        .map(|curved_power| CurvedPower::new(curved_power.curve, total_power));
+    prop.total_power = match &prop.curved_power {
+        Some(curved_power) => curved_power.curve.apply(total_power),
+        None => total_power,
+    };
+    prop.last_updated = env.block.time;
+    prop.update_status(&env.block)?;
+    check_proposal_size(&prop)?;
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_amended_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        version + 1,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "amend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("version", (version + 1).to_string()))
+}
+
+pub fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_vetoer = match &config.vetoer {
+        Some(vetoer) => vetoer.is_vetoer(deps.as_ref(), &config.dao, &info.sender)?,
+        None => false,
+    };
+    if !is_vetoer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    prop.update_status(&env.block)?;
+    if !prop.is_timelocked(&env.block) {
+        return Err(ContractError::NotTimelocked {});
+    }
+
+    let old_status = prop.status;
+
+    // Vetoed proposals are treated as failed proposals for the
+    // purposes of deposit refunds.
+    let refund_message = match &prop.deposit_info {
+        Some(deposit_info) => {
+            get_refund_deposit_msgs(deposit_info, &prop.proposer, &config.dao, false)?
+        }
+        None => vec![],
+    };
+
+    prop.status = Status::Vetoed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status,
+        prop.status,
+        &env.contract.address,
+        &prop.msgs,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_messages(refund_message)
+        .add_attribute("action", "veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
@@ -459,6 +1171,10 @@ pub fn execute_update_config(
     dao: String,
     deposit_info: Option<DepositInfo>,
     close_proposal_on_execution_failure: bool,
+    voting_power_curve: VotingPowerCurve,
+    timelock: Option<Duration>,
+    vetoer: Option<Vetoer>,
+    pre_propose: Option<PreProposeInfo>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -468,6 +1184,8 @@ pub fn execute_update_config(
     }
 
     threshold.validate()?;
+    voting_power_curve.validate()?;
+    voting_power_curve.validate_threshold(&threshold)?;
     let dao = deps.api.addr_validate(&dao)?;
     let deposit_info = deposit_info
         .map(|info| info.into_checked(deps.as_ref(), dao.clone()))
@@ -475,6 +1193,9 @@ pub fn execute_update_config(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
+    let (timelock, vetoer) =
+        validate_timelock_and_vetoer(deps.as_ref(), timelock, vetoer, max_voting_period)?;
+    let pre_propose = validate_pre_propose(deps.as_ref(), pre_propose)?;
 
     CONFIG.save(
         deps.storage,
@@ -487,6 +1208,10 @@ pub fn execute_update_config(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            voting_power_curve,
+            timelock,
+            vetoer,
+            pre_propose,
         },
     )?;
 
@@ -498,9 +1223,10 @@ pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
     validated_address: Addr,
+    options: HookOptions,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook_with_options(storage, validated_address, options)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -521,6 +1247,8 @@ pub fn execute_add_proposal_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    options: HookOptions,
+    filter: Option<ProposalHookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -530,7 +1258,11 @@ pub fn execute_add_proposal_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    let options = match filter {
+        Some(filter) => filter.apply(deps.api, options)?,
+        None => options,
+    };
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address, options)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -563,6 +1295,7 @@ pub fn execute_add_vote_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    options: HookOptions,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -572,7 +1305,7 @@ pub fn execute_add_vote_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(VOTE_HOOKS, deps.storage, validated_address, options)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -615,13 +1348,55 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::Delegation { delegator, height } => query_delegation(deps, delegator, height),
+        QueryMsg::ListDelegators {
+            delegate,
+            height,
+            start_after,
+            limit,
+        } => query_list_delegators(deps, delegate, height, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
-        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
-        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalHooks { start_after, limit } => to_binary(
+            &PROPOSAL_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::VoteHooks { start_after, limit } => to_binary(
+            &VOTE_HOOKS.query_hooks(
+                deps,
+                start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+                limit,
+            )?,
+        ),
+        QueryMsg::DisabledProposalHooks {} => {
+            to_binary(&PROPOSAL_HOOKS.query_disabled_hooks(deps)?)
+        }
+        QueryMsg::DisabledVoteHooks {} => to_binary(&VOTE_HOOKS.query_disabled_hooks(deps)?),
+        QueryMsg::ProposalHookFailures { address } => to_binary(
+            &PROPOSAL_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookFailures { address } => {
+            to_binary(&VOTE_HOOKS.query_hook_failures(deps, &deps.api.addr_validate(&address)?)?)
+        }
+        QueryMsg::PendingProposal { pending_id } => query_pending_proposal(deps, pending_id),
+        QueryMsg::ListPendingProposals { start_after, limit } => {
+            query_list_pending_proposals(deps, start_after, limit)
+        }
+        QueryMsg::ProposalVersions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_proposal_versions(deps, proposal_id, start_after, limit),
     }
 }
 
@@ -632,7 +1407,7 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     let proposal = PROPOSALS.load(deps.storage, id)?;
-    to_binary(&proposal.into_response(&env.block, id))
+    to_binary(&proposal.into_response(&env.block, id)?)
 }
 
 pub fn query_list_proposals(
@@ -649,7 +1424,7 @@ pub fn query_list_proposals(
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
         .into_iter()
         .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .collect::<StdResult<_>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
 }
@@ -668,11 +1443,58 @@ pub fn query_reverse_proposals(
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
         .into_iter()
         .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .collect::<StdResult<_>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_pending_proposal(deps: Deps, pending_id: u64) -> StdResult<Binary> {
+    let proposal = PENDING_PROPOSALS.load(deps.storage, pending_id)?;
+    to_binary(&PendingProposalResponse {
+        pending_id,
+        proposal,
+    })
+}
+
+pub fn query_list_pending_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let pending_proposals = PENDING_PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            item.map(|(pending_id, proposal)| PendingProposalResponse {
+                pending_id,
+                proposal,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&PendingProposalListResponse { pending_proposals })
+}
+
+pub fn query_proposal_versions(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let versions = PROPOSAL_VERSIONS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(version, proposal)| ProposalVersionResponse { version, proposal }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalVersionListResponse { versions })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_binary(&proposal_count)
@@ -680,11 +1502,16 @@ pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
     let voter = deps.api.addr_validate(&voter)?;
-    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let ballot = match PROPOSALS.may_load(deps.storage, proposal_id)? {
+        Some(prop) => load_ballot(deps.storage, &prop, proposal_id, &voter)?,
+        None => None,
+    };
     let vote = ballot.map(|ballot| VoteInfo {
         voter,
         vote: ballot.vote,
         power: ballot.power,
+        delegated_power: ballot.delegated_power,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -700,10 +1527,18 @@ pub fn query_list_votes(
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
     let min = start_after.map(Bound::<Addr>::exclusive);
+    // Ballots cast before the proposal was last amended are skipped.
+    let revision = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .map_or(0, |prop| prop.revision);
 
     let votes = BALLOTS
         .prefix(proposal_id)
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, ballot)| ballot.revision == revision)
+        })
         .take(limit as usize)
         .map(|item| {
             let (voter, ballot) = item?;
@@ -711,6 +1546,8 @@ pub fn query_list_votes(
                 voter,
                 vote: ballot.vote,
                 power: ballot.power,
+                delegated_power: ballot.delegated_power,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -718,6 +1555,43 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_delegation(deps: Deps, delegator: String, height: Option<u64>) -> StdResult<Binary> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let delegation = delegation(deps.storage, delegator.clone(), height)?;
+    to_binary(&match delegation {
+        Some(Delegation { delegate }) => DelegationResponse {
+            delegate: Some(delegate),
+            power: get_voting_power(deps, delegator, CONFIG.load(deps.storage)?.dao, height)?,
+        },
+        None => DelegationResponse {
+            delegate: None,
+            power: Uint128::zero(),
+        },
+    })
+}
+
+pub fn query_list_delegators(
+    deps: Deps,
+    delegate: String,
+    height: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let delegators = delegators(deps.storage, delegate, height)?
+        .into_iter()
+        .filter(|delegator| start_after.as_ref().is_none_or(|start| delegator > start))
+        .take(limit as usize)
+        .collect();
+
+    to_binary(&DelegatorListResponse { delegators })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
@@ -781,6 +1655,10 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                     deposit_info: current_config.deposit_info,
                     // Loads of text, but we're only updating this field.
                     close_proposal_on_execution_failure,
+                    voting_power_curve: VotingPowerCurve::Linear {},
+                    timelock: None,
+                    vetoer: None,
+                    pre_propose: None,
                 },
             )?;
 
@@ -805,12 +1683,16 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                         start_height: prop.start_height,
                         min_voting_period: prop.min_voting_period,
                         expiration: prop.expiration,
+                        timelock: None,
+                        timelock_expiration: None,
                         threshold: prop.threshold,
                         total_power: prop.total_power,
+                        curved_power: None,
                         msgs: prop.msgs,
                         status: prop.status,
                         votes: prop.votes,
                         allow_revoting: prop.allow_revoting,
+                        revision: 0,
                         deposit_info: prop.deposit_info,
                         // CosmWasm does not expose a way to query the timestamp
                         // of a block given block height. As such, we assign migrated
@@ -825,10 +1707,28 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                     Ok(())
                 })?;
 
+            // Hooks were stored as a single list before they had
+            // stable indices.
+            PROPOSAL_HOOKS.migrate_legacy_hooks(deps.storage)?;
+            VOTE_HOOKS.migrate_legacy_hooks(deps.storage)?;
+
             Ok(Response::default())
         }
 
-        MigrateMsg::FromCompatible {} => Ok(Response::default()),
+        MigrateMsg::FromCompatible {} => {
+            // Deposit info stored before refund policies were added
+            // has a `refund_failed_proposals` bool which is read as
+            // the equivalent policy. Resave the config so that it is
+            // stored with the policy. Proposals are read the same way
+            // and are left as is.
+            let config = CONFIG.load(deps.storage)?;
+            CONFIG.save(deps.storage, &config)?;
+            // Hooks were stored as a single list before they had
+            // stable indices.
+            PROPOSAL_HOOKS.migrate_legacy_hooks(deps.storage)?;
+            VOTE_HOOKS.migrate_legacy_hooks(deps.storage)?;
+            Ok(Response::default())
+        }
     }
 }
 
@@ -849,12 +1749,16 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
             Ok(Response::new().add_attribute("proposal execution failed", proposal_id.to_string()))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
-            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed proposal hook", format!("{addr}:{idx}")))
+            let (addr, outcome) =
+                PROPOSAL_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} proposal hook"), format!("{addr}:{idx}")))
         }
         TaggedReplyId::FailedVoteHook(idx) => {
-            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
+            let (addr, outcome) =
+                VOTE_HOOKS.handle_reply(deps.storage, &env.block, idx, msg.result)?;
+            Ok(Response::new()
+                .add_attribute(format!("{outcome} vote hook"), format!("{addr}:{idx}")))
         }
     }
 }
@@ -1,6 +1,5 @@
-use std::u64;
-
 use cosmwasm_std::StdError;
+use cw_utils::Expiration;
 use indexable_hooks::HookError;
 use thiserror::Error;
 use voting::reply::error::TagError;
@@ -67,6 +66,33 @@ pub enum ContractError {
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
 
+    #[error("Proposal is timelocked until ({expiration})")]
+    Timelocked { expiration: Expiration },
+
+    #[error("Only proposals in their timelock may be vetoed")]
+    NotTimelocked {},
+
+    #[error("A vetoer may only be set if proposals are timelocked")]
+    VetoerWithoutTimelock {},
+
+    #[error("Proposals may not be withdrawn once they have been voted on")]
+    WithdrawAfterVote {},
+
+    #[error("Proposals may not be amended while proposals require approval")]
+    AmendWithApprover {},
+
+    #[error("No such pending proposal ({id})")]
+    NoSuchPendingProposal { id: u64 },
+
+    #[error("An address may not delegate its vote to itself")]
+    SelfDelegation {},
+
+    #[error("Sender has not delegated their vote")]
+    NotDelegating {},
+
+    #[error("Delegate already has the maximum number of delegators ({max})")]
+    TooManyDelegators { max: usize },
+
     #[error("{0}")]
     Tag(#[from] TagError),
 }
@@ -1,16 +1,23 @@
 use cosmwasm_std::{
+    from_binary,
     testing::{mock_dependencies, mock_env},
-    Addr, Attribute, Reply, SubMsgResult, Uint128,
+    Addr, Attribute, OwnedDeps, Reply, ReplyOn, SubMsgResponse, SubMsgResult, Uint128,
 };
+use indexable_hooks::{
+    DisabledHook, DisabledHooksResponse, HookFailurePolicy, HookFailuresResponse, HookOptions,
+    HooksResponse,
+};
+use vote_hooks::{new_vote_hooks, VoteHookMsgV2};
 use voting::{
     reply::{mask_proposal_execution_proposal_id, mask_proposal_hook_index, mask_vote_hook_index},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
-    voting::Votes,
+    voting::{Vote, Votes},
 };
 
 use crate::{
-    contract::reply,
+    contract::{query, reply},
+    msg::QueryMsg,
     proposal::SingleChoiceProposal,
     state::{PROPOSALS, PROPOSAL_HOOKS, VOTE_HOOKS},
 };
@@ -33,12 +40,16 @@ fn test_reply_proposal_mock() {
                 proposer: Addr::unchecked(CREATOR_ADDR),
                 start_height: env.block.height,
                 expiration: cw_utils::Duration::Height(6).after(&env.block),
+                timelock: None,
+                timelock_expiration: None,
                 min_voting_period: None,
                 threshold: Threshold::AbsolutePercentage {
                     percentage: PercentageThreshold::Majority {},
                 },
                 allow_revoting: false,
+                revision: 0,
                 total_power: Uint128::new(100_000_000),
+                curved_power: None,
                 msgs: vec![],
                 status: Status::Open,
                 votes: Votes::zero(),
@@ -110,3 +121,168 @@ fn test_reply_hooks_mock() {
         }
     );
 }
+
+#[test]
+fn test_reply_hook_failure_policies() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    PROPOSAL_HOOKS
+        .add_hook_with_options(
+            deps.as_mut().storage,
+            Addr::unchecked("ignored"),
+            HookOptions {
+                failure_policy: HookFailurePolicy::Ignore {},
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    VOTE_HOOKS
+        .add_hook_with_options(
+            deps.as_mut().storage,
+            Addr::unchecked("failing"),
+            HookOptions {
+                failure_policy: HookFailurePolicy::Retry { times: 1 },
+                gas_limit: Some(100_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let fail = |id| Reply {
+        id,
+        result: SubMsgResult::Err("error_msg".to_string()),
+    };
+
+    // Ignored hooks stay registered and have their failures logged.
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        fail(mask_proposal_hook_index(0)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "ignored proposal hook".to_string(),
+            value: "ignored:0".to_string()
+        }
+    );
+    let hooks: HooksResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(hooks.hooks, vec!["ignored".to_string()]);
+    let failures: HookFailuresResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalHookFailures {
+                address: "ignored".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(failures.failures.len(), 1);
+    assert_eq!(failures.failures[0].error, "error_msg");
+    assert_eq!(failures.failures[0].height, env.block.height);
+
+    // Messages that fail to be delivered to hooks that retry are
+    // sent again with the next vote, and hooks are disabled once a
+    // message has failed after being retried.
+    let vote = |deps: &mut OwnedDeps<_, _, _>| {
+        let msgs = new_vote_hooks(
+            VOTE_HOOKS,
+            deps.as_mut().storage,
+            VoteHookMsgV2::NewVote {
+                proposal_id: 1,
+                voter: Addr::unchecked("voter"),
+                vote: Vote::Yes,
+                power: Uint128::new(1),
+                rationale: None,
+                module: env.contract.address.clone(),
+            },
+        )
+        .unwrap();
+        assert!(msgs
+            .iter()
+            .all(|msg| msg.reply_on == ReplyOn::Always && msg.gas_limit == Some(100_000)));
+        msgs.len()
+    };
+    let success = || Reply {
+        id: mask_vote_hook_index(0),
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    assert_eq!(vote(&mut deps), 1);
+    let res = reply(deps.as_mut(), env.clone(), fail(mask_vote_hook_index(0))).unwrap();
+    assert_eq!(res.attributes[0].key, "retrying vote hook");
+    assert_eq!(vote(&mut deps), 2);
+    let res = reply(deps.as_mut(), env.clone(), success()).unwrap();
+    assert_eq!(res.attributes[0].key, "recovered vote hook");
+    let res = reply(deps.as_mut(), env.clone(), success()).unwrap();
+    assert_eq!(res.attributes[0].key, "delivered vote hook");
+
+    assert_eq!(vote(&mut deps), 1);
+    reply(deps.as_mut(), env.clone(), fail(mask_vote_hook_index(0))).unwrap();
+    assert_eq!(vote(&mut deps), 2);
+    let res = reply(deps.as_mut(), env.clone(), fail(mask_vote_hook_index(0))).unwrap();
+    assert_eq!(res.attributes[0].key, "disabled vote hook");
+    assert_eq!(vote(&mut deps), 0);
+
+    let disabled: DisabledHooksResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::DisabledVoteHooks {}).unwrap()).unwrap();
+    assert_eq!(
+        disabled.hooks,
+        vec![DisabledHook {
+            address: "failing".to_string(),
+            last_error: "error_msg".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_reply_hooks_after_removal() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    for addr in ["first", "second"] {
+        PROPOSAL_HOOKS
+            .add_hook(deps.as_mut().storage, Addr::unchecked(addr))
+            .unwrap();
+    }
+    PROPOSAL_HOOKS
+        .remove_hook(deps.as_mut().storage, Addr::unchecked("first"))
+        .unwrap();
+
+    // Replies reach the hook they were sent to after earlier hooks
+    // are removed.
+    let reply_msg = Reply {
+        id: mask_proposal_hook_index(1),
+        result: SubMsgResult::Err("error_msg".to_string()),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "removed proposal hook".to_string(),
+            value: "second:1".to_string()
+        }
+    );
+    assert!(PROPOSAL_HOOKS
+        .query_hooks(deps.as_ref(), None, None)
+        .unwrap()
+        .hooks
+        .is_empty());
+}
@@ -1,10 +1,14 @@
 use cosmwasm_std::{CosmosMsg, Empty};
 use cw_utils::Duration;
+use indexable_hooks::{HookFailurePolicy, HookVersion};
+use proposal_hooks::ProposalHookFilter;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw_core_macros::govmod_query;
-use voting::{deposit::DepositInfo, threshold::Threshold, voting::Vote};
+use voting::{
+    curve::VotingPowerCurve, deposit::DepositInfo, threshold::Threshold, veto::Vetoer, voting::Vote,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -40,6 +44,41 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The curve applied to voting power before votes are
+    /// counted. For example, `root { degree: 2 }` enables quadratic
+    /// voting. Proposals using a non-linear curve may not complete
+    /// until they expire as the weight of outstanding votes is not
+    /// known. The curved total power is estimated as described on
+    /// `CurvedPower`, and absolute count thresholds may not be used
+    /// with a non-linear curve.
+    #[serde(default)]
+    pub voting_power_curve: VotingPowerCurve,
+    /// The amount of time after a proposal's voting period ends
+    /// before it may be executed, even if it passed early. This gives
+    /// members a chance to react to passed proposals before they are
+    /// executed. Must have the same units as `max_voting_period`.
+    pub timelock: Option<Duration>,
+    /// Who may veto passed proposals during their timelock, for
+    /// example this DAO's parent DAO. May only be set if `timelock`
+    /// is set.
+    pub vetoer: Option<Vetoer>,
+    /// Enables the pre-propose approval queue. If set, proposals
+    /// submitted by non-members, or by anyone if configured, are held
+    /// in a pending queue until an approver approves them.
+    #[serde(default)]
+    pub pre_propose: Option<PreProposeInfo>,
+}
+
+/// Information about the pre-propose approval queue.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PreProposeInfo {
+    /// The address, for example a subDAO, that may approve or reject
+    /// pending proposals.
+    pub approver: String,
+    /// If true, proposals submitted by members also require
+    /// approval. Otherwise, members' proposals are created
+    /// immediately and only non-members' proposals require approval.
+    pub require_approval_for_members: bool,
 }
 
 /// Information about the token to use for proposal deposits.
@@ -59,6 +98,7 @@ pub enum DepositToken {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Creates a proposal in the governance module.
     Propose {
@@ -77,7 +117,22 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         /// The senders position on the proposal.
         vote: Vote,
+        /// An optional explanation of the vote, at most
+        /// `MAX_RATIONALE_LENGTH` bytes long. Stored alongside the
+        /// ballot and included in vote hook messages.
+        #[serde(default)]
+        rationale: Option<String>,
     },
+    /// Delegates the sender's vote to DELEGATE, replacing any
+    /// existing delegation. When a delegate votes on a proposal they
+    /// also vote with the voting power that addresses which had
+    /// delegated to them when the proposal was created had at that
+    /// time. The sender must have voting power and a delegate may
+    /// have at most `state::MAX_DELEGATORS` delegators. A delegator
+    /// may override their delegate's vote by voting themselves.
+    Delegate { delegate: String },
+    /// Removes the sender's delegation.
+    Undelegate {},
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
     Execute {
@@ -91,6 +146,50 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Withdraws a proposal. May only be called by the proposal's
+    /// proposer while the proposal is open and has not been voted
+    /// on. The proposal deposit is refunded in full.
+    Withdraw {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+    },
+    /// Amends a proposal. May only be called by the proposal's
+    /// proposer while the proposal is open. Amending a proposal
+    /// clears its votes and restarts voting as if the proposal had
+    /// just been created. The previous version of the proposal is
+    /// kept and may be queried with `ProposalVersions`. V2 proposal
+    /// hooks are sent a `ProposalAmended` message. Proposals may not
+    /// be amended if a pre-propose approver is configured.
+    Amend {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        /// The new title of the proposal.
+        title: String,
+        /// The new description of the proposal.
+        description: String,
+        /// The new messages of the proposal.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Vetoes a passed proposal during its timelock. May only be
+    /// called by the vetoer. The proposal deposit is handled as if
+    /// the proposal had been closed.
+    Veto {
+        /// The ID of the proposal to veto.
+        proposal_id: u64,
+    },
+    /// Approves a pending proposal, creating a proposal from it. May
+    /// only be called by the approver or the DAO.
+    ApprovePendingProposal {
+        /// The ID of the pending proposal to approve.
+        pending_id: u64,
+    },
+    /// Rejects a pending proposal, removing it from the queue. The
+    /// proposal deposit is handled as if a proposal had failed. May
+    /// only be called by the approver or the DAO.
+    RejectPendingProposal {
+        /// The ID of the pending proposal to reject.
+        pending_id: u64,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal passing threshold. This will only apply
@@ -129,20 +228,69 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// The curve applied to voting power before votes are
+        /// counted. This will only apply to proposals created after
+        /// the config update.
+        #[serde(default)]
+        voting_power_curve: VotingPowerCurve,
+        /// The amount of time after a proposal's voting period ends
+        /// before it may be executed. This will only apply to
+        /// proposals created after the config update.
+        timelock: Option<Duration>,
+        /// Who may veto passed proposals during their timelock.
+        /// Applies to all outstanding and future proposals.
+        vetoer: Option<Vetoer>,
+        /// Configuration for the pre-propose approval queue. Pending
+        /// proposals remain in the queue if this is unset and may
+        /// then be approved or rejected by the DAO.
+        #[serde(default)]
+        pre_propose: Option<PreProposeInfo>,
     },
     /// Adds an address as a consumer of proposal hooks. Consumers of
     /// proposal hooks have hook messages executed on them whenever
     /// the status of a proposal changes or a proposal is created. If
     /// a consumer contract errors when handling a hook message it
     /// will be removed from the list of consumers.
-    AddProposalHook { address: String },
+    AddProposalHook {
+        address: String,
+        /// The version of the proposal hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+        /// Limits the messages sent to ADDRESS. Defaults to sending
+        /// every message.
+        #[serde(default)]
+        filter: Option<ProposalHookFilter>,
+    },
     /// Removes a consumer of proposal hooks.
     RemoveProposalHook { address: String },
     /// Adds an address as a consumer of vote hooks. Consumers of vote
     /// hooks have hook messages executed on them whenever the a vote
     /// is cast. If a consumer contract errors when handling a hook
     /// message it will be removed from the list of consumers.
-    AddVoteHook { address: String },
+    AddVoteHook {
+        address: String,
+        /// The version of the vote hook message format to send
+        /// to ADDRESS. Defaults to `HookVersion::V1`.
+        #[serde(default)]
+        version: Option<HookVersion>,
+        /// What to do when delivering a message to ADDRESS
+        /// fails. Defaults to removing the hook.
+        #[serde(default)]
+        failure_policy: Option<HookFailurePolicy>,
+        /// The maximum amount of gas delivering a message to ADDRESS
+        /// may use.
+        #[serde(default)]
+        gas_limit: Option<u64>,
+    },
     /// Removed a consumer of vote hooks.
     RemoveVoteHook { address: String },
 }
@@ -199,11 +347,74 @@ pub enum QueryMsg {
         /// query. If no limit is specified a max of 30 are returned.
         limit: Option<u64>,
     },
-    /// Lists all of the consumers of proposal hooks for this module.
-    ProposalHooks {},
-    /// Lists all of the consumers of vote hooks for this
-    /// module. Returns indexable_hooks::HooksResponse.
-    VoteHooks {},
+    /// Gets the address that DELEGATOR had delegated their vote to
+    /// at HEIGHT, or currently if HEIGHT is not set. Returns
+    /// `query::DelegationResponse`.
+    Delegation {
+        delegator: String,
+        height: Option<u64>,
+    },
+    /// Lists the addresses that had delegated their vote to DELEGATE
+    /// at HEIGHT, or currently if HEIGHT is not set. Returns
+    /// `query::DelegatorListResponse`.
+    ListDelegators {
+        delegate: String,
+        height: Option<u64>,
+        /// The delegator to start listing delegators after. Ordering
+        /// is done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of delegators to return. If no limit is
+        /// set a max of 30 will be returned.
+        limit: Option<u64>,
+    },
+    /// Lists the consumers of proposal hooks for this module in the
+    /// order they were registered. Returns
+    /// indexable_hooks::HooksResponse.
+    ProposalHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the consumers of vote hooks for this module in the order
+    /// they were registered. Returns indexable_hooks::HooksResponse.
+    VoteHooks {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Lists the proposal hooks that have been disabled after failing
+    /// too many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledProposalHooks {},
+    /// Lists the vote hooks that have been disabled after failing too
+    /// many times. Returns indexable_hooks::DisabledHooksResponse.
+    DisabledVoteHooks {},
+    /// Lists the most recent failed deliveries to a proposal
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    ProposalHookFailures { address: String },
+    /// Lists the most recent failed deliveries to a vote
+    /// hook. Returns indexable_hooks::HookFailuresResponse.
+    VoteHookFailures { address: String },
+    /// Gets information about a proposal awaiting approval. Returns
+    /// `query::PendingProposalResponse`.
+    PendingProposal { pending_id: u64 },
+    /// Lists proposals awaiting approval in ascending order of
+    /// pending ID. Returns `query::PendingProposalListResponse`.
+    ListPendingProposals {
+        /// The pending ID to start listing pending proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of pending proposals to return. If no
+        /// limit is set a max of 30 will be returned.
+        limit: Option<u64>,
+    },
+    /// Lists the previous versions of an amended proposal in
+    /// ascending order of version. The first version of a proposal is
+    /// version 1. Returns `query::ProposalVersionListResponse`.
+    ProposalVersions {
+        proposal_id: u64,
+        /// The version to start listing versions after.
+        start_after: Option<u64>,
+        /// The maximum number of versions to return. If no limit is
+        /// set a max of 30 will be returned.
+        limit: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
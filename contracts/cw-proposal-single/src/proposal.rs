@@ -1,15 +1,17 @@
 use crate::query::ProposalResponse;
-use crate::state::PROPOSAL_COUNT;
+use crate::state::{PENDING_PROPOSAL_COUNT, PROPOSAL_COUNT};
 use cosmwasm_std::{
     Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Timestamp, Uint128,
 };
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use voting::curve::CurvedPower;
 use voting::deposit::CheckedDepositInfo;
 use voting::proposal::Proposal;
 use voting::status::Status;
 use voting::threshold::{PercentageThreshold, Threshold};
+use voting::timelock::{block_expiration, passed_at, remaining};
 use voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -29,17 +31,37 @@ pub struct SingleChoiceProposal {
     /// The the time at which this proposal will expire and close for
     /// additional votes.
     pub expiration: Expiration,
+    /// The amount of time this proposal must wait after passing
+    /// before it may be executed. None if the proposal is not
+    /// timelocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timelock: Option<Duration>,
+    /// The time at which this proposal's timelock ends. Set once the
+    /// proposal has passed to `timelock` after the point at which it
+    /// passed. The proposal may not be executed until then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timelock_expiration: Option<Expiration>,
     /// The threshold at which this proposal will pass.
     pub threshold: Threshold,
     /// The total amount of voting power at the time of this
     /// proposal's creation.
     pub total_power: Uint128,
+    /// Uncurved voting power information if votes on this proposal
+    /// are weighted by a non-linear voting power curve. If set,
+    /// `votes` and `total_power` are curved weights and
+    /// `total_power` is recomputed after each vote.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curved_power: Option<CurvedPower>,
     /// The messages that will be executed should this proposal pass.
     pub msgs: Vec<CosmosMsg<Empty>>,
 
     pub status: Status,
     pub votes: Votes,
     pub allow_revoting: bool,
+    /// The number of times this proposal has been amended. Ballots
+    /// cast before the latest amendment are ignored.
+    #[serde(default)]
+    pub revision: u64,
 
     /// Information about the deposit that was sent as part of this
     /// proposal. None if no deposit.
@@ -70,6 +92,12 @@ pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
     Ok(id)
 }
 
+pub fn advance_pending_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PENDING_PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PENDING_PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
 impl SingleChoiceProposal {
     /// Consumes the proposal and returns a version which may be used
     /// in a query response. The difference being that proposal
@@ -78,9 +106,17 @@ impl SingleChoiceProposal {
     /// the proposal expiring has changed its status. This method
     /// recomputes the status so that queries get accurate
     /// information.
-    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
-        self.update_status(block);
-        ProposalResponse { id, proposal: self }
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> StdResult<ProposalResponse> {
+        self.update_status(block)?;
+        let timelock_remaining = match self.timelock_expiration {
+            Some(expiration) if self.status == Status::Passed => remaining(expiration, block),
+            _ => None,
+        };
+        Ok(ProposalResponse {
+            id,
+            proposal: self,
+            timelock_remaining,
+        })
     }
 
     /// Gets the current status of the proposal.
@@ -96,23 +132,102 @@ impl SingleChoiceProposal {
         }
     }
 
-    /// Sets a proposals status to its current status.
-    pub fn update_status(&mut self, block: &BlockInfo) {
+    /// Sets a proposals status to its current status. Must not be
+    /// used after voting on the proposal, see
+    /// `update_status_after_vote`.
+    pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
+        self.set_status(block, |prop| {
+            passed_at(prop.min_voting_period, prop.expiration, block, |at| {
+                Ok(prop.is_passed(at))
+            })
+        })
+    }
+
+    /// Sets a proposal's status to its current status after a vote
+    /// has been cast on it. If the proposal has passed it passed
+    /// because of the vote.
+    pub fn update_status_after_vote(&mut self, block: &BlockInfo) -> StdResult<()> {
+        self.set_status(block, |prop| Ok(block_expiration(&prop.expiration, block)))
+    }
+
+    /// Sets a proposal's status to its current status and, if it has
+    /// just passed, starts its timelock from the point returned by
+    /// PASSED_AT.
+    fn set_status<F>(&mut self, block: &BlockInfo, passed_at: F) -> StdResult<()>
+    where
+        F: FnOnce(&Self) -> StdResult<Expiration>,
+    {
         let new_status = self.current_status(block);
         // Update last_updated only if status changed.
         if new_status != self.status {
             self.last_updated = block.time
         }
-        self.status = new_status
+        if new_status == Status::Passed && self.status == Status::Open {
+            if let Some(timelock) = self.timelock {
+                self.timelock_expiration = Some((passed_at(self)? + timelock)?);
+            }
+        }
+        self.status = new_status;
+        Ok(())
+    }
+
+    /// Returns true if this proposal has passed but may not be
+    /// executed yet as its timelock has not ended. The proposal's
+    /// status must have been updated.
+    pub fn is_timelocked(&self, block: &BlockInfo) -> bool {
+        self.status == Status::Passed
+            && self
+                .timelock_expiration
+                .is_some_and(|expiration| !expiration.is_expired(block))
+    }
+
+    /// Returns the weight of a vote cast with POWER voting power.
+    pub fn vote_weight(&self, power: Uint128) -> Uint128 {
+        match &self.curved_power {
+            Some(curved_power) => curved_power.curve.apply(power),
+            None => power,
+        }
+    }
+
+    /// Records that an address with POWER voting power has voted for
+    /// the first time and recomputes the proposal's total power if
+    /// its votes are curved. Must be called after the address' vote
+    /// has been added to the proposal.
+    pub fn add_voter(&mut self, power: Uint128) {
+        if let Some(curved_power) = &mut self.curved_power {
+            curved_power.add_voter(power);
+            self.total_power = curved_power.total_power(self.votes.total());
+        }
+    }
+
+    /// Records that POWER of the voting power that has voted on this
+    /// proposal is no longer cast and recomputes the proposal's total
+    /// power if its votes are curved. Must be called after the vote
+    /// has been updated.
+    pub fn remove_voter_power(&mut self, power: Uint128) -> StdResult<()> {
+        if let Some(curved_power) = &mut self.curved_power {
+            curved_power.remove_power(power)?;
+            self.total_power = curved_power.total_power(self.votes.total());
+        }
+        Ok(())
+    }
+
+    /// Returns true if votes may change the outcome of this proposal
+    /// in ways that can not be known until it expires. This is the
+    /// case if revoting is enabled or votes are weighted by a
+    /// non-linear curve, in which case the weight of outstanding
+    /// votes is unknown.
+    fn is_outcome_pending(&self, block: &BlockInfo) -> bool {
+        (self.allow_revoting || self.curved_power.is_some()) && !self.expiration.is_expired(block)
     }
 
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail).
     pub fn is_passed(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed or votes are curved nothing is
+        // known until the proposal has expired.
+        if self.is_outcome_pending(block) {
             return false;
         }
         // If the min voting period is set and not expired the
@@ -155,9 +270,9 @@ impl SingleChoiceProposal {
     /// As above for the passed check, used to check if a proposal is
     /// already rejected.
     pub fn is_rejected(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed or votes are curved and the
+        // proposal is not expired no information is known.
+        if self.is_outcome_pending(block) {
             return false;
         }
 
@@ -295,12 +410,16 @@ mod test {
             proposer: Addr::unchecked("test"),
             start_height: 100,
             expiration,
+            timelock: None,
+            timelock_expiration: None,
             min_voting_period: Some(min_voting_period),
             allow_revoting,
+            revision: 0,
             msgs: vec![],
             status: Status::Open,
             threshold,
             total_power,
+            curved_power: None,
             votes,
             deposit_info: None,
             created: block.time,
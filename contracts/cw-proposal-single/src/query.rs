@@ -1,10 +1,12 @@
 use cosmwasm_std::{Addr, Uint128};
+use cw_utils::Duration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use voting::voting::Vote;
 
 use crate::proposal::SingleChoiceProposal;
+use crate::state::{PendingProposal, ProposalVersion};
 
 /// Information about a proposal returned by proposal queries.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -12,6 +14,10 @@ pub struct ProposalResponse {
     /// The ID of the proposal being returned.
     pub id: u64,
     pub proposal: SingleChoiceProposal,
+    /// The amount of time remaining until the proposal may be
+    /// executed if it has passed and is in its timelock. None
+    /// otherwise.
+    pub timelock_remaining: Option<Duration>,
 }
 
 /// Information about a vote that was cast.
@@ -23,6 +29,11 @@ pub struct VoteInfo {
     pub vote: Vote,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// The voting power delegated to the voter that was cast with
+    /// this vote. Included in `power`.
+    pub delegated_power: Uint128,
+    /// The voter's explanation of their vote, if they gave one.
+    pub rationale: Option<String>,
 }
 
 /// Information about a vote.
@@ -44,3 +55,49 @@ pub struct VoteListResponse {
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// Information about a proposal awaiting approval.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingProposalResponse {
+    /// The pending ID of the proposal. This is not the ID the
+    /// proposal will have once approved.
+    pub pending_id: u64,
+    pub proposal: PendingProposal,
+}
+
+/// A list of proposals returned by `ListPendingProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingProposalListResponse {
+    pub pending_proposals: Vec<PendingProposalResponse>,
+}
+
+/// A previous version of an amended proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalVersionResponse {
+    pub version: u64,
+    pub proposal: ProposalVersion,
+}
+
+/// A list of proposal versions returned by `ProposalVersions`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalVersionListResponse {
+    pub versions: Vec<ProposalVersionResponse>,
+}
+
+/// Information about a delegation.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegationResponse {
+    /// The address the delegator had delegated their vote to. None if
+    /// they had not delegated their vote.
+    pub delegate: Option<Addr>,
+    /// The delegator's voting power at the queried height, which is
+    /// counted with their delegate's votes on proposals created at
+    /// that height. Zero if they had not delegated their vote.
+    pub power: Uint128,
+}
+
+/// A list of delegators returned by `ListDelegators`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegatorListResponse {
+    pub delegators: Vec<Addr>,
+}
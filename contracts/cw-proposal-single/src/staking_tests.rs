@@ -4,7 +4,7 @@ use cw_core::{msg::ModuleInstantiateInfo, query::DumpStateResponse};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use cw_utils::Duration;
 
-use voting::threshold::Threshold;
+use voting::{curve::VotingPowerCurve, threshold::Threshold};
 
 use crate::msg::InstantiateMsg;
 
@@ -115,6 +115,10 @@ fn instantiate_with_staked_balances_voting() {
                 allow_revoting: false,
                 deposit_info: None,
                 close_proposal_on_execution_failure: true,
+                voting_power_curve: VotingPowerCurve::Linear {},
+                timelock: None,
+                vetoer: None,
+                pre_propose: None,
             })
             .unwrap(),
         }],
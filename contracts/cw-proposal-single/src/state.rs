@@ -1,22 +1,40 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 use cw_utils::Duration;
 
 use indexable_hooks::Hooks;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use voting::{deposit::CheckedDepositInfo, threshold::Threshold, voting::Vote};
+use voting::{
+    curve::VotingPowerCurve,
+    deposit::CheckedDepositInfo,
+    threshold::Threshold,
+    veto::CheckedVetoer,
+    voting::{Vote, Votes},
+};
 
 use crate::proposal::SingleChoiceProposal;
 
 /// A vote cast for a proposal.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Ballot {
-    /// The amount of voting power behind the vote.
+    /// The weight of the vote. This is the voter's voting power with
+    /// the proposal's voting power curve applied.
     pub power: Uint128,
     /// The position.
     pub vote: Vote,
+    /// The voting power delegated to the voter that was cast with
+    /// this vote. Included in `power`.
+    #[serde(default)]
+    pub delegated_power: Uint128,
+    /// The voter's explanation of their vote, if they gave one.
+    #[serde(default)]
+    pub rationale: Option<String>,
+    /// The revision of the proposal this vote was cast on. Votes cast
+    /// before the proposal was last amended do not count.
+    #[serde(default)]
+    pub revision: u64,
 }
 /// The governance module's configuration.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -55,6 +73,70 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The curve applied to voting power before votes are
+    /// counted. Linear unless configured otherwise.
+    #[serde(default)]
+    pub voting_power_curve: VotingPowerCurve,
+    /// The amount of time after a proposal's voting period ends
+    /// before it may be executed. None if proposals may be executed
+    /// as soon as they pass.
+    #[serde(default)]
+    pub timelock: Option<Duration>,
+    /// Who may veto passed proposals during their timelock.
+    #[serde(default)]
+    pub vetoer: Option<CheckedVetoer>,
+    /// Configuration for the pre-propose approval queue. None if
+    /// proposals are created without approval.
+    #[serde(default)]
+    pub pre_propose: Option<PreProposeConfig>,
+}
+
+/// An address' delegation of its vote.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Delegation {
+    /// The address the vote is delegated to.
+    pub delegate: Addr,
+}
+
+/// Counterpart to `msg::PreProposeInfo` which has been validated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PreProposeConfig {
+    /// The address that may approve or reject pending proposals.
+    pub approver: Addr,
+    /// If proposals submitted by members also require approval.
+    pub require_approval_for_members: bool,
+}
+
+/// A proposal that has been submitted but not yet approved. Pending
+/// proposals do not have proposal IDs and may not be voted on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that submitted this proposal.
+    pub proposer: Addr,
+    /// The messages that will be executed should this proposal be
+    /// approved and pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// Information about the deposit that was paid when this
+    /// proposal was submitted.
+    pub deposit_info: Option<CheckedDepositInfo>,
+    /// The timestamp at which this proposal was submitted.
+    pub created: Timestamp,
+}
+
+/// A previous version of a proposal that has since been amended.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalVersion {
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The block height at which voting on this version started.
+    pub start_height: u64,
+    /// The votes cast on this version before it was amended.
+    pub votes: Votes,
+    /// The timestamp at which this version was amended.
+    pub amended: Timestamp,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -64,7 +146,40 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+/// Maps delegators to their delegation. Snapshotted so that
+/// delegations may be looked up at the start height of a proposal.
+pub const DELEGATIONS: SnapshotMap<Addr, Delegation> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+/// The maximum number of addresses that may delegate their vote to a
+/// single delegate. Bounds the voting power lookups made when a
+/// delegate votes.
+pub const MAX_DELEGATORS: usize = 50;
+/// The addresses that have delegated their vote to each delegate, in
+/// ascending order. Snapshotted so that a delegate's votes may include
+/// the power their delegators had at the start height of a proposal.
+/// A delegate has at most `MAX_DELEGATORS` delegators.
+pub const DELEGATORS: SnapshotMap<Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegator_lists",
+    "delegator_lists__checkpoints",
+    "delegator_lists__changelog",
+    Strategy::EveryBlock,
+);
+/// The voting power of delegators that voted on a proposal
+/// themselves, keyed by (proposal ID, proposal revision,
+/// delegate). This power is not counted with the delegate's vote.
+pub const OVERRIDDEN_POWER: Map<(u64, u64, Addr), Uint128> = Map::new("overridden_power");
+/// Previous versions of amended proposals keyed by (proposal ID,
+/// version).
+pub const PROPOSAL_VERSIONS: Map<(u64, u64), ProposalVersion> = Map::new("proposal_versions");
+/// The number of proposals that have been submitted to the
+/// pre-propose queue.
+pub const PENDING_PROPOSAL_COUNT: Item<u64> = Item::new("pending_proposal_count");
+pub const PENDING_PROPOSALS: Map<u64, PendingProposal> = Map::new("pending_proposals");
 /// Consumers of proposal state change hooks.
-pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks", "proposal_hook_indices");
 /// Consumers of vote hooks.
-pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_indices");
@@ -1,8 +1,7 @@
-use std::u128;
-
 use cosmwasm_std::{
+    coins,
     testing::{mock_dependencies, mock_env},
-    to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Order, Timestamp, Uint128, WasmMsg,
+    to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Order, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw20::Cw20Coin;
 use cw20_staked_balance_voting::msg::ActiveThreshold;
@@ -10,8 +9,8 @@ use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Execut
 
 use cw_core::{msg::ModuleInstantiateInfo, state::ProposalModule};
 use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
 use cw_utils::Expiration;
+use cw_utils::{Duration, PaymentError};
 
 use indexable_hooks::HooksResponse;
 
@@ -19,18 +18,28 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use testing::{ShouldExecute, TestSingleChoiceVote};
 use voting::{
-    deposit::{CheckedDepositInfo, DepositInfo, DepositToken},
+    curve::VotingPowerCurve,
+    deposit::{
+        CheckedDepositInfo, CheckedDepositToken, DepositInfo, DepositRefundPolicy, DepositToken,
+    },
+    error::VotingError,
+    proposal::MAX_RATIONALE_LENGTH,
     status::Status,
     threshold::{PercentageThreshold, Threshold},
+    veto::Vetoer,
     voting::{Vote, Votes},
 };
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, PreProposeInfo, QueryMsg},
     proposal::SingleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteResponse},
-    state::Config,
+    query::{
+        DelegationResponse, DelegatorListResponse, PendingProposalListResponse,
+        PendingProposalResponse, ProposalListResponse, ProposalResponse,
+        ProposalVersionListResponse, VoteInfo, VoteListResponse, VoteResponse,
+    },
+    state::{Config, PROPOSAL_HOOKS},
     ContractError,
 };
 
@@ -890,6 +899,10 @@ where
         allow_revoting: false,
         deposit_info,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -915,7 +928,9 @@ where
         .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
     if let Some(CheckedDepositInfo {
-        ref token, deposit, ..
+        token: CheckedDepositToken::Cw20(ref token),
+        deposit,
+        ..
     }) = config.deposit_info
     {
         app.execute_contract(
@@ -958,6 +973,7 @@ where
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: position,
+                rationale: None,
             },
             &[],
         );
@@ -989,6 +1005,8 @@ where
                             }
                             None => weight,
                         },
+                        delegated_power: Uint128::zero(),
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -1049,6 +1067,10 @@ fn test_propose() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -1081,6 +1103,10 @@ fn test_propose() {
         dao: governance_addr,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
     assert_eq!(config, expected);
 
@@ -1108,10 +1134,14 @@ fn test_propose() {
         proposer: Addr::unchecked(CREATOR_ADDR),
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
+        timelock: None,
+        timelock_expiration: None,
         min_voting_period: None,
         threshold,
         allow_revoting: false,
+        revision: 0,
         total_power: Uint128::new(100_000_000),
+        curved_power: None,
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
@@ -1141,6 +1171,10 @@ fn test_propose_supports_stargate_message() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -1186,10 +1220,14 @@ fn test_propose_supports_stargate_message() {
         proposer: Addr::unchecked(CREATOR_ADDR),
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
+        timelock: None,
+        timelock_expiration: None,
         min_voting_period: None,
         threshold,
         allow_revoting: false,
+        revision: 0,
         total_power: Uint128::new(100_000_000),
+        curved_power: None,
         msgs: vec![CosmosMsg::Stargate {
             type_url: "foo_type".to_string(),
             value: to_binary("foo_bin").unwrap(),
@@ -1348,9 +1386,13 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
         deposit_info: Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -1381,9 +1423,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     assert_eq!(
         config.deposit_info,
         Some(CheckedDepositInfo {
-            token: expected_token,
+            token: CheckedDepositToken::Cw20(expected_token),
             deposit: Uint128::new(1),
-            refund_failed_proposals: true
+            refund_policy: DepositRefundPolicy::Always {}
         })
     )
 }
@@ -1428,9 +1470,13 @@ fn test_different_token_proposal_deposit() {
                 address: cw20_addr.to_string(),
             },
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
@@ -1486,9 +1532,13 @@ fn test_bad_token_proposal_deposit() {
                 address: votemod_addr.to_string(),
             },
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
@@ -1512,9 +1562,13 @@ fn test_take_proposal_deposit() {
         deposit_info: Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -1541,11 +1595,14 @@ fn test_take_proposal_deposit() {
         .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
     let CheckedDepositInfo {
-        token,
+        token: CheckedDepositToken::Cw20(token),
         deposit,
-        refund_failed_proposals,
-    } = govmod_config.deposit_info.unwrap();
-    assert!(refund_failed_proposals);
+        refund_policy,
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
+    assert_eq!(refund_policy, DepositRefundPolicy::Always {});
     assert_eq!(deposit, Uint128::new(1));
 
     // This should fail because we have not created an allowance for
@@ -1621,7 +1678,7 @@ fn test_deposit_return_on_execute() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
     );
     let gov_state: cw_core::query::DumpStateResponse = app
@@ -1637,7 +1694,13 @@ fn test_deposit_return_on_execute() {
         .wrap()
         .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -1693,7 +1756,7 @@ fn test_close_open_proposal() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
     );
 
@@ -1734,7 +1797,13 @@ fn test_close_open_proposal() {
         .wrap()
         .query_wasm_smart(govmod_single, &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -1767,7 +1836,7 @@ fn test_zero_deposit() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(0),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
     );
 }
@@ -1789,7 +1858,7 @@ fn test_deposit_return_on_close() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
     );
     let gov_state: cw_core::query::DumpStateResponse = app
@@ -1805,7 +1874,13 @@ fn test_deposit_return_on_close() {
         .wrap()
         .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -1862,6 +1937,10 @@ fn test_execute_expired_proposal() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -1901,6 +1980,7 @@ fn test_execute_expired_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -1982,7 +2062,7 @@ fn test_update_config() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
     );
 
@@ -2025,6 +2105,10 @@ fn test_update_config() {
             dao: CREATOR_ADDR.to_string(),
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         &[],
     )
@@ -2045,6 +2129,10 @@ fn test_update_config() {
             dao: CREATOR_ADDR.to_string(),
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         &[],
     )
@@ -2066,6 +2154,10 @@ fn test_update_config() {
         dao: Addr::unchecked(CREATOR_ADDR),
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
     assert_eq!(govmod_config, expected);
 
@@ -2085,6 +2177,10 @@ fn test_update_config() {
             dao: CREATOR_ADDR.to_string(),
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         &[],
     )
@@ -2108,7 +2204,7 @@ fn test_no_return_if_no_refunds() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
     );
     let gov_state: cw_core::query::DumpStateResponse = app
@@ -2124,7 +2220,13 @@ fn test_no_return_if_no_refunds() {
         .wrap()
         .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = govmod_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
 
     // Close the proposal, this should cause the deposit to be
     // refunded.
@@ -2168,6 +2270,10 @@ fn test_query_list_proposals() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![Cw20Coin {
             address: CREATOR_ADDR.to_string(),
@@ -2236,13 +2342,17 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: app.block_info().height,
             expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
+            timelock: None,
+            timelock_expiration: None,
             min_voting_period: None,
             threshold: Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Majority {},
                 quorum: PercentageThreshold::Percent(Decimal::percent(0)),
             },
             allow_revoting: false,
+            revision: 0,
             total_power: Uint128::new(100),
+            curved_power: None,
             msgs: vec![],
             status: Status::Open,
             votes: Votes::zero(),
@@ -2250,6 +2360,7 @@ fn test_query_list_proposals() {
             created: app.block_info().time,
             last_updated: app.block_info().time,
         },
+        timelock_remaining: None,
     };
     assert_eq!(proposals_forward.proposals[0], expected);
 
@@ -2283,13 +2394,17 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: app.block_info().height,
             expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
+            timelock: None,
+            timelock_expiration: None,
             min_voting_period: None,
             threshold: Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Majority {},
                 quorum: PercentageThreshold::Percent(Decimal::percent(0)),
             },
             allow_revoting: false,
+            revision: 0,
             total_power: Uint128::new(100),
+            curved_power: None,
             msgs: vec![],
             status: Status::Open,
             votes: Votes::zero(),
@@ -2297,6 +2412,7 @@ fn test_query_list_proposals() {
             created: app.block_info().time,
             last_updated: app.block_info().time,
         },
+        timelock_remaining: None,
     };
     assert_eq!(proposals_forward.proposals[0], expected);
     assert_eq!(proposals_backward.proposals[1], expected);
@@ -2322,6 +2438,10 @@ fn test_hooks() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -2349,18 +2469,34 @@ fn test_hooks() {
     // Expect no hooks
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 0);
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 0);
 
     let msg = ExecuteMsg::AddProposalHook {
         address: "some_addr".to_string(),
+        version: None,
+        failure_policy: None,
+        gas_limit: None,
+        filter: None,
     };
 
     // Expect error as sender is not DAO
@@ -2380,7 +2516,13 @@ fn test_hooks() {
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
@@ -2422,6 +2564,9 @@ fn test_hooks() {
 
     let msg = ExecuteMsg::AddVoteHook {
         address: "some_addr".to_string(),
+        version: None,
+        failure_policy: None,
+        gas_limit: None,
     };
 
     // Expect error as sender is not DAO
@@ -2441,7 +2586,13 @@ fn test_hooks() {
 
     let hooks: HooksResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
@@ -2497,6 +2648,10 @@ fn test_active_threshold_absolute() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr = instantiate_with_staking_active_threshold(
@@ -2624,6 +2779,10 @@ fn test_active_threshold_percent() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     // 20% needed to be active, 20% of 100000000 is 20000000
@@ -2752,6 +2911,10 @@ fn test_active_threshold_none() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -2832,6 +2995,10 @@ fn test_active_threshold_none() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -2884,6 +3051,10 @@ fn test_revoting() {
             allow_revoting: true,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -2931,6 +3102,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -2945,6 +3117,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -2963,6 +3136,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -2998,6 +3172,206 @@ fn test_revoting() {
 /// Tests that revoting is stored at a per-proposal level. Proposals
 /// created while revoting is enabled should not have it disabled if a
 /// config change turns if off.
+#[test]
+fn test_quadratic_voting() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let mut initial_balances = vec![Cw20Coin {
+        address: "whale".to_string(),
+        amount: Uint128::new(900),
+    }];
+    initial_balances.extend((0..4).map(|i| Cw20Coin {
+        address: format!("minnow-{}", i),
+        amount: Uint128::new(100),
+    }));
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Root { degree: 2 },
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
+        },
+        Some(initial_balances),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("whale"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Whale watching".to_string(),
+            description: "Fund a whale watching expedition.".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    // sqrt(1300) rounded down.
+    assert_eq!(proposal.proposal.total_power, Uint128::new(36));
+
+    // With linear voting the whale would hold a majority of voting
+    // power. With quadratic voting they have a weight of 30.
+    app.execute_contract(
+        Addr::unchecked("whale"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "whale".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vote.vote.unwrap().power, Uint128::new(30));
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    // 30 cast with 900 of 1300 power, so the outstanding power is
+    // expected to carry a weight of 30 * 400 / 900.
+    assert_eq!(proposal.proposal.total_power, Uint128::new(43));
+    // The weight of outstanding votes is not known so the proposal
+    // can not pass early.
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    for i in 0..4 {
+        app.execute_contract(
+            Addr::unchecked(format!("minnow-{}", i)),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::No,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.total_power, Uint128::new(70));
+    assert_eq!(proposal.proposal.votes.no, Uint128::new(40));
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.update_block(|b| b.height += 10);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+}
+
+#[test]
+fn test_invalid_voting_power_curve() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Root { degree: 1 },
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(voting::error::VotingError::InvalidRootDegree {})
+    ));
+
+    // Absolute counts can not be compared against curved votes.
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsoluteCount {
+            threshold: Uint128::new(10),
+        },
+        voting_power_curve: VotingPowerCurve::Root { degree: 2 },
+        ..instantiate
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(voting::error::VotingError::CurvedAbsoluteCount {})
+    ));
+}
+
 #[test]
 fn test_allow_revoting_config_changes() {
     let mut app = App::default();
@@ -3016,6 +3390,10 @@ fn test_allow_revoting_config_changes() {
             allow_revoting: true,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3069,6 +3447,10 @@ fn test_allow_revoting_config_changes() {
             deposit_info: None,
             dao: core_addr.to_string(),
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         &[],
     )
@@ -3089,6 +3471,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3100,6 +3483,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3124,6 +3508,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 2,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3136,6 +3521,7 @@ fn test_allow_revoting_config_changes() {
             &ExecuteMsg::Vote {
                 proposal_id: 2,
                 vote: Vote::Yes,
+                rationale: None,
             },
             &[],
         )
@@ -3166,6 +3552,10 @@ fn test_revoting_same_vote_twice() {
             allow_revoting: true,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3208,6 +3598,7 @@ fn test_revoting_same_vote_twice() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3220,6 +3611,7 @@ fn test_revoting_same_vote_twice() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: Vote::Yes,
+                rationale: None,
             },
             &[],
         )
@@ -3239,6 +3631,7 @@ fn test_revoting_same_vote_twice() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: Vote::No,
+                rationale: None,
             },
             &[],
         )
@@ -3249,6 +3642,7 @@ fn test_revoting_same_vote_twice() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: Vote::Yes,
+                rationale: None,
             },
             &[],
         )
@@ -3274,6 +3668,10 @@ fn test_three_of_five_multisig() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3328,6 +3726,7 @@ fn test_three_of_five_multisig() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3338,6 +3737,7 @@ fn test_three_of_five_multisig() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3359,6 +3759,7 @@ fn test_three_of_five_multisig() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3406,6 +3807,10 @@ fn test_three_of_five_multisig_reject() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3460,6 +3865,7 @@ fn test_three_of_five_multisig_reject() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3470,6 +3876,7 @@ fn test_three_of_five_multisig_reject() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3481,6 +3888,7 @@ fn test_three_of_five_multisig_reject() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3492,6 +3900,7 @@ fn test_three_of_five_multisig_reject() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3545,9 +3954,13 @@ fn test_voting_module_token_with_multisig_style_voting() {
             deposit_info: Some(DepositInfo {
                 token: DepositToken::VotingModuleToken {},
                 deposit: Uint128::new(1),
-                refund_failed_proposals: true,
+                refund_policy: DepositRefundPolicy::Always {},
             }),
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3584,6 +3997,10 @@ fn test_three_of_five_multisig_revoting() {
             allow_revoting: true,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3638,6 +4055,7 @@ fn test_three_of_five_multisig_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3648,6 +4066,7 @@ fn test_three_of_five_multisig_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3659,6 +4078,7 @@ fn test_three_of_five_multisig_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3670,6 +4090,7 @@ fn test_three_of_five_multisig_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -3692,6 +4113,7 @@ fn test_three_of_five_multisig_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -3826,6 +4248,10 @@ fn test_migrate_from_compatible() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -3885,6 +4311,10 @@ fn test_proposal_count_initialized_to_zero() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3932,6 +4362,10 @@ fn test_no_early_pass_with_min_duration() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3974,6 +4408,7 @@ fn test_no_early_pass_with_min_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4021,6 +4456,10 @@ fn test_min_duration_units_missmatch() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4054,6 +4493,10 @@ fn test_min_duration_larger_than_proposal_duration() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4086,6 +4529,10 @@ fn test_min_duration_same_as_proposal_duration() {
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4128,6 +4575,7 @@ fn test_min_duration_same_as_proposal_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4150,6 +4598,7 @@ fn test_min_duration_same_as_proposal_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -4183,6 +4632,10 @@ fn test_timestamp_updated() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -4275,6 +4728,7 @@ fn test_timestamp_updated() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4329,6 +4783,7 @@ fn test_timestamp_updated() {
         &ExecuteMsg::Vote {
             proposal_id: 2,
             vote: Vote::No,
+            rationale: None,
         },
         &[],
     )
@@ -4411,6 +4866,14 @@ fn test_migrate_mock() {
     };
     v1_item.save(&mut deps.storage, &v1_config).unwrap();
 
+    let v1_hooks: Item<Vec<Addr>> = Item::new("proposal_hooks");
+    v1_hooks
+        .save(
+            &mut deps.storage,
+            &vec![Addr::unchecked("first"), Addr::unchecked("second")],
+        )
+        .unwrap();
+
     let msg = MigrateMsg::FromV1 {
         close_proposal_on_execution_failure: true,
     };
@@ -4441,8 +4904,18 @@ fn test_migrate_mock() {
             dao: Addr::unchecked("simple happy desert"),
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            voting_power_curve: VotingPowerCurve::Linear {},
+            timelock: None,
+            vetoer: None,
+            pre_propose: None,
         }
     );
+
+    let hooks = PROPOSAL_HOOKS
+        .query_hooks(deps.as_ref(), None, None)
+        .unwrap();
+    assert_eq!(hooks.hooks, vec!["first", "second"]);
+    assert!(v1_hooks.may_load(&deps.storage).unwrap().is_none());
 }
 
 #[test]
@@ -4462,7 +4935,7 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
         Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
-            refund_failed_proposals: false,
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
         }),
     );
 
@@ -4493,7 +4966,13 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
         .wrap()
         .query_wasm_smart(proposal_single, &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = proposal_config.deposit_info.unwrap();
+    let CheckedDepositInfo {
+        token: CheckedDepositToken::Cw20(token),
+        ..
+    } = proposal_config.deposit_info.unwrap()
+    else {
+        panic!("deposit token is not a cw20")
+    };
     let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
@@ -4525,6 +5004,10 @@ fn test_close_failed_proposal() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -4612,6 +5095,7 @@ fn test_close_failed_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4664,6 +5148,10 @@ fn test_close_failed_proposal() {
                         dao: original.dao.to_string(),
                         deposit_info: None,
                         close_proposal_on_execution_failure: false,
+                        voting_power_curve: VotingPowerCurve::Linear {},
+                        timelock: None,
+                        vetoer: None,
+                        pre_propose: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -4681,6 +5169,7 @@ fn test_close_failed_proposal() {
             &ExecuteMsg::Vote {
                 proposal_id: 2,
                 vote: Vote::Yes,
+                rationale: None,
             },
             &[],
         )
@@ -4721,6 +5210,7 @@ fn test_close_failed_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 3,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4766,9 +5256,13 @@ fn test_no_double_refund_on_execute_fail_and_close() {
             // Important to set to true here as we want to be sure
             // that we don't get a second refund on close. Refunds on
             // close only happen if this is true.
-            refund_failed_proposals: true,
+            refund_policy: DepositRefundPolicy::Always {},
         }),
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let core_addr = instantiate_with_staking_active_threshold(
@@ -4893,6 +5387,7 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -4965,8 +5460,1942 @@ fn test_no_double_refund_on_execute_fail_and_close() {
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
     cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    crate::state::CONFIG
+        .save(&mut deps.storage, &legacy_deposit_config())
+        .unwrap();
     migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
     let version = cw2::get_contract_version(&deps.storage).unwrap();
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+fn legacy_deposit_config() -> Config {
+    Config {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Time(604800),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        dao: Addr::unchecked("dao"),
+        deposit_info: Some(CheckedDepositInfo {
+            token: CheckedDepositToken::Cw20(Addr::unchecked("token")),
+            deposit: Uint128::new(1),
+            refund_policy: DepositRefundPolicy::OnlyPassed {},
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    }
+}
+
+#[test]
+fn test_migrate_refund_policy() {
+    let mut deps = mock_dependencies();
+    // Store a config as it was before refund policies were added.
+    let config = String::from_utf8(cosmwasm_std::to_vec(&legacy_deposit_config()).unwrap())
+        .unwrap()
+        .replace(
+            r#""refund_policy":{"only_passed":{}}"#,
+            r#""refund_failed_proposals":false"#,
+        );
+    assert!(config.contains("refund_failed_proposals"));
+    deps.storage.set(b"config_v2", config.as_bytes());
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let stored = deps.storage.get(b"config_v2").unwrap();
+    assert!(!String::from_utf8(stored)
+        .unwrap()
+        .contains("refund_failed_proposals"));
+    let config = crate::state::CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config, legacy_deposit_config());
+}
+
+#[test]
+fn test_timelock_and_veto() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: Some(Duration::Height(4)),
+        vetoer: Some(Vetoer::Address {
+            address: "vetoer".to_string(),
+        }),
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let start_height = app.block_info().height;
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    for proposal_id in 1..=2 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Proposals that pass early are timelocked from when they
+    // passed, not from the end of their voting period.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::Timelocked { expiration } if expiration == Expiration::AtHeight(start_height + 4)
+    ));
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.timelock_remaining, Some(Duration::Height(4)));
+
+    // Only the vetoer may veto.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Veto { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked("vetoer"),
+        govmod_single.clone(),
+        &ExecuteMsg::Veto { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+    assert_eq!(proposal.timelock_remaining, None);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    // Once the timelock ends proposals may no longer be vetoed and
+    // may be executed.
+    app.update_block(|block| block.height += 4);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("vetoer"),
+            govmod_single.clone(),
+            &ExecuteMsg::Veto { proposal_id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotTimelocked {}));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_timelock_starts_when_passed() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: Some(Duration::Height(2)),
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: Some(Duration::Height(4)),
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let start_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The proposal passed once its min voting period ended, even
+    // though nothing updated its status then.
+    app.update_block(|block| block.height += 5);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.timelock_remaining, Some(Duration::Height(1)));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::Timelocked { expiration } if expiration == Expiration::AtHeight(start_height + 6)
+    ));
+
+    app.update_block(next_block);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single,
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_timelock() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: Some(Vetoer::Address {
+            address: "vetoer".to_string(),
+        }),
+        pre_propose: None,
+    };
+    let err = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal-single",
+            None,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::VetoerWithoutTimelock {}
+    ));
+
+    let instantiate = InstantiateMsg {
+        timelock: Some(Duration::Time(100)),
+        vetoer: None,
+        pre_propose: None,
+        ..instantiate
+    };
+    let err = app
+        .instantiate_contract(
+            govmod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal-single",
+            None,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::VotingError(VotingError::TimelockUnitsConflict {})
+    ));
+}
+
+#[test]
+fn test_parent_dao_veto() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: Some(Duration::Height(4)),
+        vetoer: Some(Vetoer::ParentDao {}),
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The DAO is its own admin so it has no parent DAO that may
+    // veto.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("parent"),
+            govmod_single.clone(),
+            &ExecuteMsg::Veto { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Make "parent" the DAO's admin.
+    app.execute_contract(
+        governance_addr.clone(),
+        governance_addr.clone(),
+        &cw_core::msg::ExecuteMsg::NominateAdmin {
+            admin: Some("parent".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("parent"),
+        governance_addr,
+        &cw_core::msg::ExecuteMsg::AcceptAdminNomination {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("parent"),
+        govmod_single.clone(),
+        &ExecuteMsg::Veto { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+}
+
+#[test]
+fn test_pre_propose_approval() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: Some(PreProposeInfo {
+            approver: "approver".to_string(),
+            require_approval_for_members: false,
+        }),
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose = |app: &mut App, proposer: &str| {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Non-members' proposals are added to the pending queue while
+    // members' proposals are created immediately.
+    propose(&mut app, "nonmember");
+    propose(&mut app, CREATOR_ADDR);
+    propose(&mut app, "nonmember");
+
+    let count: u64 = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalCount {})
+        .unwrap();
+    assert_eq!(count, 1);
+    let pending: PendingProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListPendingProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pending
+            .pending_proposals
+            .iter()
+            .map(|p| (p.pending_id, p.proposal.proposer.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(1, "nonmember"), (2, "nonmember")]
+    );
+
+    // Only the approver may approve.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::ApprovePendingProposal { pending_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked("approver"),
+        govmod_single.clone(),
+        &ExecuteMsg::ApprovePendingProposal { pending_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("nonmember"));
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.execute_contract(
+        Addr::unchecked("approver"),
+        govmod_single.clone(),
+        &ExecuteMsg::RejectPendingProposal { pending_id: 2 },
+        &[],
+    )
+    .unwrap();
+    let pending: PendingProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListPendingProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(pending.pending_proposals.is_empty());
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("approver"),
+            govmod_single.clone(),
+            &ExecuteMsg::ApprovePendingProposal { pending_id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::NoSuchPendingProposal { id: 2 }
+    ));
+
+    // Approved proposals may not be amended, which would bypass
+    // approval of the new messages.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("nonmember"),
+            govmod_single.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: 2,
+                title: "Amended".to_string(),
+                description: "This is an amended proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AmendWithApprover {}));
+
+    let count: u64 = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::ProposalCount {})
+        .unwrap();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_pre_propose_rejection_refund() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_policy: DepositRefundPolicy::Always {},
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: Some(PreProposeInfo {
+            approver: "approver".to_string(),
+            require_approval_for_members: true,
+        }),
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(2),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositToken::Cw20(token) = govmod_config.deposit_info.unwrap().token else {
+        panic!("deposit token is not a cw20")
+    };
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Members' proposals require approval as well and the deposit is
+    // taken on submission.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let pending: PendingProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::PendingProposal { pending_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(pending.proposal.proposer, Addr::unchecked("ekez"));
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(1));
+
+    app.execute_contract(
+        Addr::unchecked("approver"),
+        govmod_single,
+        &ExecuteMsg::RejectPendingProposal { pending_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(2));
+}
+
+#[test]
+fn test_native_proposal_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always {},
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: "ekez".to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(20),
+        }],
+    }))
+    .unwrap();
+
+    let propose = |app: &mut App, funds: &[Coin]| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+
+    let err = propose(&mut app, &[]).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::Payment(_))
+    ));
+    let err = propose(
+        &mut app,
+        &[Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(5),
+        }],
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidDeposit { .. })
+    ));
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: "ekez".to_string(),
+        amount: coins(1, "uatom"),
+    }))
+    .unwrap();
+    let mut funds = coins(10, "ujuno");
+    funds.extend(coins(1, "uatom"));
+    let err = propose(&mut app, &funds).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::Payment(PaymentError::MultipleDenoms {}))
+    ));
+
+    propose(
+        &mut app,
+        &[Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(10),
+        }],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance("ekez", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(10));
+
+    // Reject the proposal and close it to get a refund.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance("ekez", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(20));
+}
+
+#[test]
+fn test_propose_without_native_deposit_nonpayable() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }))
+    .unwrap();
+
+    // Funds sent with a proposal that has no native deposit would be
+    // stuck in the proposal module.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::Payment(PaymentError::NonPayable {}))
+    ));
+}
+
+#[test]
+fn test_slashed_refund_policy() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let mut instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Slashed {
+                slash: Decimal::percent(30),
+            },
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate.clone(),
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: "ekez".to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(20),
+        }],
+    }))
+    .unwrap();
+
+    let propose_and_vote = |app: &mut App, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(10),
+            }],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Failed proposals have part of their deposit slashed.
+    propose_and_vote(&mut app, 1, Vote::No);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance("ekez", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(17));
+    let balance = app
+        .wrap()
+        .query_balance(governance_addr.clone(), "ujuno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::new(3));
+
+    // Passed proposals are refunded in full.
+    propose_and_vote(&mut app, 2, Vote::Yes);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance("ekez", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(17));
+
+    // Slashing more than the deposit is not allowed.
+    instantiate.deposit_info = Some(DepositInfo {
+        token: DepositToken::Native {
+            denom: "ujuno".to_string(),
+        },
+        deposit: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::Slashed {
+            slash: Decimal::percent(101),
+        },
+    });
+    let err: ContractError = app
+        .execute_contract(
+            governance_addr,
+            govmod_single,
+            &ExecuteMsg::UpdateConfig {
+                threshold: instantiate.threshold,
+                max_voting_period: instantiate.max_voting_period,
+                min_voting_period: None,
+                only_members_execute: false,
+                allow_revoting: false,
+                dao: "dao".to_string(),
+                deposit_info: instantiate.deposit_info,
+                close_proposal_on_execution_failure: true,
+                voting_power_curve: VotingPowerCurve::Linear {},
+                timelock: None,
+                vetoer: None,
+                pre_propose: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidSlash {})
+    ));
+}
+
+#[test]
+fn test_vote_delegation() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(25),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(4),
+            },
+            Cw20Coin {
+                address: "dave".to_string(),
+                amount: Uint128::new(51),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let delegate = |app: &mut App, delegator: &str| {
+        app.execute_contract(
+            Addr::unchecked(delegator),
+            govmod_single.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: "carol".to_string(),
+            },
+            &[],
+        )
+    };
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("dave"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote,
+                rationale: None,
+            },
+            &[],
+        )
+    };
+    let proposal = |app: &App, proposal_id: u64| -> ProposalResponse {
+        app.wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap()
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("carol"),
+            govmod_single.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: "carol".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::SelfDelegation {}));
+    // Addresses without voting power may not delegate.
+    let err: ContractError = delegate(&mut app, "eve").unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    delegate(&mut app, "alice").unwrap();
+    delegate(&mut app, "bob").unwrap();
+    app.update_block(next_block);
+    let delegation_height = app.block_info().height;
+
+    propose(&mut app);
+    // Delegations made after a proposal is created do not apply to
+    // it.
+    delegate(&mut app, "dave").unwrap();
+
+    vote(&mut app, "carol", 1, Vote::Yes).unwrap();
+    let prop = proposal(&app, 1).proposal;
+    assert_eq!(prop.votes.yes, Uint128::new(49));
+    assert_eq!(prop.status, Status::Open);
+
+    // Voting directly overrides a delegate's vote.
+    vote(&mut app, "alice", 1, Vote::No).unwrap();
+    let prop = proposal(&app, 1).proposal;
+    assert_eq!(prop.votes.yes, Uint128::new(24));
+    assert_eq!(prop.votes.no, Uint128::new(25));
+    // But only once.
+    let err: ContractError = vote(&mut app, "alice", 1, Vote::Yes)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+
+    vote(&mut app, "dave", 1, Vote::No).unwrap();
+    assert_eq!(proposal(&app, 1).proposal.status, Status::Rejected);
+
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        votes.votes,
+        vec![
+            VoteInfo {
+                voter: Addr::unchecked("alice"),
+                vote: Vote::No,
+                power: Uint128::new(25),
+                delegated_power: Uint128::zero(),
+                rationale: None,
+            },
+            VoteInfo {
+                voter: Addr::unchecked("carol"),
+                vote: Vote::Yes,
+                power: Uint128::new(24),
+                delegated_power: Uint128::new(20),
+                rationale: None,
+            },
+            VoteInfo {
+                voter: Addr::unchecked("dave"),
+                vote: Vote::No,
+                power: Uint128::new(51),
+                delegated_power: Uint128::zero(),
+                rationale: None,
+            },
+        ]
+    );
+
+    // Dave's delegation and Bob's undelegation apply to proposals
+    // created in later blocks.
+    app.execute_contract(
+        Addr::unchecked("bob"),
+        govmod_single.clone(),
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    propose(&mut app);
+    vote(&mut app, "carol", 2, Vote::Yes).unwrap();
+    let prop = proposal(&app, 2).proposal;
+    assert_eq!(prop.votes.yes, Uint128::new(80));
+    assert_eq!(prop.status, Status::Passed);
+
+    // Delegators that vote before their delegate are not counted
+    // with the delegate's vote.
+    propose(&mut app);
+    vote(&mut app, "alice", 3, Vote::No).unwrap();
+    vote(&mut app, "carol", 3, Vote::Yes).unwrap();
+    let prop = proposal(&app, 3).proposal;
+    assert_eq!(prop.votes.yes, Uint128::new(55));
+    assert_eq!(prop.votes.no, Uint128::new(25));
+
+    let delegators: DelegatorListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListDelegators {
+                delegate: "carol".to_string(),
+                height: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        delegators.delegators,
+        vec![Addr::unchecked("alice"), Addr::unchecked("dave")]
+    );
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Delegation {
+                delegator: "bob".to_string(),
+                height: Some(delegation_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked("carol")));
+    assert_eq!(delegation.power, Uint128::new(20));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            govmod_single,
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotDelegating {}));
+}
+
+#[test]
+fn test_vote_delegation_unstaked() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            gov_state.voting_module,
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod_single.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: "carol".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Alice unstakes after delegating. Her delegation no longer
+    // carries any voting power.
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        staking_contract,
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(30),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    app.execute_contract(
+        Addr::unchecked("bob"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("carol"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(40));
+    assert_eq!(proposal.proposal.total_power, Uint128::new(70));
+
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &QueryMsg::Delegation {
+                delegator: "alice".to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked("carol")));
+    assert_eq!(delegation.power, Uint128::zero());
+}
+
+#[test]
+fn test_withdraw_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        // Revoting keeps voted on proposals open.
+        allow_revoting: true,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Never {},
+        }),
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(20),
+        }],
+    }))
+    .unwrap();
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(10),
+            }],
+        )
+        .unwrap();
+    }
+
+    let withdraw = |app: &mut App, sender: &str, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            govmod_single.clone(),
+            &ExecuteMsg::Withdraw { proposal_id },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+
+    // Only the proposer may withdraw a proposal.
+    let err = withdraw(&mut app, "ekez", 1).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Withdrawn proposals have their deposits refunded in full.
+    withdraw(&mut app, CREATOR_ADDR, 1).unwrap();
+    let balance = app.wrap().query_balance(CREATOR_ADDR, "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(10));
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Withdrawn);
+
+    let err = withdraw(&mut app, CREATOR_ADDR, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+
+    // Proposals may not be withdrawn once voted on.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err = withdraw(&mut app, CREATOR_ADDR, 2).unwrap_err();
+    assert!(matches!(err, ContractError::WithdrawAfterVote {}));
+}
+
+#[test]
+fn test_amend_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        // Revoting keeps voted on proposals open.
+        allow_revoting: true,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let first_start_height = app.block_info().height;
+    let vote = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let amend = |app: &mut App, sender: &str, title: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            govmod_single.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: 1,
+                title: title.to_string(),
+                description: "This is an amended proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+    vote(&mut app);
+
+    // Only the proposer may amend a proposal.
+    let err = amend(&mut app, "ekez", "Amended").unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.update_block(next_block);
+    amend(&mut app, CREATOR_ADDR, "Amended").unwrap();
+
+    // Votes are cleared, ballots cast on the previous version are
+    // ignored, and voting restarts.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.title, "Amended");
+    assert_eq!(proposal.proposal.votes, Votes::zero());
+    assert_eq!(proposal.proposal.start_height, app.block_info().height);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(app.block_info().height + 6)
+    );
+    let ballot: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(ballot.vote, None);
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(votes.votes, vec![]);
+
+    // The proposer may vote again on the amended proposal.
+    vote(&mut app);
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(votes.votes.len(), 1);
+    amend(&mut app, CREATOR_ADDR, "Amended again").unwrap();
+
+    let versions: ProposalVersionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::ProposalVersions {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let versions: Vec<(u64, String, u64, Uint128)> = versions
+        .versions
+        .into_iter()
+        .map(|v| {
+            (
+                v.version,
+                v.proposal.title,
+                v.proposal.start_height,
+                v.proposal.votes.yes,
+            )
+        })
+        .collect();
+    assert_eq!(
+        versions,
+        vec![
+            (
+                1,
+                "A simple text proposal".to_string(),
+                first_start_height,
+                Uint128::new(100_000_000)
+            ),
+            (
+                2,
+                "Amended".to_string(),
+                first_start_height + 1,
+                Uint128::new(100_000_000)
+            ),
+        ]
+    );
+
+    // Proposals may only be amended while open.
+    app.update_block(|block| block.height += 6);
+    let err = amend(&mut app, CREATOR_ADDR, "Too late").unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+}
+
+#[test]
+fn test_vote_rationale() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: true,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(70),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Rationales longer than the maximum are rejected.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+                rationale: Some("a".repeat(MAX_RATIONALE_LENGTH as usize + 1)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::RationaleTooLong { .. })
+    ));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: Some("I wrote it.".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote.vote.unwrap().rationale,
+        Some("I wrote it.".to_string())
+    );
+
+    // Revoting replaces the rationale along with the vote.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+            rationale: Some("Changed my mind.".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let rationales: Vec<_> = votes
+        .votes
+        .into_iter()
+        .map(|vote| (vote.voter.into_string(), vote.rationale))
+        .collect();
+    assert_eq!(
+        rationales,
+        vec![
+            (
+                CREATOR_ADDR.to_string(),
+                Some("Changed my mind.".to_string())
+            ),
+            ("ekez".to_string(), None),
+        ]
+    );
+}
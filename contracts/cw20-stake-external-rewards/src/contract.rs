@@ -1,10 +1,10 @@
 use crate::msg::{
-    ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, PendingRewardsResponse, QueryMsg,
-    ReceiveMsg,
+    ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, PendingReward, PendingRewardsResponse,
+    QueryMsg, ReceiveMsg, StakeChangedHookMsg,
 };
 use crate::state::{
-    Config, RewardConfig, CONFIG, LAST_UPDATE_BLOCK, PENDING_REWARDS, REWARD_CONFIG,
-    REWARD_PER_TOKEN, USER_REWARD_PER_TOKEN,
+    denom_key, now, v1, Config, RewardConfig, CONFIG, LAST_UPDATE, PENDING_REWARDS, REWARD_CONFIGS,
+    REWARD_PER_TOKEN, USER_REWARD_PER_TOKEN, V1_STREAM,
 };
 use crate::ContractError;
 use crate::ContractError::{
@@ -15,19 +15,27 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, Uint256, WasmMsg,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, Uint256, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::{Cw20ReceiveMsg, Denom};
-use cw20_stake::hooks::StakeChangedHookMsg;
+use cw_core_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 
 use cw20::Denom::Cw20;
-use std::cmp::min;
+use cw_utils::{Duration, Expiration};
+use std::cmp::{max, min};
 use std::convert::TryInto;
 
 const CONTRACT_NAME: &str = "crates.io:cw20-stake-external-rewards";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum number of reward streams a contract may have. Every
+/// stake change updates all of the streams so this bounds the gas
+/// used by staking and unstaking.
+pub const MAX_REWARD_STREAMS: u32 = 10;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -48,31 +56,32 @@ pub fn instantiate(
         Cw20(addr) => Cw20(deps.api.addr_validate(addr.as_ref())?),
     };
 
-    if msg.reward_duration == 0 {
-        return Err(ContractError::ZeroRewardDuration {});
-    }
+    validate_reward_duration(&msg.reward_duration)?;
 
     // Verify contract provided is a staking contract
-    let _: cw20_stake::msg::TotalStakedAtHeightResponse = deps.querier.query_wasm_smart(
-        &msg.staking_contract,
-        &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None },
-    )?;
+    let staking_contract = deps.api.addr_validate(&msg.staking_contract)?;
+    get_total_staked(deps.as_ref(), &staking_contract)?;
 
     let config = Config {
         owner,
         manager,
-        staking_contract: deps.api.addr_validate(&msg.staking_contract)?,
-        reward_token,
+        staking_contract,
     };
     CONFIG.save(deps.storage, &config)?;
 
     // Non-zero rewards duration checked above.
     let reward_config = RewardConfig {
+        reward_token,
+        period_start: 0,
         period_finish: 0,
         reward_rate: Uint128::zero(),
         reward_duration: msg.reward_duration,
     };
-    REWARD_CONFIG.save(deps.storage, &reward_config)?;
+    REWARD_CONFIGS.save(
+        deps.storage,
+        &denom_key(&reward_config.reward_token),
+        &reward_config,
+    )?;
 
     Ok(Response::new()
         .add_attribute(
@@ -92,7 +101,7 @@ pub fn instantiate(
         .add_attribute("staking_contract", config.staking_contract)
         .add_attribute(
             "reward_token",
-            match config.reward_token {
+            match reward_config.reward_token {
                 Denom::Native(denom) => denom,
                 Cw20(addr) => addr.into_string(),
             },
@@ -102,13 +111,78 @@ pub fn instantiate(
         .add_attribute("reward_duration", reward_config.reward_duration.to_string()))
 }
 
+fn validate_reward_duration(duration: &Duration) -> Result<(), ContractError> {
+    match duration {
+        Duration::Height(0) | Duration::Time(0) => Err(ContractError::ZeroRewardDuration {}),
+        _ => Ok(()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Contracts from before reward streams were added store their
+    // single reward token's state outside of the stream maps.
+    if let Some(reward_config) = v1::REWARD_CONFIG.may_load(deps.storage)? {
+        migrate_v1_reward_stream(deps.storage, reward_config)?;
+    }
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
 
+fn migrate_v1_reward_stream(
+    storage: &mut dyn Storage,
+    reward_config: v1::RewardConfig,
+) -> StdResult<()> {
+    let v1::Config {
+        owner,
+        manager,
+        staking_contract,
+        reward_token,
+    } = v1::CONFIG.load(storage)?;
+    CONFIG.save(
+        storage,
+        &Config {
+            owner,
+            manager,
+            staking_contract,
+        },
+    )?;
+
+    let key = denom_key(&reward_token);
+    REWARD_CONFIGS.save(
+        storage,
+        &key,
+        &RewardConfig {
+            reward_token,
+            // v1 periods always started when they were funded.
+            period_start: reward_config
+                .period_finish
+                .saturating_sub(reward_config.reward_duration),
+            period_finish: reward_config.period_finish,
+            reward_rate: reward_config.reward_rate,
+            // v1 streams always distributed rewards per block.
+            reward_duration: Duration::Height(reward_config.reward_duration),
+        },
+    )?;
+    if let Some(reward_per_token) = v1::REWARD_PER_TOKEN.may_load(storage)? {
+        REWARD_PER_TOKEN.save(storage, &key, &reward_per_token)?;
+    }
+    if let Some(last_update_block) = v1::LAST_UPDATE_BLOCK.may_load(storage)? {
+        LAST_UPDATE.save(storage, &key, &last_update_block)?;
+    }
+
+    // There is an entry per staker in the v1 per-user maps, too many
+    // to move in one migration. They are moved as each staker's
+    // rewards are updated instead.
+    V1_STREAM.save(storage, &key)?;
+
+    v1::REWARD_CONFIG.remove(storage);
+    v1::REWARD_PER_TOKEN.remove(storage);
+    v1::LAST_UPDATE_BLOCK.remove(storage);
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -118,11 +192,20 @@ pub fn execute(
 ) -> Result<Response<Empty>, ContractError> {
     match msg {
         ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
-        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
-        ExecuteMsg::Fund {} => execute_fund_native(deps, env, info),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info, false),
+        ExecuteMsg::ClaimAndRestake {} => execute_claim(deps, env, info, true),
+        ExecuteMsg::Fund { start } => execute_fund_native(deps, env, info, start),
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
-        ExecuteMsg::UpdateRewardDuration { new_duration } => {
-            execute_update_reward_duration(deps, env, info, new_duration)
+        ExecuteMsg::AddRewardStream {
+            reward_token,
+            reward_duration,
+        } => execute_add_reward_stream(deps, env, info, reward_token, reward_duration),
+        ExecuteMsg::UpdateRewardDuration {
+            reward_token,
+            new_duration,
+        } => execute_update_reward_duration(deps, env, info, reward_token, new_duration),
+        ExecuteMsg::CancelRewards { reward_token } => {
+            execute_cancel_rewards(deps, env, info, reward_token)
         }
         ExecuteMsg::UpdateOwner { new_owner } => execute_update_owner(deps, env, info, new_owner),
         ExecuteMsg::UpdateManager { new_manager } => {
@@ -138,13 +221,15 @@ pub fn execute_receive(
     wrapper: Cw20ReceiveMsg,
 ) -> Result<Response<Empty>, ContractError> {
     let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
-    let config = CONFIG.load(deps.storage)?;
     let sender = deps.api.addr_validate(&wrapper.sender)?;
-    if config.reward_token != Denom::Cw20(info.sender) {
+    let reward_token = Denom::Cw20(info.sender);
+    if !REWARD_CONFIGS.has(deps.storage, &denom_key(&reward_token)) {
         return Err(InvalidCw20 {});
     };
     match msg {
-        ReceiveMsg::Fund {} => execute_fund(deps, env, sender, wrapper.amount),
+        ReceiveMsg::Fund { start } => {
+            execute_fund(deps, env, sender, reward_token, wrapper.amount, start)
+        }
     }
 }
 
@@ -152,23 +237,24 @@ pub fn execute_fund_native(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    start: Option<Expiration>,
 ) -> Result<Response<Empty>, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    match config.reward_token {
-        Denom::Native(denom) => {
-            let amount = cw_utils::must_pay(&info, &denom).map_err(|_| InvalidFunds {})?;
-            execute_fund(deps, env, info.sender, amount)
-        }
-        Cw20(_) => Err(InvalidFunds {}),
+    // Only one reward stream may be funded at a time.
+    let Coin { denom, amount } = cw_utils::one_coin(&info).map_err(|_| InvalidFunds {})?;
+    let reward_token = Denom::Native(denom);
+    if !REWARD_CONFIGS.has(deps.storage, &denom_key(&reward_token)) {
+        return Err(InvalidFunds {});
     }
+    execute_fund(deps, env, info.sender, reward_token, amount, start)
 }
 
 pub fn execute_fund(
     mut deps: DepsMut,
     env: Env,
     sender: Addr,
+    reward_token: Denom,
     amount: Uint128,
+    start: Option<Expiration>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.owner != Some(sender.clone()) && config.manager != Some(sender.clone()) {
@@ -176,32 +262,174 @@ pub fn execute_fund(
     };
 
     update_rewards(&mut deps, &env, &sender)?;
-    let reward_config = REWARD_CONFIG.load(deps.storage)?;
-    if reward_config.period_finish > env.block.height {
-        return Err(RewardPeriodNotFinished {});
-    }
-    let new_reward_config = RewardConfig {
-        period_finish: env.block.height + reward_config.reward_duration,
-        reward_rate: amount
-            .checked_div(Uint128::from(reward_config.reward_duration))
-            .map_err(StdError::divide_by_zero)?,
-        // As we're not changing the value and changing the value
-        // validates that the duration is non-zero we don't need to
-        // check here.
-        reward_duration: reward_config.reward_duration,
-    };
-
-    if new_reward_config.reward_rate == Uint128::zero() {
-        return Err(ContractError::RewardRateLessThenOnePerBlock {});
+    let key = denom_key(&reward_token);
+    let reward_config = REWARD_CONFIGS.load(deps.storage, &key)?;
+    let now = reward_config.now(&env.block);
+    let new_reward_config = if reward_config.period_finish > now {
+        // The current period is running or scheduled. Distribute the
+        // new funds over its remainder without restarting it.
+        if start.is_some() {
+            return Err(RewardPeriodNotFinished {});
+        }
+        let remaining = reward_config.period_finish - max(now, reward_config.period_start);
+        let additional_rate = amount
+            .checked_div(Uint128::from(remaining))
+            .map_err(StdError::divide_by_zero)?;
+        if additional_rate == Uint128::zero() {
+            return Err(ContractError::RewardRateLessThenOnePerBlock {});
+        };
+        RewardConfig {
+            reward_rate: reward_config
+                .reward_rate
+                .checked_add(additional_rate)
+                .map_err(StdError::overflow)?,
+            ..reward_config
+        }
+    } else {
+        let period_start = match (start, reward_config.reward_duration) {
+            (None, _) => now,
+            (Some(Expiration::AtHeight(height)), Duration::Height(_)) => height,
+            (Some(Expiration::AtTime(time)), Duration::Time(_)) => time.seconds(),
+            _ => return Err(ContractError::InvalidStart {}),
+        };
+        if period_start < now {
+            return Err(ContractError::InvalidStart {});
+        }
+        let period_length = reward_config.period_length();
+        let new_reward_config = RewardConfig {
+            reward_token: reward_config.reward_token,
+            period_start,
+            period_finish: period_start + period_length,
+            reward_rate: amount
+                .checked_div(Uint128::from(period_length))
+                .map_err(StdError::divide_by_zero)?,
+            // As we're not changing the value and changing the value
+            // validates that the duration is non-zero we don't need to
+            // check here.
+            reward_duration: reward_config.reward_duration,
+        };
+        if new_reward_config.reward_rate == Uint128::zero() {
+            return Err(ContractError::RewardRateLessThenOnePerBlock {});
+        };
+        new_reward_config
     };
 
-    REWARD_CONFIG.save(deps.storage, &new_reward_config)?;
-    LAST_UPDATE_BLOCK.save(deps.storage, &env.block.height)?;
+    REWARD_CONFIGS.save(deps.storage, &key, &new_reward_config)?;
+    LAST_UPDATE.save(deps.storage, &key, &now)?;
 
     Ok(Response::new()
         .add_attribute("action", "fund")
+        .add_attribute("reward_token", key)
         .add_attribute("amount", amount)
-        .add_attribute("new_reward_rate", new_reward_config.reward_rate.to_string()))
+        .add_attribute("new_reward_rate", new_reward_config.reward_rate.to_string())
+        .add_attribute("period_start", new_reward_config.period_start.to_string())
+        .add_attribute("period_finish", new_reward_config.period_finish.to_string()))
+}
+
+pub fn execute_cancel_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_token: Option<Denom>,
+) -> Result<Response<Empty>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if Some(info.sender.clone()) != config.owner {
+        return Err(Unauthorized {});
+    };
+
+    let reward_config = load_reward_config(deps.storage, reward_token)?;
+    let now = reward_config.now(&env.block);
+    if reward_config.period_finish <= now {
+        return Err(ContractError::RewardPeriodFinished {});
+    }
+
+    // Distribute the rewards up to now before the stream is stopped
+    // so that they remain claimable.
+    let total_staked = get_total_staked(deps.as_ref(), &config.staking_contract)?;
+    update_reward_per_token(&mut deps, &env, &reward_config, total_staked)?;
+
+    let remaining = reward_config.period_finish - max(now, reward_config.period_start);
+    let refund = reward_config
+        .reward_rate
+        .checked_mul(Uint128::from(remaining))
+        .map_err(StdError::overflow)?;
+    let key = denom_key(&reward_config.reward_token);
+    REWARD_CONFIGS.save(
+        deps.storage,
+        &key,
+        &RewardConfig {
+            reward_token: reward_config.reward_token.clone(),
+            period_start: min(reward_config.period_start, now),
+            period_finish: now,
+            reward_rate: Uint128::zero(),
+            reward_duration: reward_config.reward_duration,
+        },
+    )?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "cancel_rewards")
+        .add_attribute("reward_token", key)
+        .add_attribute("refund", refund);
+    if !refund.is_zero() {
+        response = response.add_message(get_transfer_msg(
+            info.sender,
+            refund,
+            reward_config.reward_token,
+        )?);
+    }
+    Ok(response)
+}
+
+pub fn execute_add_reward_stream(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    reward_token: Denom,
+    reward_duration: Duration,
+) -> Result<Response<Empty>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if Some(info.sender.clone()) != config.owner && Some(info.sender) != config.manager {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    let reward_token = match reward_token {
+        Denom::Native(denom) => Denom::Native(denom),
+        Cw20(addr) => Cw20(deps.api.addr_validate(addr.as_ref())?),
+    };
+    validate_reward_duration(&reward_duration)?;
+
+    let key = denom_key(&reward_token);
+    if REWARD_CONFIGS.has(deps.storage, &key) {
+        return Err(ContractError::DuplicateRewardStream {});
+    }
+    let streams = REWARD_CONFIGS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if streams >= MAX_REWARD_STREAMS as usize {
+        return Err(ContractError::TooManyRewardStreams {
+            max: MAX_REWARD_STREAMS,
+        });
+    }
+
+    // Stakers' accumulated reward per token for a new stream starts
+    // at zero, the same as the stream's, so no rewards are owed for
+    // the time before the stream was added.
+    REWARD_CONFIGS.save(
+        deps.storage,
+        &key,
+        &RewardConfig {
+            reward_token,
+            period_start: 0,
+            period_finish: 0,
+            reward_rate: Uint128::zero(),
+            reward_duration,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_reward_stream")
+        .add_attribute("reward_token", key)
+        .add_attribute("reward_duration", reward_duration.to_string()))
 }
 
 pub fn execute_stake_changed(
@@ -238,25 +466,87 @@ pub fn execute_unstake(
     Ok(Response::new().add_attribute("action", "unstake"))
 }
 
+/// Pays out the sender's pending rewards. If RESTAKE is set, the
+/// rewards of streams distributing the staking contract's token are
+/// staked for the sender instead.
 pub fn execute_claim(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    restake: bool,
 ) -> Result<Response<Empty>, ContractError> {
     update_rewards(&mut deps, &env, &info.sender)?;
-    let rewards = PENDING_REWARDS
-        .load(deps.storage, info.sender.clone())
-        .map_err(|_| NoRewardsClaimable {})?;
-    if rewards == Uint128::zero() {
-        return Err(ContractError::NoRewardsClaimable {});
-    }
-    PENDING_REWARDS.save(deps.storage, info.sender.clone(), &Uint128::zero())?;
     let config = CONFIG.load(deps.storage)?;
-    let transfer_msg = get_transfer_msg(info.sender, rewards, config.reward_token)?;
-    Ok(Response::new()
-        .add_message(transfer_msg)
-        .add_attribute("action", "claim")
-        .add_attribute("amount", rewards))
+    let staked_token = if restake {
+        let staking_config: cw20_stake::state::Config = deps
+            .querier
+            .query_wasm_smart(
+                &config.staking_contract,
+                &cw20_stake::msg::QueryMsg::GetConfig {},
+            )
+            .map_err(|_| ContractError::RestakeUnsupported {})?;
+        Some(staking_config.token_address)
+    } else {
+        None
+    };
+    let action = if restake {
+        "claim_and_restake"
+    } else {
+        "claim"
+    };
+    let mut response = Response::new().add_attribute("action", action);
+    let mut claimed = false;
+    for reward_config in load_reward_configs(deps.storage)? {
+        let key = denom_key(&reward_config.reward_token);
+        let rewards = PENDING_REWARDS
+            .may_load(deps.storage, (&key, &info.sender))?
+            .unwrap_or_default();
+        if rewards.is_zero() {
+            continue;
+        }
+        PENDING_REWARDS.save(deps.storage, (&key, &info.sender), &Uint128::zero())?;
+        let msg = match (&staked_token, reward_config.reward_token) {
+            (Some(staked_token), Denom::Cw20(token)) if *staked_token == token => get_restake_msg(
+                &config.staking_contract,
+                info.sender.clone(),
+                rewards,
+                token,
+            )?,
+            (_, reward_token) => get_transfer_msg(info.sender.clone(), rewards, reward_token)?,
+        };
+        response = response
+            .add_message(msg)
+            .add_attribute("reward_token", key)
+            .add_attribute("amount", rewards);
+        claimed = true;
+    }
+    if !claimed {
+        return Err(NoRewardsClaimable {});
+    }
+    Ok(response)
+}
+
+/// Stakes AMOUNT of the cw20 TOKEN in STAKING_CONTRACT on behalf of
+/// RECIPIENT.
+fn get_restake_msg(
+    staking_contract: &Addr,
+    recipient: Addr,
+    amount: Uint128,
+    token: Addr,
+) -> StdResult<CosmosMsg> {
+    let cw20_msg = to_binary(&cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount,
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::StakeFor {
+            recipient: recipient.into_string(),
+        })?,
+    })?;
+    Ok(WasmMsg::Execute {
+        contract_addr: token.into_string(),
+        msg: cw20_msg,
+        funds: vec![],
+    }
+    .into())
 }
 
 pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdResult<CosmosMsg> {
@@ -281,34 +571,101 @@ pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdRe
     }
 }
 
+/// Updates the rewards of every reward stream for ADDR. Must be
+/// called before ADDR's staked balance changes.
 pub fn update_rewards(deps: &mut DepsMut, env: &Env, addr: &Addr) -> StdResult<()> {
     let config = CONFIG.load(deps.storage)?;
-    let reward_per_token = get_reward_per_token(deps.as_ref(), env, &config.staking_contract)?;
-    REWARD_PER_TOKEN.save(deps.storage, &reward_per_token)?;
-
-    let earned_rewards = get_rewards_earned(
-        deps.as_ref(),
-        env,
-        addr,
-        reward_per_token,
-        &config.staking_contract,
-    )?;
-    PENDING_REWARDS.update::<_, StdError>(deps.storage, addr.clone(), |r| {
-        Ok(r.unwrap_or_default() + earned_rewards)
-    })?;
-
-    USER_REWARD_PER_TOKEN.save(deps.storage, addr.clone(), &reward_per_token)?;
-    let last_time_reward_applicable = get_last_time_reward_applicable(deps.as_ref(), env)?;
-    LAST_UPDATE_BLOCK.save(deps.storage, &last_time_reward_applicable)?;
+    let total_staked = get_total_staked(deps.as_ref(), &config.staking_contract)?;
+    let staked_balance = get_staked_balance(deps.as_ref(), &config.staking_contract, addr)?;
+    for reward_config in load_reward_configs(deps.storage)? {
+        let key = denom_key(&reward_config.reward_token);
+        let reward_per_token = update_reward_per_token(deps, env, &reward_config, total_staked)?;
+
+        let earned_rewards = get_rewards_earned(
+            deps.as_ref(),
+            addr,
+            &reward_config,
+            reward_per_token,
+            staked_balance,
+        )?;
+        let pending_rewards = load_pending_rewards(deps.storage, &key, addr)?;
+        PENDING_REWARDS.save(
+            deps.storage,
+            (&key, addr),
+            &(pending_rewards + earned_rewards),
+        )?;
+
+        USER_REWARD_PER_TOKEN.save(deps.storage, (&key, addr), &reward_per_token)?;
+        if is_v1_stream(deps.storage, &key)? {
+            v1::PENDING_REWARDS.remove(deps.storage, addr.clone());
+            v1::USER_REWARD_PER_TOKEN.remove(deps.storage, addr.clone());
+        }
+    }
     Ok(())
 }
 
-pub fn get_reward_per_token(deps: Deps, env: &Env, staking_contract: &Addr) -> StdResult<Uint256> {
-    let reward_config = REWARD_CONFIG.load(deps.storage)?;
-    let total_staked = get_total_staked(deps, staking_contract)?;
-    let last_time_reward_applicable = get_last_time_reward_applicable(deps, env)?;
-    let last_update_block = LAST_UPDATE_BLOCK.load(deps.storage).unwrap_or_default();
-    let prev_reward_per_token = REWARD_PER_TOKEN.load(deps.storage).unwrap_or_default();
+fn is_v1_stream(storage: &dyn Storage, key: &str) -> StdResult<bool> {
+    Ok(V1_STREAM.may_load(storage)?.as_deref() == Some(key))
+}
+
+/// Loads ADDR's pending rewards for the stream stored under KEY,
+/// falling back to the v1 maps if the stream was migrated from a v1
+/// contract and ADDR's rewards have not been updated since.
+fn load_pending_rewards(storage: &dyn Storage, key: &str, addr: &Addr) -> StdResult<Uint128> {
+    match PENDING_REWARDS.may_load(storage, (key, addr))? {
+        Some(rewards) => Ok(rewards),
+        None if is_v1_stream(storage, key)? => Ok(v1::PENDING_REWARDS
+            .may_load(storage, addr.clone())?
+            .unwrap_or_default()),
+        None => Ok(Uint128::zero()),
+    }
+}
+
+/// Loads ADDR's reward per token for the stream stored under KEY as
+/// of their last update, falling back to the v1 maps like
+/// `load_pending_rewards`.
+fn load_user_reward_per_token(storage: &dyn Storage, key: &str, addr: &Addr) -> StdResult<Uint256> {
+    match USER_REWARD_PER_TOKEN.may_load(storage, (key, addr))? {
+        Some(reward_per_token) => Ok(reward_per_token),
+        None if is_v1_stream(storage, key)? => Ok(v1::USER_REWARD_PER_TOKEN
+            .may_load(storage, addr.clone())?
+            .unwrap_or_default()),
+        None => Ok(Uint256::zero()),
+    }
+}
+
+/// Brings the reward per token of REWARD_CONFIG's stream up to date
+/// and returns it.
+fn update_reward_per_token(
+    deps: &mut DepsMut,
+    env: &Env,
+    reward_config: &RewardConfig,
+    total_staked: Uint128,
+) -> StdResult<Uint256> {
+    let key = denom_key(&reward_config.reward_token);
+    let reward_per_token = get_reward_per_token(deps.as_ref(), env, reward_config, total_staked)?;
+    REWARD_PER_TOKEN.save(deps.storage, &key, &reward_per_token)?;
+    let last_time_reward_applicable = get_last_time_reward_applicable(env, reward_config);
+    LAST_UPDATE.save(deps.storage, &key, &last_time_reward_applicable)?;
+    Ok(reward_per_token)
+}
+
+pub fn get_reward_per_token(
+    deps: Deps,
+    env: &Env,
+    reward_config: &RewardConfig,
+    total_staked: Uint128,
+) -> StdResult<Uint256> {
+    let key = denom_key(&reward_config.reward_token);
+    let last_time_reward_applicable = get_last_time_reward_applicable(env, reward_config);
+    let last_update = LAST_UPDATE
+        .may_load(deps.storage, &key)?
+        .unwrap_or_default();
+    let prev_reward_per_token = REWARD_PER_TOKEN
+        .may_load(deps.storage, &key)?
+        .unwrap_or_default();
+    // Rewards are not distributed before the period starts.
+    let distributed_since = max(last_update, reward_config.period_start);
     let additional_reward_per_token = if total_staked == Uint128::zero() {
         Uint256::zero()
     } else {
@@ -317,7 +674,7 @@ pub fn get_reward_per_token(deps: Deps, env: &Env, staking_contract: &Addr) -> S
         let numerator = reward_config
             .reward_rate
             .full_mul(Uint128::from(
-                last_time_reward_applicable - last_update_block,
+                last_time_reward_applicable.saturating_sub(distributed_since),
             ))
             .checked_mul(scale_factor())?;
         let denominator = Uint256::from(total_staked);
@@ -329,71 +686,133 @@ pub fn get_reward_per_token(deps: Deps, env: &Env, staking_contract: &Addr) -> S
 
 pub fn get_rewards_earned(
     deps: Deps,
-    _env: &Env,
     addr: &Addr,
+    reward_config: &RewardConfig,
     reward_per_token: Uint256,
-    staking_contract: &Addr,
+    staked_balance: Uint128,
 ) -> StdResult<Uint128> {
-    let _config = CONFIG.load(deps.storage)?;
-    let staked_balance = Uint256::from(get_staked_balance(deps, staking_contract, addr)?);
-    let user_reward_per_token = USER_REWARD_PER_TOKEN
-        .load(deps.storage, addr.clone())
-        .unwrap_or_default();
+    let key = denom_key(&reward_config.reward_token);
+    let user_reward_per_token = load_user_reward_per_token(deps.storage, &key, addr)?;
     let reward_factor = reward_per_token.checked_sub(user_reward_per_token)?;
-    Ok(staked_balance
+    Ok(Uint256::from(staked_balance)
         .checked_mul(reward_factor)?
         .checked_div(scale_factor())?
         .try_into()?)
 }
 
-fn get_last_time_reward_applicable(deps: Deps, env: &Env) -> StdResult<u64> {
-    let reward_config = REWARD_CONFIG.load(deps.storage)?;
-    Ok(min(env.block.height, reward_config.period_finish))
+fn get_last_time_reward_applicable(env: &Env, reward_config: &RewardConfig) -> u64 {
+    min(reward_config.now(&env.block), reward_config.period_finish)
+}
+
+fn load_reward_configs(storage: &dyn Storage) -> StdResult<Vec<RewardConfig>> {
+    REWARD_CONFIGS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, reward_config)| reward_config))
+        .collect()
+}
+
+/// Loads the reward stream distributing REWARD_TOKEN, or the only
+/// reward stream if REWARD_TOKEN is not provided.
+fn load_reward_config(
+    storage: &dyn Storage,
+    reward_token: Option<Denom>,
+) -> Result<RewardConfig, ContractError> {
+    match reward_token {
+        Some(reward_token) => REWARD_CONFIGS
+            .may_load(storage, &denom_key(&reward_token))?
+            .ok_or(ContractError::UnknownRewardStream {}),
+        None => {
+            let mut reward_configs = load_reward_configs(storage)?;
+            if reward_configs.len() != 1 {
+                return Err(ContractError::RewardTokenRequired {});
+            }
+            Ok(reward_configs.remove(0))
+        }
+    }
 }
 
+// Versions of cw20-stake from before it answered voting module
+// queries are queried for their staked balances directly, so that
+// rewards contracts keep working if they are migrated before the
+// staking contract.
+
 fn get_total_staked(deps: Deps, contract_addr: &Addr) -> StdResult<Uint128> {
-    let msg = cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None };
-    let resp: cw20_stake::msg::TotalStakedAtHeightResponse =
-        deps.querier.query_wasm_smart(contract_addr, &msg)?;
-    Ok(resp.total)
+    let msg = VotingQuery::TotalPowerAtHeight { height: None };
+    match deps
+        .querier
+        .query_wasm_smart::<TotalPowerAtHeightResponse>(contract_addr, &msg)
+    {
+        Ok(resp) => Ok(resp.power),
+        Err(_) => {
+            let msg = cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None };
+            let resp: cw20_stake::msg::TotalStakedAtHeightResponse =
+                deps.querier.query_wasm_smart(contract_addr, &msg)?;
+            Ok(resp.total)
+        }
+    }
 }
 
 fn get_staked_balance(deps: Deps, contract_addr: &Addr, addr: &Addr) -> StdResult<Uint128> {
-    let msg = cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+    let msg = VotingQuery::VotingPowerAtHeight {
         address: addr.into(),
         height: None,
     };
-    let resp: cw20_stake::msg::StakedBalanceAtHeightResponse =
-        deps.querier.query_wasm_smart(contract_addr, &msg)?;
-    Ok(resp.balance)
+    match deps
+        .querier
+        .query_wasm_smart::<VotingPowerAtHeightResponse>(contract_addr, &msg)
+    {
+        Ok(resp) => Ok(resp.power),
+        Err(_) => {
+            let msg = cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+                address: addr.into(),
+                height: None,
+            };
+            let resp: cw20_stake::msg::StakedBalanceAtHeightResponse =
+                deps.querier.query_wasm_smart(contract_addr, &msg)?;
+            Ok(resp.balance)
+        }
+    }
 }
 
 pub fn execute_update_reward_duration(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    new_duration: u64,
+    reward_token: Option<Denom>,
+    new_duration: Duration,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if Some(info.sender.clone()) != config.owner && Some(info.sender) != config.manager {
         return Err(ContractError::Unauthorized {});
     };
 
-    let mut reward_config = REWARD_CONFIG.load(deps.storage)?;
-    if reward_config.period_finish > env.block.height {
+    let mut reward_config = load_reward_config(deps.storage, reward_token)?;
+    if reward_config.period_finish > reward_config.now(&env.block) {
         return Err(ContractError::RewardPeriodNotFinished {});
     };
 
-    if new_duration == 0 {
-        return Err(ContractError::ZeroRewardDuration {});
-    }
+    validate_reward_duration(&new_duration)?;
 
+    let key = denom_key(&reward_config.reward_token);
     let old_duration = reward_config.reward_duration;
+    if std::mem::discriminant(&old_duration) != std::mem::discriminant(&new_duration) {
+        // The stream is changing between distributing per block and
+        // per second. Finish distributing the last period in the old
+        // unit and then restart the stream's clock in the new one.
+        let total_staked = get_total_staked(deps.as_ref(), &config.staking_contract)?;
+        update_reward_per_token(&mut deps, &env, &reward_config, total_staked)?;
+        let now = now(&new_duration, &env.block);
+        reward_config.period_start = now;
+        reward_config.period_finish = now;
+        reward_config.reward_rate = Uint128::zero();
+        LAST_UPDATE.save(deps.storage, &key, &now)?;
+    }
     reward_config.reward_duration = new_duration;
-    REWARD_CONFIG.save(deps.storage, &reward_config)?;
+    REWARD_CONFIGS.save(deps.storage, &key, &reward_config)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_reward_duration")
+        .add_attribute("reward_token", key)
         .add_attribute("new_duration", new_duration.to_string())
         .add_attribute("old_duration", old_duration.to_string()))
 }
@@ -478,8 +897,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 pub fn query_info(deps: Deps, _env: Env) -> StdResult<InfoResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let reward = REWARD_CONFIG.load(deps.storage)?;
-    Ok(InfoResponse { config, reward })
+    let rewards = load_reward_configs(deps.storage)?;
+    Ok(InfoResponse { config, rewards })
 }
 
 pub fn query_pending_rewards(
@@ -489,24 +908,33 @@ pub fn query_pending_rewards(
 ) -> StdResult<PendingRewardsResponse> {
     let addr = deps.api.addr_validate(&addr)?;
     let config = CONFIG.load(deps.storage)?;
-    let reward_per_token = get_reward_per_token(deps, &env, &config.staking_contract)?;
-    let earned_rewards = get_rewards_earned(
-        deps,
-        &env,
-        &addr,
-        reward_per_token,
-        &config.staking_contract,
-    )?;
-
-    let existing_rewards = PENDING_REWARDS
-        .load(deps.storage, addr.clone())
-        .unwrap_or_default();
-    let pending_rewards = earned_rewards + existing_rewards;
+    let total_staked = get_total_staked(deps, &config.staking_contract)?;
+    let staked_balance = get_staked_balance(deps, &config.staking_contract, &addr)?;
+    let pending_rewards = load_reward_configs(deps.storage)?
+        .into_iter()
+        .map(|reward_config| {
+            let key = denom_key(&reward_config.reward_token);
+            let reward_per_token = get_reward_per_token(deps, &env, &reward_config, total_staked)?;
+            let earned_rewards = get_rewards_earned(
+                deps,
+                &addr,
+                &reward_config,
+                reward_per_token,
+                staked_balance,
+            )?;
+            let existing_rewards = load_pending_rewards(deps.storage, &key, &addr)?;
+            Ok(PendingReward {
+                amount: earned_rewards + existing_rewards,
+                last_update: LAST_UPDATE
+                    .may_load(deps.storage, &key)?
+                    .unwrap_or_default(),
+                denom: reward_config.reward_token,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     Ok(PendingRewardsResponse {
         address: addr.to_string(),
         pending_rewards,
-        denom: config.reward_token,
-        last_update_block: LAST_UPDATE_BLOCK.load(deps.storage).unwrap_or_default(),
     })
 }
 
@@ -517,16 +945,21 @@ mod tests {
     use crate::{
         contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
         msg::MigrateMsg,
+        state::{
+            denom_key, v1, Config, RewardConfig, CONFIG, LAST_UPDATE, PENDING_REWARDS,
+            REWARD_CONFIGS, REWARD_PER_TOKEN, USER_REWARD_PER_TOKEN, V1_STREAM,
+        },
         ContractError,
     };
 
     use cosmwasm_std::{
         coin,
         testing::{mock_dependencies, mock_env},
-        to_binary, Addr, Empty, Uint128,
+        to_binary, Addr, ContractResult, Empty, SystemResult, Uint128, Uint256,
     };
     use cw20::{Cw20Coin, Cw20ExecuteMsg, Denom};
-    use cw_utils::Duration;
+    use cw_core_interface::voting::TotalPowerAtHeightResponse;
+    use cw_utils::{Duration, Expiration};
 
     use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 
@@ -556,6 +989,33 @@ mod tests {
         Box::new(contract)
     }
 
+    pub fn contract_native_staking() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw_native_staked_balance_voting::contract::execute,
+            cw_native_staked_balance_voting::contract::instantiate,
+            cw_native_staked_balance_voting::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_nft_staking() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw721_stake::contract::execute,
+            cw721_stake::contract::instantiate,
+            cw721_stake::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    pub fn contract_cw721() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw721_base::entry::execute,
+            cw721_base::entry::instantiate,
+            cw721_base::entry::query,
+        );
+        Box::new(contract)
+    }
+
     pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
             cw20_base::contract::execute,
@@ -565,6 +1025,31 @@ mod tests {
         Box::new(contract)
     }
 
+    // Answers queries like versions of cw20-stake from before it
+    // answered voting module queries.
+    fn legacy_staking_query(
+        deps: cosmwasm_std::Deps,
+        env: cosmwasm_std::Env,
+        msg: cw20_stake::msg::QueryMsg,
+    ) -> cosmwasm_std::StdResult<cosmwasm_std::Binary> {
+        match msg {
+            cw20_stake::msg::QueryMsg::VotingPowerAtHeight { .. }
+            | cw20_stake::msg::QueryMsg::TotalPowerAtHeight { .. } => {
+                Err(cosmwasm_std::StdError::generic_err("unknown variant"))
+            }
+            msg => cw20_stake::contract::query(deps, env, msg),
+        }
+    }
+
+    pub fn contract_legacy_staking() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_stake::contract::execute,
+            cw20_stake::contract::instantiate,
+            legacy_staking_query,
+        );
+        Box::new(contract)
+    }
+
     fn mock_app() -> App {
         App::default()
     }
@@ -663,7 +1148,7 @@ mod tests {
             manager: Some(manager.into_string()),
             staking_contract: staking_contract.clone().into_string(),
             reward_token,
-            reward_duration: 100000,
+            reward_duration: Duration::Height(100000),
         };
         let reward_addr = app
             .instantiate_contract(reward_code_id, owner, &msg, &[], "reward", None)
@@ -709,7 +1194,27 @@ mod tests {
                 },
             )
             .unwrap();
-        assert_eq!(res.pending_rewards, Uint128::new(expected));
+        assert_eq!(res.pending_rewards[0].amount, Uint128::new(expected));
+    }
+
+    fn query_pending_rewards(
+        app: &mut App,
+        reward_addr: &Addr,
+        address: &str,
+    ) -> Vec<(Denom, Uint128)> {
+        let res: PendingRewardsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                reward_addr,
+                &QueryMsg::GetPendingRewards {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.pending_rewards
+            .into_iter()
+            .map(|reward| (reward.denom, reward.amount))
+            .collect()
     }
 
     fn claim_rewards(app: &mut App, reward_addr: Addr, address: &str) {
@@ -726,7 +1231,7 @@ mod tests {
         reward_addr: &Addr,
         amount: u128,
     ) {
-        let fund_sub_msg = to_binary(&ReceiveMsg::Fund {}).unwrap();
+        let fund_sub_msg = to_binary(&ReceiveMsg::Fund { start: None }).unwrap();
         let fund_msg = Cw20ExecuteMsg::Send {
             contract: reward_addr.clone().into_string(),
             amount: Uint128::new(amount),
@@ -738,6 +1243,80 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_legacy_staking_contract() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        let denom = "utest".to_string();
+        let cw20_addr = instantiate_cw20(
+            &mut app,
+            vec![
+                Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: ADDR2.to_string(),
+                    amount: Uint128::new(50),
+                },
+            ],
+        );
+        let staking_code_id = app.store_code(contract_legacy_staking());
+        let staking_addr = app
+            .instantiate_contract(
+                staking_code_id,
+                Addr::unchecked(ADDR1),
+                &cw20_stake::msg::InstantiateMsg {
+                    owner: Some(OWNER.to_string()),
+                    manager: None,
+                    token_address: cw20_addr.to_string(),
+                    unstaking_duration: None,
+                },
+                &[],
+                "staking",
+                None,
+            )
+            .unwrap();
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR1, 100);
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR2, 50);
+        app.update_block(next_block);
+
+        let reward_funding = vec![coin(100000000, denom.clone())];
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: admin.to_string(),
+            amount: reward_funding.clone(),
+        }))
+        .unwrap();
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Native(denom.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &reward_funding,
+        )
+        .unwrap();
+
+        // Staked balances are read from the staking contract's staked
+        // balance queries.
+        app.update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 666);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 333);
+
+        unstake_tokens(&mut app, &staking_addr, ADDR2, 50);
+        app.update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1666);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 333);
+
+        claim_rewards(&mut app, reward_addr, ADDR1);
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(1666));
+    }
+
     #[test]
     fn test_zero_rewards_duration() {
         let mut app = mock_app();
@@ -763,7 +1342,7 @@ mod tests {
             manager: Some(manager.into_string()),
             staking_contract: staking_addr.to_string(),
             reward_token,
-            reward_duration: 0,
+            reward_duration: Duration::Height(0),
         };
         let err: ContractError = app
             .instantiate_contract(reward_code_id, owner, &msg, &[], "reward", None)
@@ -812,7 +1391,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -830,9 +1409,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(1000));
-        assert_eq!(res.reward.period_finish, 101000);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_finish, 101000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         app.borrow_mut().update_block(next_block);
         assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500);
@@ -917,7 +1496,7 @@ mod tests {
         );
 
         app.borrow_mut().update_block(|b| b.height = 200000);
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         // Add more rewards
         let reward_funding = vec![coin(200000000, denom.clone())];
@@ -1011,60 +1590,338 @@ mod tests {
     }
 
     #[test]
-    fn test_cw20_rewards() {
+    fn test_native_staking_rewards() {
         let mut app = mock_app();
         let admin = Addr::unchecked(OWNER);
         app.borrow_mut().update_block(|b| b.height = 0);
-        let initial_balances = vec![
-            Cw20Coin {
-                address: ADDR1.to_string(),
-                amount: Uint128::new(100),
-            },
-            Cw20Coin {
-                address: ADDR2.to_string(),
-                amount: Uint128::new(50),
-            },
-            Cw20Coin {
-                address: ADDR3.to_string(),
-                amount: Uint128::new(50),
-            },
-        ];
+        let stake_denom = "ujuno".to_string();
         let denom = "utest".to_string();
-        let (staking_addr, cw20_addr) = setup_staking_contract(&mut app, initial_balances);
-        let reward_token = instantiate_cw20(
-            &mut app,
-            vec![Cw20Coin {
-                address: OWNER.to_string(),
-                amount: Uint128::new(500000000),
-            }],
-        );
-        let reward_addr = setup_reward_contract(
-            &mut app,
-            staking_addr.clone(),
-            Denom::Cw20(reward_token.clone()),
-            admin.clone(),
-            Addr::unchecked(MANAGER),
-        );
-
-        app.borrow_mut().update_block(|b| b.height = 1000);
-
-        fund_rewards_cw20(
-            &mut app,
-            &admin,
-            reward_token.clone(),
-            &reward_addr,
-            100000000,
-        );
-
-        let res: InfoResponse = app
-            .borrow_mut()
-            .wrap()
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(100, stake_denom.clone())],
+            }
+        }))
+        .unwrap();
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(100, stake_denom.clone())],
+            }
+        }))
+        .unwrap();
+        let reward_funding = vec![coin(100000000, denom.clone())];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+
+        let staking_code_id = app.store_code(contract_native_staking());
+        let staking_addr = app
+            .instantiate_contract(
+                staking_code_id,
+                Addr::unchecked(ADDR1),
+                &cw_native_staked_balance_voting::msg::InstantiateMsg {
+                    owner: Some(cw_native_staked_balance_voting::msg::Owner::Addr(
+                        OWNER.to_string(),
+                    )),
+                    manager: Some(MANAGER.to_string()),
+                    denom: stake_denom.clone(),
+                    unstaking_duration: None,
+                },
+                &[],
+                "staking",
+                None,
+            )
+            .unwrap();
+        let stake = cw_native_staked_balance_voting::msg::ExecuteMsg::Stake {};
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &stake,
+            &[coin(100, stake_denom.clone())],
+        )
+        .unwrap();
+        app.update_block(next_block);
+
+        // The native staking contract sends the same stake change
+        // hooks and answers the same staked balance queries as
+        // cw20-stake.
+        let reward_code_id = app.store_code(contract_rewards());
+        let reward_addr = app
+            .instantiate_contract(
+                reward_code_id,
+                admin.clone(),
+                &crate::msg::InstantiateMsg {
+                    owner: Some(OWNER.to_string()),
+                    manager: Some(MANAGER.to_string()),
+                    staking_contract: staking_addr.to_string(),
+                    reward_token: Denom::Native(denom.clone()),
+                    reward_duration: Duration::Height(100000),
+                },
+                &[],
+                "reward",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            staking_addr.clone(),
+            &cw_native_staked_balance_voting::msg::ExecuteMsg::AddHook {
+                addr: reward_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &reward_funding,
+        )
+        .unwrap();
+
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1000);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 0);
+
+        // ADDR2 stakes and begins earning half of the rewards.
+        app.execute_contract(
+            Addr::unchecked(ADDR2),
+            staking_addr.clone(),
+            &stake,
+            &[coin(100, stake_denom.clone())],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 500);
+
+        // ADDR1 unstakes and stops earning rewards.
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr,
+            &cw_native_staked_balance_voting::msg::ExecuteMsg::Unstake {
+                amount: Uint128::new(100),
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_balance_native(&app, ADDR1, &stake_denom),
+            Uint128::new(100)
+        );
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 1500);
+
+        claim_rewards(&mut app, reward_addr.clone(), ADDR1);
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(1500));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 0);
+
+        // Only cw20-stake supports restaking rewards.
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(ADDR2),
+                reward_addr.clone(),
+                &ExecuteMsg::ClaimAndRestake {},
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::RestakeUnsupported {});
+        claim_rewards(&mut app, reward_addr.clone(), ADDR2);
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(1500));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 0);
+    }
+
+    #[test]
+    fn test_nft_staking_rewards() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let denom = "utest".to_string();
+        let reward_funding = vec![coin(100000000, denom.clone())];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+
+        let cw721_id = app.store_code(contract_cw721());
+        let cw721_addr = app
+            .instantiate_contract(
+                cw721_id,
+                Addr::unchecked(ADDR1),
+                &cw721_base::msg::InstantiateMsg {
+                    name: "Test".to_string(),
+                    symbol: "Test".to_string(),
+                    minter: ADDR1.to_string(),
+                },
+                &[],
+                "cw721",
+                None,
+            )
+            .unwrap();
+        let staking_code_id = app.store_code(contract_nft_staking());
+        let staking_addr = app
+            .instantiate_contract(
+                staking_code_id,
+                Addr::unchecked(ADDR1),
+                &cw721_stake::msg::InstantiateMsg {
+                    owner: Some(cw721_stake::msg::Owner::Addr(OWNER.to_string())),
+                    manager: Some(MANAGER.to_string()),
+                    nft_address: cw721_addr.to_string(),
+                    unstaking_duration: None,
+                },
+                &[],
+                "staking",
+                None,
+            )
+            .unwrap();
+
+        // ADDR1 and ADDR2 each own two NFTs.
+        for (token_id, owner) in [("1", ADDR1), ("2", ADDR1), ("3", ADDR2), ("4", ADDR2)] {
+            app.execute_contract(
+                Addr::unchecked(ADDR1),
+                cw721_addr.clone(),
+                &cw721_base::msg::ExecuteMsg::Mint(cw721_base::msg::MintMsg::<Option<Empty>> {
+                    token_id: token_id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: None,
+                    extension: None,
+                }),
+                &[],
+            )
+            .unwrap();
+        }
+        let stake_nft = |app: &mut App, sender: &str, token_id: &str| {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                cw721_addr.clone(),
+                &cw721::Cw721ExecuteMsg::SendNft {
+                    contract: staking_addr.to_string(),
+                    token_id: token_id.to_string(),
+                    msg: to_binary("").unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+        };
+        stake_nft(&mut app, ADDR1, "1");
+        stake_nft(&mut app, ADDR1, "2");
+        app.update_block(next_block);
+
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Native(denom.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &reward_funding,
+        )
+        .unwrap();
+
+        // Each staked NFT counts as one unit of stake.
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1000);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 0);
+
+        stake_nft(&mut app, ADDR2, "3");
+        stake_nft(&mut app, ADDR2, "4");
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 500);
+
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr,
+            &cw721_stake::msg::ExecuteMsg::Unstake {
+                token_ids: vec!["1".to_string(), "2".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 1500);
+
+        claim_rewards(&mut app, reward_addr.clone(), ADDR1);
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(1500));
+        claim_rewards(&mut app, reward_addr, ADDR2);
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(1500));
+    }
+
+    #[test]
+    fn test_cw20_rewards() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let initial_balances = vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: ADDR3.to_string(),
+                amount: Uint128::new(50),
+            },
+        ];
+        let denom = "utest".to_string();
+        let (staking_addr, cw20_addr) = setup_staking_contract(&mut app, initial_balances);
+        let reward_token = instantiate_cw20(
+            &mut app,
+            vec![Cw20Coin {
+                address: OWNER.to_string(),
+                amount: Uint128::new(500000000),
+            }],
+        );
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Cw20(reward_token.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+
+        fund_rewards_cw20(
+            &mut app,
+            &admin,
+            reward_token.clone(),
+            &reward_addr,
+            100000000,
+        );
+
+        let res: InfoResponse = app
+            .borrow_mut()
+            .wrap()
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(1000));
-        assert_eq!(res.reward.period_finish, 101000);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_finish, 101000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         app.borrow_mut().update_block(next_block);
         assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500);
@@ -1241,6 +2098,108 @@ mod tests {
         stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR3, 1);
     }
 
+    #[test]
+    fn test_claim_and_restake() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let denom = "utest".to_string();
+        let cw20_addr = instantiate_cw20(
+            &mut app,
+            vec![
+                Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: ADDR2.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: OWNER.to_string(),
+                    amount: Uint128::new(100000000),
+                },
+            ],
+        );
+        app.update_block(next_block);
+        let staking_addr = instantiate_staking(&mut app, cw20_addr.clone(), None);
+        app.update_block(next_block);
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR1, 100);
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR2, 100);
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: vec![coin(100000000, denom.clone())],
+            }
+        }))
+        .unwrap();
+
+        // Distribute the staked token and a native token.
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Cw20(cw20_addr.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::AddRewardStream {
+                reward_token: Denom::Native(denom.clone()),
+                reward_duration: Duration::Height(100000),
+            },
+            &[],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(|b| b.height = 1000);
+        fund_rewards_cw20(&mut app, &admin, cw20_addr.clone(), &reward_addr, 100000000);
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(100000000, denom.clone())],
+        )
+        .unwrap();
+
+        app.borrow_mut().update_block(next_block);
+        assert_eq!(
+            query_pending_rewards(&mut app, &reward_addr, ADDR1),
+            vec![
+                (Denom::Cw20(cw20_addr.clone()), Uint128::new(500)),
+                (Denom::Native(denom.clone()), Uint128::new(500)),
+            ]
+        );
+
+        // Rewards in the staked token are staked for ADDR1 and the
+        // rest are sent to them.
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            reward_addr.clone(),
+            &ExecuteMsg::ClaimAndRestake {},
+            &[],
+        )
+        .unwrap();
+        assert_eq!(get_balance_cw20(&app, &cw20_addr, ADDR1), Uint128::zero());
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(500));
+        app.borrow_mut().update_block(next_block);
+        let res: cw20_stake::msg::StakedBalanceAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &staking_addr,
+                &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+                    address: ADDR1.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.balance, Uint128::new(600));
+
+        // The restaked rewards earn rewards.
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 857);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 642);
+    }
+
     #[test]
     fn update_rewards() {
         let mut app = mock_app();
@@ -1288,7 +2247,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         // None admin cannot update rewards
         let err: ContractError = app
@@ -1321,70 +2280,391 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(2000));
-        assert_eq!(res.reward.period_finish, 101000);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(2000));
+        assert_eq!(res.rewards[0].period_finish, 101000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         // Create new period after old period
         app.borrow_mut().update_block(|b| b.height = 101000);
 
-        let reward_funding = vec![coin(100000000, denom.clone())];
+        let reward_funding = vec![coin(100000000, denom.clone())];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+        let _res = app
+            .borrow_mut()
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &fund_msg,
+                &reward_funding,
+            )
+            .unwrap();
+
+        let res: InfoResponse = app
+            .borrow_mut()
+            .wrap()
+            .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
+            .unwrap();
+
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_finish, 201000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
+
+        // Add funds in middle of period tops up the remainder of
+        // the period without restarting it
+        app.borrow_mut().update_block(|b| b.height = 151000);
+
+        let reward_funding = vec![coin(200000000, denom.clone())];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+        let _res = app
+            .borrow_mut()
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &fund_msg,
+                &reward_funding,
+            )
+            .unwrap();
+
+        let res: InfoResponse = app
+            .borrow_mut()
+            .wrap()
+            .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
+            .unwrap();
+
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(5000));
+        assert_eq!(res.rewards[0].period_start, 101000);
+        assert_eq!(res.rewards[0].period_finish, 201000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
+
+        // A start may not be provided for a period that is running
+        let reward_funding = vec![coin(100000000, denom)];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+        let err = app
+            .borrow_mut()
+            .execute_contract(
+                admin,
+                reward_addr,
+                &ExecuteMsg::Fund {
+                    start: Some(Expiration::AtHeight(300000)),
+                },
+                &reward_funding,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::RewardPeriodNotFinished {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scheduled_rewards() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let initial_balances = vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(100),
+            },
+        ];
+        let denom = "utest".to_string();
+        let (staking_addr, _) = setup_staking_contract(&mut app, initial_balances);
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: vec![coin(299999000, denom.clone())],
+            }
+        }))
+        .unwrap();
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr,
+            Denom::Native(denom.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+
+        // Periods may not start in the past.
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::Fund {
+                    start: Some(Expiration::AtHeight(999)),
+                },
+                &[coin(100000000, denom.clone())],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidStart {});
+
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund {
+                start: Some(Expiration::AtHeight(2000)),
+            },
+            &[coin(100000000, denom.clone())],
+        )
+        .unwrap();
+        let res: InfoResponse = app
+            .wrap()
+            .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
+            .unwrap();
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_start, 2000);
+        assert_eq!(res.rewards[0].period_finish, 102000);
+
+        // Nothing is distributed before the period starts.
+        app.borrow_mut().update_block(|b| b.height = 2000);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 0);
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500);
+
+        // Topping up increases the rate for the rest of the period.
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(99999000, denom.clone())],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 1500);
+
+        // Only the owner may cancel rewards.
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(MANAGER),
+                reward_addr.clone(),
+                &ExecuteMsg::CancelRewards { reward_token: None },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Cancelling returns what has not been distributed and leaves
+        // what has been claimable.
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::CancelRewards { reward_token: None },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_balance_native(&app, OWNER, &denom),
+            Uint128::new(100000000 + 2000 * 99998)
+        );
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1500);
+        claim_rewards(&mut app, reward_addr.clone(), ADDR1);
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(1500));
+
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::CancelRewards { reward_token: None },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::RewardPeriodFinished {});
+
+        // Scheduled periods may be cancelled before they start.
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund {
+                start: Some(Expiration::AtHeight(3000)),
+            },
+            &[coin(100000000, denom.clone())],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(|b| b.height = 2500);
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::CancelRewards { reward_token: None },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            get_balance_native(&app, OWNER, &denom),
+            Uint128::new(100000000 + 2000 * 99998)
+        );
+        app.borrow_mut().update_block(|b| b.height = 4000);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 0);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 1500);
+    }
+
+    #[test]
+    fn test_time_based_rewards() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let initial_balances = vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(100),
+            },
+        ];
+        let denom = "utest".to_string();
+        let (staking_addr, _) = setup_staking_contract(&mut app, initial_balances);
         app.sudo(SudoMsg::Bank({
             BankSudo::Mint {
                 to_address: admin.to_string(),
-                amount: reward_funding.clone(),
+                amount: vec![coin(3000000, denom.clone())],
             }
         }))
         .unwrap();
-        let _res = app
-            .borrow_mut()
-            .execute_contract(
+
+        let reward_code_id = app.store_code(contract_rewards());
+        let reward_addr = app
+            .instantiate_contract(
+                reward_code_id,
                 admin.clone(),
-                reward_addr.clone(),
-                &fund_msg,
-                &reward_funding,
+                &crate::msg::InstantiateMsg {
+                    owner: Some(OWNER.to_string()),
+                    manager: Some(MANAGER.to_string()),
+                    staking_contract: staking_addr.to_string(),
+                    reward_token: Denom::Native(denom.clone()),
+                    reward_duration: Duration::Time(1000),
+                },
+                &[],
+                "reward",
+                None,
             )
             .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            staking_addr,
+            &cw20_stake::msg::ExecuteMsg::AddHook {
+                addr: reward_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(next_block);
 
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(1000000, denom.clone())],
+        )
+        .unwrap();
+        let start = app.block_info().time.seconds();
         let res: InfoResponse = app
-            .borrow_mut()
             .wrap()
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_start, start);
+        assert_eq!(res.rewards[0].period_finish, start + 1000);
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(1000));
-        assert_eq!(res.reward.period_finish, 201000);
-        assert_eq!(res.reward.reward_duration, 100000);
-
-        // Add funds in middle of period returns an error
-        app.borrow_mut().update_block(|b| b.height = 151000);
+        // Rewards accrue with time, not blocks.
+        app.update_block(|b| b.time = b.time.plus_seconds(10));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 5000);
+        app.update_block(|b| b.height += 100);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 5000);
 
-        let reward_funding = vec![coin(200000000, denom)];
-        app.sudo(SudoMsg::Bank({
-            BankSudo::Mint {
-                to_address: admin.to_string(),
-                amount: reward_funding.clone(),
-            }
-        }))
+        // Once the period finishes a new one may be scheduled, but
+        // only at a time.
+        app.update_block(|b| b.time = b.time.plus_seconds(1000));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500000);
+        let height = app.block_info().height;
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::Fund {
+                    start: Some(Expiration::AtHeight(height + 10)),
+                },
+                &[coin(1000000, denom.clone())],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidStart {});
+        let scheduled = app.block_info().time.plus_seconds(100);
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund {
+                start: Some(Expiration::AtTime(scheduled)),
+            },
+            &[coin(1000000, denom.clone())],
+        )
         .unwrap();
-        let err = app
-            .borrow_mut()
-            .execute_contract(admin, reward_addr.clone(), &fund_msg, &reward_funding)
-            .unwrap_err();
+        app.update_block(|b| b.time = b.time.plus_seconds(100));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500000);
+        app.update_block(|b| b.time = b.time.plus_seconds(1000));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1000000);
+
+        // Switching the stream to heights keeps what has been earned
+        // and distributes future periods per block.
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::UpdateRewardDuration {
+                reward_token: None,
+                new_duration: Duration::Height(100),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(1000000, denom.clone())],
+        )
+        .unwrap();
+        app.update_block(|b| b.time = b.time.plus_seconds(1000));
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1000000);
+        app.update_block(|b| b.height += 10);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 1050000);
+
+        claim_rewards(&mut app, reward_addr, ADDR2);
         assert_eq!(
-            ContractError::RewardPeriodNotFinished {},
-            err.downcast().unwrap()
+            get_balance_native(&app, ADDR2, &denom),
+            Uint128::new(1050000)
         );
-
-        let res: InfoResponse = app
-            .borrow_mut()
-            .wrap()
-            .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
-            .unwrap();
-
-        assert_eq!(res.reward.reward_rate, Uint128::new(1000));
-        assert_eq!(res.reward.period_finish, 201000);
-        assert_eq!(res.reward.reward_duration, 100000);
     }
 
     #[test]
@@ -1423,12 +2703,15 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(0));
-        assert_eq!(res.reward.period_finish, 0);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(0));
+        assert_eq!(res.rewards[0].period_finish, 0);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         // Zero rewards durations are not allowed.
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 0 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(0),
+        };
         let err: ContractError = app
             .borrow_mut()
             .execute_contract(admin.clone(), reward_addr.clone(), &msg, &[])
@@ -1437,7 +2720,10 @@ mod tests {
             .unwrap();
         assert_eq!(err, ContractError::ZeroRewardDuration {});
 
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 10 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(10),
+        };
         let _resp = app
             .borrow_mut()
             .execute_contract(admin.clone(), reward_addr.clone(), &msg, &[])
@@ -1449,12 +2735,15 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(0));
-        assert_eq!(res.reward.period_finish, 0);
-        assert_eq!(res.reward.reward_duration, 10);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(0));
+        assert_eq!(res.rewards[0].period_finish, 0);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(10));
 
         // Non-admin cannot update rewards
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 100 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(100),
+        };
         let err: ContractError = app
             .borrow_mut()
             .execute_contract(Addr::unchecked("non-admin"), reward_addr.clone(), &msg, &[])
@@ -1482,7 +2771,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -1500,12 +2789,15 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(100));
-        assert_eq!(res.reward.period_finish, 1010);
-        assert_eq!(res.reward.reward_duration, 10);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(100));
+        assert_eq!(res.rewards[0].period_finish, 1010);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(10));
 
         // Cannot update reward period before it finishes
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 10 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(10),
+        };
         let err: ContractError = app
             .borrow_mut()
             .execute_contract(admin.clone(), reward_addr.clone(), &msg, &[])
@@ -1517,7 +2809,10 @@ mod tests {
         // Update reward period once rewards are finished
         app.borrow_mut().update_block(|b| b.height = 1010);
 
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 100 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(100),
+        };
         let _resp = app
             .borrow_mut()
             .execute_contract(admin, reward_addr.clone(), &msg, &[])
@@ -1529,9 +2824,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(100));
-        assert_eq!(res.reward.period_finish, 1010);
-        assert_eq!(res.reward.reward_duration, 100);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(100));
+        assert_eq!(res.rewards[0].period_finish, 1010);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100));
     }
 
     #[test]
@@ -1765,7 +3060,10 @@ mod tests {
         );
 
         // Manager can update reward duration
-        let msg = ExecuteMsg::UpdateRewardDuration { new_duration: 10 };
+        let msg = ExecuteMsg::UpdateRewardDuration {
+            reward_token: None,
+            new_duration: Duration::Height(10),
+        };
         let _resp = app
             .borrow_mut()
             .execute_contract(manager.clone(), reward_addr.clone(), &msg, &[])
@@ -1777,9 +3075,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(0));
-        assert_eq!(res.reward.period_finish, 0);
-        assert_eq!(res.reward.reward_duration, 10);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(0));
+        assert_eq!(res.rewards[0].period_finish, 0);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(10));
 
         // Manager can fund contract
 
@@ -1794,7 +3092,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -1807,9 +3105,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(10));
-        assert_eq!(res.reward.period_finish, 1010);
-        assert_eq!(res.reward.reward_duration, 10);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(10));
+        assert_eq!(res.rewards[0].period_finish, 1010);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(10));
     }
 
     #[test]
@@ -1846,7 +3144,7 @@ mod tests {
         app.borrow_mut().update_block(|b| b.height = 1000);
 
         // No funding
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let err: ContractError = app
             .borrow_mut()
@@ -1866,7 +3164,7 @@ mod tests {
         }))
         .unwrap();
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let err: ContractError = app
             .borrow_mut()
@@ -1891,7 +3189,7 @@ mod tests {
         }))
         .unwrap();
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let err: ContractError = app
             .borrow_mut()
@@ -1914,7 +3212,7 @@ mod tests {
                 amount: Uint128::new(500000000),
             }],
         );
-        let fund_sub_msg = to_binary(&ReceiveMsg::Fund {}).unwrap();
+        let fund_sub_msg = to_binary(&ReceiveMsg::Fund { start: None }).unwrap();
         let fund_msg = Cw20ExecuteMsg::Send {
             contract: reward_addr.into_string(),
             amount: Uint128::new(100),
@@ -1968,7 +3266,7 @@ mod tests {
         app.borrow_mut().update_block(|b| b.height = 1000);
 
         // Test with invalid token
-        let fund_sub_msg = to_binary(&ReceiveMsg::Fund {}).unwrap();
+        let fund_sub_msg = to_binary(&ReceiveMsg::Fund { start: None }).unwrap();
         let fund_msg = Cw20ExecuteMsg::Send {
             contract: reward_addr.clone().into_string(),
             amount: Uint128::new(100),
@@ -1992,7 +3290,7 @@ mod tests {
         }))
         .unwrap();
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let err: ContractError = app
             .borrow_mut()
@@ -2047,7 +3345,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -2060,9 +3358,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(1000));
-        assert_eq!(res.reward.period_finish, 101000);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(1000));
+        assert_eq!(res.rewards[0].period_finish, 101000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         app.borrow_mut().update_block(next_block);
         assert_pending_rewards(&mut app, &reward_addr, ADDR1, 0);
@@ -2131,7 +3429,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -2144,9 +3442,9 @@ mod tests {
             .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
             .unwrap();
 
-        assert_eq!(res.reward.reward_rate, Uint128::new(10));
-        assert_eq!(res.reward.period_finish, 101000);
-        assert_eq!(res.reward.reward_duration, 100000);
+        assert_eq!(res.rewards[0].reward_rate, Uint128::new(10));
+        assert_eq!(res.rewards[0].period_finish, 101000);
+        assert_eq!(res.rewards[0].reward_duration, Duration::Height(100000));
 
         app.borrow_mut().update_block(next_block);
         assert_pending_rewards(&mut app, &reward_addr, ADDR1, 5);
@@ -2195,7 +3493,7 @@ mod tests {
 
         app.borrow_mut().update_block(|b| b.height = 1000);
 
-        let fund_msg = ExecuteMsg::Fund {};
+        let fund_msg = ExecuteMsg::Fund { start: None };
 
         let _res = app
             .borrow_mut()
@@ -2203,6 +3501,316 @@ mod tests {
             .unwrap_err();
     }
 
+    #[test]
+    fn test_multiple_reward_streams() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let initial_balances = vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: ADDR3.to_string(),
+                amount: Uint128::new(50),
+            },
+        ];
+        let (staking_addr, _) = setup_staking_contract(&mut app, initial_balances);
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: vec![coin(100000000, "utest"), coin(50000000, "uother")],
+            }
+        }))
+        .unwrap();
+        let cw20_token = instantiate_cw20(
+            &mut app,
+            vec![Cw20Coin {
+                address: OWNER.to_string(),
+                amount: Uint128::new(100000000),
+            }],
+        );
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr,
+            Denom::Native("utest".to_string()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+
+        // Only the owner and manager may add reward streams.
+        let add_other = ExecuteMsg::AddRewardStream {
+            reward_token: Denom::Native("uother".to_string()),
+            reward_duration: Duration::Height(100000),
+        };
+        let err: ContractError = app
+            .execute_contract(Addr::unchecked(ADDR1), reward_addr.clone(), &add_other, &[])
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Unauthorized {});
+        app.execute_contract(admin.clone(), reward_addr.clone(), &add_other, &[])
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(MANAGER),
+            reward_addr.clone(),
+            &ExecuteMsg::AddRewardStream {
+                reward_token: Denom::Cw20(cw20_token.clone()),
+                reward_duration: Duration::Height(50000),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::AddRewardStream {
+                    reward_token: Denom::Native("utest".to_string()),
+                    reward_duration: Duration::Height(10),
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::DuplicateRewardStream {});
+
+        // With more than one stream the stream to update must be
+        // specified.
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::UpdateRewardDuration {
+                    reward_token: None,
+                    new_duration: Duration::Height(10),
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::RewardTokenRequired {});
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+
+        // Native streams are funded one at a time.
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &ExecuteMsg::Fund { start: None },
+                &[coin(100, "utest"), coin(100, "uother")],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidFunds {});
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(100000000, "utest")],
+        )
+        .unwrap();
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund { start: None },
+            &[coin(50000000, "uother")],
+        )
+        .unwrap();
+        fund_rewards_cw20(
+            &mut app,
+            &admin,
+            cw20_token.clone(),
+            &reward_addr,
+            100000000,
+        );
+
+        let res: InfoResponse = app
+            .wrap()
+            .query_wasm_smart(&reward_addr, &QueryMsg::Info {})
+            .unwrap();
+        let rates: Vec<(Denom, Uint128, u64)> = res
+            .rewards
+            .into_iter()
+            .map(|r| (r.reward_token, r.reward_rate, r.period_finish))
+            .collect();
+        assert_eq!(
+            rates,
+            vec![
+                (Denom::Cw20(cw20_token.clone()), Uint128::new(2000), 51000),
+                (
+                    Denom::Native("uother".to_string()),
+                    Uint128::new(500),
+                    101000
+                ),
+                (
+                    Denom::Native("utest".to_string()),
+                    Uint128::new(1000),
+                    101000
+                ),
+            ]
+        );
+
+        app.borrow_mut().update_block(next_block);
+        assert_eq!(
+            query_pending_rewards(&mut app, &reward_addr, ADDR1),
+            vec![
+                (Denom::Cw20(cw20_token.clone()), Uint128::new(1000)),
+                (Denom::Native("uother".to_string()), Uint128::new(250)),
+                (Denom::Native("utest".to_string()), Uint128::new(500)),
+            ]
+        );
+        assert_eq!(
+            query_pending_rewards(&mut app, &reward_addr, ADDR2),
+            vec![
+                (Denom::Cw20(cw20_token.clone()), Uint128::new(500)),
+                (Denom::Native("uother".to_string()), Uint128::new(125)),
+                (Denom::Native("utest".to_string()), Uint128::new(250)),
+            ]
+        );
+
+        // Claiming pays out every stream.
+        claim_rewards(&mut app, reward_addr.clone(), ADDR1);
+        assert_eq!(get_balance_native(&app, ADDR1, "utest"), Uint128::new(500));
+        assert_eq!(get_balance_native(&app, ADDR1, "uother"), Uint128::new(250));
+        assert_eq!(
+            get_balance_cw20(&app, &cw20_token, ADDR1),
+            Uint128::new(1000)
+        );
+        assert!(query_pending_rewards(&mut app, &reward_addr, ADDR1)
+            .into_iter()
+            .all(|(_, amount)| amount.is_zero()));
+
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                reward_addr,
+                &ExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::NoRewardsClaimable {});
+    }
+
+    #[test]
+    fn test_migrate_single_reward_token() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+        let addr = Addr::unchecked(ADDR1);
+        v1::CONFIG
+            .save(
+                &mut deps.storage,
+                &v1::Config {
+                    owner: Some(Addr::unchecked(OWNER)),
+                    manager: None,
+                    staking_contract: Addr::unchecked("staking"),
+                    reward_token: Denom::Native("utest".to_string()),
+                },
+            )
+            .unwrap();
+        v1::REWARD_CONFIG
+            .save(
+                &mut deps.storage,
+                &v1::RewardConfig {
+                    period_finish: 1000,
+                    reward_rate: Uint128::new(10),
+                    reward_duration: 100,
+                },
+            )
+            .unwrap();
+        v1::REWARD_PER_TOKEN
+            .save(&mut deps.storage, &Uint256::from(5u64))
+            .unwrap();
+        v1::LAST_UPDATE_BLOCK.save(&mut deps.storage, &900).unwrap();
+        v1::PENDING_REWARDS
+            .save(&mut deps.storage, addr.clone(), &Uint128::new(20))
+            .unwrap();
+        v1::USER_REWARD_PER_TOKEN
+            .save(&mut deps.storage, addr.clone(), &Uint256::from(3u64))
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let key = denom_key(&Denom::Native("utest".to_string()));
+        assert_eq!(V1_STREAM.load(&deps.storage).unwrap(), key);
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap(),
+            Config {
+                owner: Some(Addr::unchecked(OWNER)),
+                manager: None,
+                staking_contract: Addr::unchecked("staking"),
+            }
+        );
+        assert_eq!(
+            REWARD_CONFIGS.load(&deps.storage, &key).unwrap(),
+            RewardConfig {
+                reward_token: Denom::Native("utest".to_string()),
+                period_start: 900,
+                period_finish: 1000,
+                reward_rate: Uint128::new(10),
+                reward_duration: Duration::Height(100),
+            }
+        );
+        assert_eq!(
+            REWARD_PER_TOKEN.load(&deps.storage, &key).unwrap(),
+            Uint256::from(5u64)
+        );
+        assert_eq!(LAST_UPDATE.load(&deps.storage, &key).unwrap(), 900);
+        assert!(v1::REWARD_CONFIG.may_load(&deps.storage).unwrap().is_none());
+
+        // Per-user state is read from the v1 maps until the user's
+        // rewards are updated.
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::zero(),
+                    height: 0,
+                })
+                .unwrap(),
+            ))
+        });
+        let pending =
+            crate::contract::query_pending_rewards(deps.as_ref(), mock_env(), ADDR1.to_string())
+                .unwrap();
+        assert_eq!(pending.pending_rewards[0].amount, Uint128::new(20));
+        assert!(PENDING_REWARDS
+            .may_load(&deps.storage, (&key, &addr))
+            .unwrap()
+            .is_none());
+
+        crate::contract::update_rewards(&mut deps.as_mut(), &mock_env(), &addr).unwrap();
+        assert_eq!(
+            PENDING_REWARDS.load(&deps.storage, (&key, &addr)).unwrap(),
+            Uint128::new(20)
+        );
+        assert_eq!(
+            USER_REWARD_PER_TOKEN
+                .load(&deps.storage, (&key, &addr))
+                .unwrap(),
+            Uint256::from(5u64)
+        );
+        assert!(v1::PENDING_REWARDS
+            .may_load(&deps.storage, addr.clone())
+            .unwrap()
+            .is_none());
+        assert!(v1::USER_REWARD_PER_TOKEN
+            .may_load(&deps.storage, addr)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     pub fn test_migrate_update_version() {
         let mut deps = mock_dependencies();
@@ -13,12 +13,26 @@ pub enum ContractError {
     NoRewardsClaimable {},
     #[error("Reward period not finished")]
     RewardPeriodNotFinished {},
+    #[error("Reward period already finished")]
+    RewardPeriodFinished {},
+    #[error("Reward period must start in the future and in the unit of the reward duration")]
+    InvalidStart {},
     #[error("Invalid funds")]
     InvalidFunds {},
     #[error("Invalid Cw20")]
     InvalidCw20 {},
-    #[error("Reward rate less then one per block")]
+    #[error("Reward rate less then one per block or second")]
     RewardRateLessThenOnePerBlock {},
     #[error("Reward duration can not be zero")]
     ZeroRewardDuration {},
+    #[error("A reward stream already exists for this token")]
+    DuplicateRewardStream {},
+    #[error("No reward stream exists for this token")]
+    UnknownRewardStream {},
+    #[error("Reward token must be specified when there is more than one reward stream")]
+    RewardTokenRequired {},
+    #[error("Staking contract does not support restaking rewards")]
+    RestakeUnsupported {},
+    #[error("Can not have more than {max} reward streams")]
+    TooManyRewardStreams { max: u32 },
 }
@@ -1,6 +1,6 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Addr, Uint128};
 use cw20::{Cw20ReceiveMsg, Denom};
-use cw20_stake::hooks::StakeChangedHookMsg;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -13,28 +13,88 @@ pub struct InstantiateMsg {
     pub manager: Option<String>,
     pub staking_contract: String,
     pub reward_token: Denom,
-    pub reward_duration: u64,
+    /// The length of the reward stream's periods. A height based
+    /// duration distributes rewards per block and a time based one
+    /// per second.
+    pub reward_duration: Duration,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub struct MigrateMsg {}
 
+/// A stake change hook sent by the staking contract. Only the
+/// address whose stake changed is read, so the hooks of any staking
+/// contract are accepted. Additional fields, such as the `amount`
+/// sent by cw20-stake or the `token_id` sent by cw721-stake, are
+/// ignored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeChangedHookMsg {
+    Stake { addr: Addr },
+    Unstake { addr: Addr },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     StakeChangeHook(StakeChangedHookMsg),
     Claim {},
+    /// Claims the sender's rewards like `Claim`, but restakes the
+    /// rewards of any stream distributing the staked token on the
+    /// sender's behalf instead of sending them. Only supported when
+    /// the staking contract is cw20-stake.
+    ClaimAndRestake {},
     Receive(Cw20ReceiveMsg),
-    Fund {},
-    UpdateRewardDuration { new_duration: u64 },
-    UpdateOwner { new_owner: Option<String> },
-    UpdateManager { new_manager: Option<String> },
+    /// Funds the reward stream for the sent native token. See
+    /// `ReceiveMsg::Fund` for how funds are distributed.
+    Fund {
+        #[serde(default)]
+        start: Option<Expiration>,
+    },
+    /// Adds a new reward stream distributing REWARD_TOKEN. Streams
+    /// are funded and claimed independently of each other.
+    AddRewardStream {
+        reward_token: Denom,
+        reward_duration: Duration,
+    },
+    /// Updates the reward duration of the stream distributing
+    /// REWARD_TOKEN. May be omitted if the contract has only one
+    /// reward stream. Switching between height and time based
+    /// durations changes the unit the stream distributes rewards in.
+    UpdateRewardDuration {
+        #[serde(default)]
+        reward_token: Option<Denom>,
+        new_duration: Duration,
+    },
+    /// Stops the current reward period of the stream distributing
+    /// REWARD_TOKEN and returns the rewards that have not yet been
+    /// distributed to the owner. Rewards already earned by stakers
+    /// remain claimable. May only be called by the owner.
+    CancelRewards {
+        #[serde(default)]
+        reward_token: Option<Denom>,
+    },
+    UpdateOwner {
+        new_owner: Option<String>,
+    },
+    UpdateManager {
+        new_manager: Option<String>,
+    },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReceiveMsg {
-    Fund {},
+    /// Funds the reward stream for the sent token. If the stream's
+    /// reward period has finished a new one is started at START, or
+    /// now if not provided, and lasts for the stream's reward
+    /// duration. START must be a height for height based streams and
+    /// a time for time based ones. Otherwise, the funds are added to the
+    /// distribution over the remainder of the current period.
+    Fund {
+        #[serde(default)]
+        start: Option<Expiration>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -47,13 +107,20 @@ pub enum QueryMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InfoResponse {
     pub config: Config,
-    pub reward: RewardConfig,
+    pub rewards: Vec<RewardConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PendingRewardsResponse {
     pub address: String,
-    pub pending_rewards: Uint128,
+    /// The pending rewards of each reward stream.
+    pub pending_rewards: Vec<PendingReward>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingReward {
     pub denom: Denom,
-    pub last_update_block: u64,
+    pub amount: Uint128,
+    /// The block or time in seconds rewards were last distributed at.
+    pub last_update: u64,
 }
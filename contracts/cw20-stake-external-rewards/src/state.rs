@@ -1,8 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128, Uint256};
+use cosmwasm_std::{Addr, BlockInfo, Uint128, Uint256};
 use cw20::Denom;
+use cw_utils::Duration;
 
 use cw_storage_plus::{Item, Map};
 
@@ -11,22 +12,109 @@ pub struct Config {
     pub owner: Option<Addr>,
     pub manager: Option<Addr>,
     pub staking_contract: Addr,
-    pub reward_token: Denom,
 }
 pub const CONFIG: Item<Config> = Item::new("config");
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+/// The configuration of a single reward stream. Each stream
+/// distributes one token and is funded independently.
+///
+/// Streams with a height based reward duration distribute rewards
+/// per block, and those with a time based duration per second. The
+/// stream's period start, period finish and last update are blocks or
+/// seconds accordingly.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct RewardConfig {
+    pub reward_token: Denom,
+    /// The point the current reward period starts distributing
+    /// rewards at. May be in the future for scheduled periods.
+    #[serde(default)]
+    pub period_start: u64,
     pub period_finish: u64,
     pub reward_rate: Uint128,
-    pub reward_duration: u64,
+    pub reward_duration: Duration,
+}
+
+impl RewardConfig {
+    /// Gets the current block height or time in seconds, whichever
+    /// this stream accrues rewards in.
+    pub fn now(&self, block: &BlockInfo) -> u64 {
+        now(&self.reward_duration, block)
+    }
+
+    /// Gets the length of a reward period in blocks or seconds.
+    pub fn period_length(&self) -> u64 {
+        match self.reward_duration {
+            Duration::Height(blocks) => blocks,
+            Duration::Time(seconds) => seconds,
+        }
+    }
+}
+
+/// Gets the current block height or time in seconds, whichever
+/// DURATION is measured in.
+pub fn now(duration: &Duration, block: &BlockInfo) -> u64 {
+    match duration {
+        Duration::Height(_) => block.height,
+        Duration::Time(_) => block.time.seconds(),
+    }
+}
+
+// All of the maps below are keyed by the stream's reward token, as
+// returned by `denom_key`.
+pub const REWARD_CONFIGS: Map<&str, RewardConfig> = Map::new("reward_configs");
+
+pub const REWARD_PER_TOKEN: Map<&str, Uint256> = Map::new("stream_reward_per_token");
+
+pub const LAST_UPDATE: Map<&str, u64> = Map::new("stream_last_update");
+
+pub const PENDING_REWARDS: Map<(&str, &Addr), Uint128> = Map::new("stream_pending_rewards");
+
+pub const USER_REWARD_PER_TOKEN: Map<(&str, &Addr), Uint256> =
+    Map::new("stream_user_reward_per_token");
+
+/// The key of the reward stream migrated from a version of this
+/// contract that supported a single reward token. Users' pending
+/// rewards and reward per token for that stream are read from the v1
+/// maps until their rewards are next updated, which moves them.
+pub const V1_STREAM: Item<String> = Item::new("v1_stream");
+
+/// Gets the key that the reward stream for DENOM is stored under.
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(addr) => format!("cw20:{}", addr),
+    }
 }
-pub const REWARD_CONFIG: Item<RewardConfig> = Item::new("reward_config");
 
-pub const REWARD_PER_TOKEN: Item<Uint256> = Item::new("reward_per_token");
+/// Storage used by versions of this contract that supported a single
+/// reward token. Per-user entries are read for the stream stored
+/// under `V1_STREAM` until they are moved; everything else is only
+/// read when migrating.
+pub mod v1 {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+    pub struct Config {
+        pub owner: Option<Addr>,
+        pub manager: Option<Addr>,
+        pub staking_contract: Addr,
+        pub reward_token: Denom,
+    }
+    pub const CONFIG: Item<Config> = Item::new("config");
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+    pub struct RewardConfig {
+        pub period_finish: u64,
+        pub reward_rate: Uint128,
+        pub reward_duration: u64,
+    }
+    pub const REWARD_CONFIG: Item<RewardConfig> = Item::new("reward_config");
 
-pub const LAST_UPDATE_BLOCK: Item<u64> = Item::new("last_update_block");
+    pub const REWARD_PER_TOKEN: Item<Uint256> = Item::new("reward_per_token");
 
-pub const PENDING_REWARDS: Map<Addr, Uint128> = Map::new("pending_rewards");
+    pub const LAST_UPDATE_BLOCK: Item<u64> = Item::new("last_update_block");
 
-pub const USER_REWARD_PER_TOKEN: Map<Addr, Uint256> = Map::new("user_reward_per_token");
+    pub const PENDING_REWARDS: Map<Addr, Uint128> = Map::new("pending_rewards");
+
+    pub const USER_REWARD_PER_TOKEN: Map<Addr, Uint256> = Map::new("user_reward_per_token");
+}
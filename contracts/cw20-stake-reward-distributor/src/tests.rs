@@ -225,7 +225,7 @@ fn test_distribute() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -241,7 +241,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.balance, Uint128::new(990));
     assert_eq!(distributor_info.last_payment_block, app.block_info().height);
 
-    app.update_block(|mut block| block.height += 500);
+    app.update_block(|block| block.height += 500);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -257,7 +257,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.balance, Uint128::new(490));
     assert_eq!(distributor_info.last_payment_block, app.block_info().height);
 
-    app.update_block(|mut block| block.height += 1000);
+    app.update_block(|block| block.height += 1000);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -275,7 +275,7 @@ fn test_distribute() {
     let last_payment_block = distributor_info.last_payment_block;
 
     // Pays out nothing
-    app.update_block(|mut block| block.height += 1100);
+    app.update_block(|block| block.height += 1100);
     let err: ContractError = app
         .execute_contract(
             Addr::unchecked(OWNER),
@@ -297,7 +297,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.last_payment_block, last_payment_block);
 
     // go to a block before the last payment
-    app.update_block(|mut block| block.height -= 2000);
+    app.update_block(|block| block.height -= 2000);
     let err: ContractError = app
         .execute_contract(
             Addr::unchecked(OWNER),
@@ -439,7 +439,7 @@ fn test_withdraw() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -504,7 +504,7 @@ fn test_dao_deploy() {
     };
     let distributor_addr = instantiate_distributor(&mut app, msg);
 
-    app.update_block(|mut block| block.height += 1000);
+    app.update_block(|block| block.height += 1000);
 
     let msg = ExecuteMsg::UpdateConfig {
         owner: OWNER.to_string(),
@@ -522,7 +522,7 @@ fn test_dao_deploy() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -8,11 +8,12 @@ use cw20::{
     TokenInfoResponse,
 };
 use cw20_stake::msg::{
-    ClaimsResponse, ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, QueryMsg,
-    StakedBalanceAtHeightResponse, StakedValueResponse, TotalStakedAtHeightResponse,
-    TotalValueResponse,
+    ClaimsResponse, ExchangeRateHistoryResponse, ExecuteMsg, GetHooksResponse, InstantiateMsg,
+    ListStakersResponse, QueryMsg, StakedBalanceAtHeightResponse, StakedValueResponse,
+    TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use cw20_stake::state::Config;
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -35,6 +36,9 @@ fn main() {
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
     export_schema(&schema_for!(ListStakersResponse), &out_dir);
+    export_schema(&schema_for!(VotingPowerAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(TotalPowerAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateHistoryResponse), &out_dir);
 
     // Need to rename so it matches the TS pattern
     export_schema_with_title(&schema_for!(Config), &out_dir, "GetConfigResponse");
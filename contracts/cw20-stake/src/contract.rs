@@ -2,20 +2,22 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Empty, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128,
+    from_binary, from_slice, to_binary, to_vec, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Uint128,
 };
 
 use cw20::Cw20ReceiveMsg;
 
 use crate::hooks::{stake_hook_msgs, unstake_hook_msgs};
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
+    ExchangeRateHistoryResponse, ExchangeRateResponse, ExecuteMsg, GetHooksResponse,
+    InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg, ReceiveMsg,
+    StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
     TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, ExchangeRate, BALANCE, CLAIMS, CONFIG, EXCHANGE_RATES, HOOKS, MAX_CLAIMS,
+    STAKED_BALANCES, STAKED_TOTAL,
 };
 use crate::ContractError;
 use cw2::set_contract_version;
@@ -30,6 +32,7 @@ pub use cw20_base::contract::{
 };
 pub use cw20_base::enumerable::{query_all_accounts, query_all_allowances};
 use cw_controllers::ClaimsResponse;
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
 use cw_utils::Duration;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw20-stake";
@@ -176,6 +179,10 @@ pub fn execute_receive(
     let sender = deps.api.addr_validate(&wrapper.sender)?;
     match msg {
         ReceiveMsg::Stake {} => execute_stake(deps, env, sender, wrapper.amount),
+        ReceiveMsg::StakeFor { recipient } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            execute_stake(deps, env, recipient, wrapper.amount)
+        }
         ReceiveMsg::Fund {} => execute_fund(deps, env, &sender, wrapper.amount),
     }
 }
@@ -327,13 +334,26 @@ pub fn execute_claim(
 
 pub fn execute_fund(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     sender: &Addr,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    BALANCE.update(deps.storage, |balance| -> StdResult<_> {
+    let balance = BALANCE.update(deps.storage, |balance| -> StdResult<_> {
         balance.checked_add(amount).map_err(StdError::overflow)
     })?;
+    // There is no exchange rate while nothing is staked.
+    let staked_total = STAKED_TOTAL.load(deps.storage)?;
+    if !staked_total.is_zero() {
+        EXCHANGE_RATES.save(
+            deps.storage,
+            env.block.height,
+            &ExchangeRate {
+                time: env.block.time,
+                total_value: balance,
+                total_staked: staked_total,
+            },
+        )?;
+    }
     Ok(Response::new()
         .add_attribute("action", "fund")
         .add_attribute("from", sender)
@@ -391,6 +411,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListStakers { start_after, limit } => {
             query_list_stakers(deps, start_after, limit)
         }
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            to_binary(&query_voting_power_at_height(deps, env, address, height)?)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => {
+            to_binary(&query_total_power_at_height(deps, env, height)?)
+        }
+        QueryMsg::ExchangeRateHistory { start_after, limit } => {
+            to_binary(&query_exchange_rate_history(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -420,6 +449,33 @@ pub fn query_total_staked_at_height(
     Ok(TotalStakedAtHeightResponse { total, height })
 }
 
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<VotingPowerAtHeightResponse> {
+    let StakedBalanceAtHeightResponse { balance, height } =
+        query_staked_balance_at_height(deps, env, address, height)?;
+    Ok(VotingPowerAtHeightResponse {
+        power: balance,
+        height,
+    })
+}
+
+pub fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<TotalPowerAtHeightResponse> {
+    let TotalStakedAtHeightResponse { total, height } =
+        query_total_staked_at_height(deps, env, height)?;
+    Ok(TotalPowerAtHeightResponse {
+        power: total,
+        height,
+    })
+}
+
 pub fn query_staked_value(
     deps: Deps,
     _env: Env,
@@ -493,6 +549,28 @@ pub fn query_list_stakers(
     to_binary(&ListStakersResponse { stakers })
 }
 
+pub fn query_exchange_rate_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ExchangeRateHistoryResponse> {
+    let rates =
+        cw_paginate::paginate_map(deps, &EXCHANGE_RATES, start_after, limit, Order::Ascending)?;
+
+    let rates = rates
+        .into_iter()
+        .map(|(height, rate)| ExchangeRateResponse {
+            height,
+            time: rate.time,
+            rate: Decimal::from_ratio(rate.total_value, rate.total_staked),
+            total_value: rate.total_value,
+            total_staked: rate.total_staked,
+        })
+        .collect();
+
+    Ok(ExchangeRateHistoryResponse { rates })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     use serde::{Deserialize, Serialize};
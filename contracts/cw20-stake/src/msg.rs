@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -42,6 +42,14 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ReceiveMsg {
     Stake {},
+    /// Stakes the sent tokens on behalf of RECIPIENT. Used by
+    /// contracts, such as cw20-stake-external-rewards, to restake
+    /// rewards for the address that earned them.
+    StakeFor {
+        recipient: String,
+    },
+    /// Adds the sent tokens to the value of every staked token
+    /// without staking more tokens, increasing the exchange rate.
     Fund {},
 }
 
@@ -68,6 +76,27 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets ADDRESS's staked balance in the format of a voting
+    /// module's voting power query so that contracts which work with
+    /// any staking contract, such as cw20-stake-external-rewards, may
+    /// query it. Returns `VotingPowerAtHeightResponse`.
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Gets the total staked balance in the format of a voting
+    /// module's total power query. Returns
+    /// `TotalPowerAtHeightResponse`.
+    TotalPowerAtHeight {
+        height: Option<u64>,
+    },
+    /// Lists the exchange rate between staked tokens and their value
+    /// after each time the contract was funded, ordered by height.
+    /// Returns `ExchangeRateHistoryResponse`.
+    ExchangeRateHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -121,3 +150,20 @@ pub struct StakerBalanceResponse {
     pub address: String,
     pub balance: Uint128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExchangeRateResponse {
+    pub height: u64,
+    pub time: Timestamp,
+    /// The value of one staked token, `total_value / total_staked`.
+    pub rate: Decimal,
+    pub total_value: Uint128,
+    pub total_staked: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExchangeRateHistoryResponse {
+    pub rates: Vec<ExchangeRateResponse>,
+}
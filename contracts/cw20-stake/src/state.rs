@@ -1,10 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Timestamp, Uint128};
 use cw_controllers::Claims;
 use cw_controllers::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -38,5 +38,18 @@ pub const CLAIMS: Claims = Claims::new("claims");
 
 pub const BALANCE: Item<Uint128> = Item::new("balance");
 
+/// The total value and total staked tokens after the contract was
+/// funded, from which the exchange rate between the two is derived.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExchangeRate {
+    pub time: Timestamp,
+    pub total_value: Uint128,
+    pub total_staked: Uint128,
+}
+
+/// The exchange rate after the contract was last funded at each
+/// height it was funded at.
+pub const EXCHANGE_RATES: Map<u64, ExchangeRate> = Map::new("exchange_rates");
+
 // Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
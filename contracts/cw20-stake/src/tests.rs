@@ -2,14 +2,14 @@ use std::borrow::BorrowMut;
 
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ExecuteMsg, ListStakersResponse, MigrateMsg, QueryMsg, ReceiveMsg,
-    StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
-    TotalStakedAtHeightResponse, TotalValueResponse,
+    ExchangeRateHistoryResponse, ExchangeRateResponse, ExecuteMsg, ListStakersResponse, MigrateMsg,
+    QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse,
+    StakerBalanceResponse, TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{Config, MAX_CLAIMS};
 use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_slice, to_binary, Addr, Empty, MessageInfo, Storage, Uint128};
+use cosmwasm_std::{from_slice, to_binary, Addr, Decimal, Empty, MessageInfo, Storage, Uint128};
 use cw20::Cw20Coin;
 use cw_utils::Duration;
 
@@ -18,6 +18,7 @@ use cw_multi_test::{next_block, App, AppResponse, Contract, ContractWrapper, Exe
 use anyhow::Result as AnyResult;
 
 use cw_controllers::{Claim, ClaimsResponse};
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
 use cw_utils::Expiration::AtHeight;
 use serde::{Deserialize, Serialize};
 
@@ -916,6 +917,109 @@ fn test_auto_compounding_staking() {
     assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::from(65u128));
 }
 
+#[test]
+fn test_stake_for() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(1000),
+    }];
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&ReceiveMsg::StakeFor {
+            recipient: ADDR2.to_string(),
+        })
+        .unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2),
+        Uint128::new(100)
+    );
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(900));
+
+    // The recipient owns the stake and may unstake it.
+    let info = mock_info(ADDR2, &[]);
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(100)).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::new(100));
+}
+
+#[test]
+fn test_exchange_rate_history() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(1000),
+    }];
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+    let fund = |app: &mut App, amount: u128| {
+        let msg = cw20::Cw20ExecuteMsg::Send {
+            contract: staking_addr.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&ReceiveMsg::Fund {}).unwrap(),
+        };
+        app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+            .unwrap();
+    };
+    let query_history = |app: &App, start_after: Option<u64>, limit: Option<u32>| {
+        let res: ExchangeRateHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &staking_addr,
+                &QueryMsg::ExchangeRateHistory { start_after, limit },
+            )
+            .unwrap();
+        res.rates
+    };
+
+    // Funding while nothing is staked does not record a rate.
+    fund(&mut app, 100);
+    assert_eq!(query_history(&app, None, None), vec![]);
+
+    let info = mock_info(ADDR1, &[]);
+    stake_tokens(&mut app, &staking_addr, &cw20_addr, info, Uint128::new(100)).unwrap();
+    app.update_block(next_block);
+    fund(&mut app, 100);
+    let first = app.block_info();
+    app.update_block(next_block);
+    fund(&mut app, 150);
+    fund(&mut app, 50);
+    let second = app.block_info();
+
+    let rates = query_history(&app, None, None);
+    assert_eq!(
+        rates,
+        vec![
+            ExchangeRateResponse {
+                height: first.height,
+                time: first.time,
+                rate: Decimal::from_ratio(3u128, 1u128),
+                total_value: Uint128::new(300),
+                total_staked: Uint128::new(100),
+            },
+            ExchangeRateResponse {
+                height: second.height,
+                time: second.time,
+                rate: Decimal::from_ratio(5u128, 1u128),
+                total_value: Uint128::new(500),
+                total_staked: Uint128::new(100),
+            },
+        ]
+    );
+    assert_eq!(query_history(&app, None, Some(1)), rates[..1]);
+    assert_eq!(query_history(&app, Some(first.height), None), rates[1..]);
+}
+
 #[test]
 fn test_simple_unstaking_with_duration() {
     let _deps = mock_dependencies();
@@ -1085,6 +1189,75 @@ fn test_double_unstake_at_height() {
     assert_eq!(balance.balance, Uint128::zero())
 }
 
+#[test]
+fn test_voting_power_queries() {
+    let mut app = App::default();
+
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(100),
+        }],
+        None,
+    );
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(60),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    let height = app.block_info().height;
+
+    // The voting power queries mirror the staked balance queries.
+    let power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        power,
+        VotingPowerAtHeightResponse {
+            power: Uint128::new(60),
+            height,
+        }
+    );
+
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(10),
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr.clone(),
+            &QueryMsg::TotalPowerAtHeight {
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    assert_eq!(power.power, Uint128::new(60));
+
+    let power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(staking_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(power.power, Uint128::new(50));
+}
+
 #[test]
 fn test_query_list_stakers() {
     let mut app = App::default();
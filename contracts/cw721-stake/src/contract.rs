@@ -1,17 +1,17 @@
 use crate::hooks::{stake_hook_msgs, unstake_hook_msgs};
-use crate::msg::MigrateMsg;
-#[cfg(not(feature = "library"))]
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, Owner, QueryMsg, StakedBalanceAtHeightResponse,
-    TotalStakedAtHeightResponse,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, MigrateMsg, Owner, QueryMsg,
+    StakedBalanceAtHeightResponse, TotalStakedAtHeightResponse,
 };
 use crate::state::{
     Config, CONFIG, HOOKS, MAX_CLAIMS, NFT_CLAIMS, STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
 };
 use crate::ContractError;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Response, StdError, StdResult, Uint128, WasmMsg,
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw721::Cw721ReceiveMsg;
@@ -2,8 +2,9 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 use cw2::set_contract_version;
-use proposal_hooks::ProposalHookMsg;
-use vote_hooks::VoteHookMsg;
+use proposal_hooks::{ProposalHookMsg, ProposalHookMsgV2};
+use vote_hooks::{VoteHookMsg, VoteHookMsgV2};
+use voting::voting::Vote;
 
 use crate::error::ContractError;
 use crate::msg::{CountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -48,6 +49,10 @@ pub fn execute(
             execute_proposal_hook(deps, env, info, proposal_hook)
         }
         ExecuteMsg::VoteHook(vote_hook) => execute_vote_hook(deps, env, info, vote_hook),
+        ExecuteMsg::ProposalHookV2(proposal_hook) => {
+            execute_proposal_hook_v2(deps, env, info, proposal_hook)
+        }
+        ExecuteMsg::VoteHookV2(vote_hook) => execute_vote_hook_v2(deps, env, info, vote_hook),
     }
 }
 
@@ -90,6 +95,47 @@ pub fn execute_vote_hook(
     Ok(Response::new().add_attribute("action", "vote_hook"))
 }
 
+pub fn execute_proposal_hook_v2(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    proposal_hook: ProposalHookMsgV2,
+) -> Result<Response, ContractError> {
+    match proposal_hook {
+        ProposalHookMsgV2::NewProposal { .. } => {
+            let mut count = PROPOSAL_COUNTER.load(deps.storage)?;
+            count += 1;
+            PROPOSAL_COUNTER.save(deps.storage, &count)?;
+        }
+        ProposalHookMsgV2::ProposalStatusChanged { .. }
+        | ProposalHookMsgV2::PendingProposalRejected { .. }
+        | ProposalHookMsgV2::ProposalAmended { .. } => {
+            let mut count = STATUS_CHANGED_COUNTER.load(deps.storage)?;
+            count += 1;
+            STATUS_CHANGED_COUNTER.save(deps.storage, &count)?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "proposal_hook_v2"))
+}
+
+pub fn execute_vote_hook_v2(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    vote_hook: VoteHookMsgV2<Vote>,
+) -> Result<Response, ContractError> {
+    match vote_hook {
+        VoteHookMsgV2::NewVote { .. } => {
+            let mut count = VOTE_COUNTER.load(deps.storage)?;
+            count += 1;
+            VOTE_COUNTER.save(deps.storage, &count)?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "vote_hook_v2"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
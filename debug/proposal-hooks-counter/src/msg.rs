@@ -1,7 +1,8 @@
-use proposal_hooks::ProposalHookMsg;
+use proposal_hooks::{ProposalHookMsg, ProposalHookMsgV2};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use vote_hooks::VoteHookMsg;
+use vote_hooks::{VoteHookMsg, VoteHookMsgV2};
+use voting::voting::Vote;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -13,6 +14,9 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     ProposalHook(ProposalHookMsg),
     VoteHook(VoteHookMsg),
+    ProposalHookV2(ProposalHookMsgV2),
+    // Only single choice votes are counted in the V2 format.
+    VoteHookV2(VoteHookMsgV2<Vote>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
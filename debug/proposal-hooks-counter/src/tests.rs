@@ -2,9 +2,12 @@ use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
 use cw20::Cw20Coin;
 use cw_core::state::ProposalModule;
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{HookVersion, HooksResponse};
+use proposal_hooks::{ProposalHookEvent, ProposalHookFilter};
 
 use voting::{
+    curve::VotingPowerCurve,
+    status::Status,
     threshold::{PercentageThreshold, Threshold},
     voting::Vote,
 };
@@ -146,6 +149,10 @@ fn test_counters() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
     };
 
     let governance_addr =
@@ -202,6 +209,10 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: counters.to_string(),
+            version: None,
+            failure_policy: None,
+            gas_limit: None,
+            filter: None,
         },
         &[],
     )
@@ -211,6 +222,9 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: counters.to_string(),
+            version: None,
+            failure_policy: None,
+            gas_limit: None,
         },
         &[],
     )
@@ -221,7 +235,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::ProposalHooks {},
+            &cw_proposal_single::msg::QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
@@ -229,7 +246,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::VoteHooks {},
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
@@ -282,6 +302,7 @@ fn test_counters() {
         &cw_proposal_single::msg::ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -307,6 +328,10 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: failing_counters.to_string(),
+            version: None,
+            failure_policy: None,
+            gas_limit: None,
+            filter: None,
         },
         &[],
     )
@@ -316,6 +341,9 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: failing_counters.to_string(),
+            version: None,
+            failure_policy: None,
+            gas_limit: None,
         },
         &[],
     )
@@ -326,7 +354,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::ProposalHooks {},
+            &cw_proposal_single::msg::QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 2);
@@ -334,7 +365,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::VoteHooks {},
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 2);
@@ -365,7 +399,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::ProposalHooks {},
+            &cw_proposal_single::msg::QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
@@ -388,7 +425,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::VoteHooks {},
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 2);
@@ -400,6 +440,7 @@ fn test_counters() {
         &cw_proposal_single::msg::ExecuteMsg::Vote {
             proposal_id: 2,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -424,7 +465,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::VoteHooks {},
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
@@ -447,7 +491,10 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &cw_proposal_single::msg::QueryMsg::ProposalHooks {},
+            &cw_proposal_single::msg::QueryMsg::ProposalHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
@@ -455,8 +502,375 @@ fn test_counters() {
         .wrap()
         .query_wasm_smart(
             govmod_single,
-            &cw_proposal_single::msg::QueryMsg::VoteHooks {},
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 }
+
+#[test]
+fn test_versioned_counters() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(single_govmod_contract());
+    let counters_id = app.store_code(counters_contract());
+
+    let instantiate = cw_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_default_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    // One counter receives the original string based messages and
+    // the other the typed V2 messages.
+    let mut counters = vec![];
+    for version in [HookVersion::V1, HookVersion::V2] {
+        let addr = app
+            .instantiate_contract(
+                counters_id,
+                Addr::unchecked(CREATOR_ADDR),
+                &InstantiateMsg {
+                    should_error: false,
+                },
+                &[],
+                "counters",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
+                address: addr.to_string(),
+                version: Some(version),
+                failure_policy: None,
+                gas_limit: None,
+                filter: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
+                address: addr.to_string(),
+                version: Some(version),
+                failure_policy: None,
+                gas_limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+        counters.push(addr);
+    }
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Both counters understood their messages. A counter that failed
+    // to parse its message would have been removed.
+    for counters in counters {
+        for query in [
+            QueryMsg::ProposalCounter {},
+            QueryMsg::VoteCounter {},
+            QueryMsg::StatusChangedCounter {},
+        ] {
+            let resp: CountResponse = app
+                .wrap()
+                .query_wasm_smart(counters.clone(), &query)
+                .unwrap();
+            assert_eq!(resp.count, 1);
+        }
+    }
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &cw_proposal_single::msg::QueryMsg::VoteHooks {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 2);
+}
+
+#[test]
+fn test_filtered_counters() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(single_govmod_contract());
+    let counters_id = app.store_code(counters_contract());
+
+    let instantiate = cw_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_default_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let mut counters = vec![];
+    for filter in [
+        None,
+        // Only told about executed proposals.
+        Some(ProposalHookFilter {
+            events: Some(vec![ProposalHookEvent::ProposalStatusChanged]),
+            statuses: Some(vec![Status::Executed]),
+            contracts: None,
+        }),
+        // Only told about proposals that touch the treasury.
+        Some(ProposalHookFilter {
+            events: None,
+            statuses: None,
+            contracts: Some(vec!["treasury".to_string()]),
+        }),
+    ] {
+        let addr = app
+            .instantiate_contract(
+                counters_id,
+                Addr::unchecked(CREATOR_ADDR),
+                &InstantiateMsg {
+                    should_error: false,
+                },
+                &[],
+                "counters",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
+                address: addr.to_string(),
+                version: None,
+                failure_policy: None,
+                gas_limit: None,
+                filter,
+            },
+            &[],
+        )
+        .unwrap();
+        counters.push(addr);
+    }
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single,
+        &cw_proposal_single::msg::ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let counts: Vec<(u64, u64)> = counters
+        .into_iter()
+        .map(|counters| {
+            let proposals: CountResponse = app
+                .wrap()
+                .query_wasm_smart(counters.clone(), &QueryMsg::ProposalCounter {})
+                .unwrap();
+            let status_changes: CountResponse = app
+                .wrap()
+                .query_wasm_smart(counters, &QueryMsg::StatusChangedCounter {})
+                .unwrap();
+            (proposals.count, status_changes.count)
+        })
+        .collect();
+    assert_eq!(counts, vec![(1, 2), (0, 1), (0, 0)]);
+}
+
+#[test]
+fn test_amended_counters() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(single_govmod_contract());
+    let counters_id = app.store_code(counters_contract());
+
+    let instantiate = cw_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        voting_power_curve: VotingPowerCurve::Linear {},
+        timelock: None,
+        vetoer: None,
+        pre_propose: None,
+    };
+    let governance_addr =
+        instantiate_with_default_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let mut counters = vec![];
+    for version in [HookVersion::V1, HookVersion::V2] {
+        let addr = app
+            .instantiate_contract(
+                counters_id,
+                Addr::unchecked(CREATOR_ADDR),
+                &InstantiateMsg {
+                    should_error: false,
+                },
+                &[],
+                "counters",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
+                address: addr.to_string(),
+                version: Some(version),
+                failure_policy: None,
+                gas_limit: None,
+                filter: None,
+            },
+            &[],
+        )
+        .unwrap();
+        counters.push(addr);
+    }
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single,
+        &cw_proposal_single::msg::ExecuteMsg::Amend {
+            proposal_id: 1,
+            title: "Amended".to_string(),
+            description: "This is an amended proposal".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Amendments are only sent to V2 hooks. Both counters were still
+    // told about the new proposal.
+    let counts: Vec<(u64, u64)> = counters
+        .into_iter()
+        .map(|counters| {
+            let proposals: CountResponse = app
+                .wrap()
+                .query_wasm_smart(counters.clone(), &QueryMsg::ProposalCounter {})
+                .unwrap();
+            let status_changes: CountResponse = app
+                .wrap()
+                .query_wasm_smart(counters, &QueryMsg::StatusChangedCounter {})
+                .unwrap();
+            (proposals.count, status_changes.count)
+        })
+        .collect();
+    assert_eq!(counts, vec![(1, 0), (1, 1)]);
+}
@@ -1,15 +1,170 @@
+use std::fmt;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use cosmwasm_std::{Addr, CustomQuery, Deps, StdError, StdResult, Storage, SubMsg};
-use cw_storage_plus::Item;
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, CustomQuery, Deps, Order, ReplyOn, StdError, StdResult, Storage,
+    SubMsg, SubMsgResult, Timestamp, WasmMsg,
+};
+use cw_storage_plus::{Bound, Item, Map};
+
+/// The maximum number of failed deliveries remembered for each hook.
+/// Older failures are forgotten as new ones are recorded.
+pub const MAX_HOOK_FAILURES: usize = 10;
+
+/// The maximum number of failed messages waiting to be delivered again
+/// to each hook. A hook whose retry queue is full when another message
+/// fails to be delivered is disabled.
+pub const MAX_HOOK_RETRIES: usize = 10;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct HooksResponse {
     pub hooks: Vec<String>,
 }
 
+/// The version of the hook message format a registered hook
+/// receives. `V1` messages describe statuses and votes as strings and
+/// are understood by every receiver. Later versions are only sent to
+/// hooks that asked for them when they were registered.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HookVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+/// What to do with a hook when delivering a message to it fails.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFailurePolicy {
+    /// Unregister the hook.
+    Remove {},
+    /// Keep delivering messages to the hook.
+    Ignore {},
+    /// Queue messages that fail to be delivered and send them to the
+    /// hook again, ahead of any new message, the next time the hook is
+    /// sent messages. Each message is sent again up to TIMES times,
+    /// after which the hook is disabled. Disabled hooks receive no
+    /// messages until they are removed and registered again.
+    Retry { times: u32 },
+}
+
+impl Default for HookFailurePolicy {
+    fn default() -> Self {
+        HookFailurePolicy::Remove {}
+    }
+}
+
+/// Settings chosen when a hook is registered.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct HookOptions {
+    pub version: HookVersion,
+    pub failure_policy: HookFailurePolicy,
+    /// The maximum amount of gas delivering a message to the hook may
+    /// use. None for no limit beyond that of the transaction.
+    pub gas_limit: Option<u64>,
+    /// The kinds of event the hook receives messages for, for example
+    /// `new_proposal`. None to receive messages for every event.
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    /// Further limits the messages the hook receives. Interpreted by
+    /// the code preparing the hook's messages.
+    #[serde(default)]
+    pub filter: Option<Binary>,
+}
+
+impl HookOptions {
+    /// Returns true if a hook with these options receives messages
+    /// for EVENT.
+    pub fn subscribed_to(&self, event: &str) -> bool {
+        self.events
+            .as_ref()
+            .is_none_or(|events| events.iter().any(|e| e == event))
+    }
+}
+
+/// A failed delivery of a hook message.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct HookFailure {
+    pub error: String,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+/// A message sent to a hook that retries failed deliveries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct HookMessage {
+    pub msg: Binary,
+    /// The number of times delivering the message has failed.
+    pub failures: u32,
+}
+
+/// A registered hook's options and delivery history.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct HookConfig {
+    pub options: HookOptions,
+    /// The number of deliveries that have failed since the last
+    /// successful one.
+    pub consecutive_failures: u32,
+    /// If the hook has failed to receive a message more times than
+    /// its failure policy allows.
+    pub disabled: bool,
+    /// The most recent failed deliveries, oldest first.
+    pub failures: Vec<HookFailure>,
+    /// Messages that failed to be delivered and will be sent again,
+    /// oldest first.
+    #[serde(default)]
+    pub retry_queue: Vec<HookMessage>,
+    /// Messages that have been sent but whose replies have not yet
+    /// been handled, in the order they were sent.
+    #[serde(default)]
+    pub in_flight: Vec<HookMessage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DisabledHook {
+    pub address: String,
+    pub last_error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DisabledHooksResponse {
+    pub hooks: Vec<DisabledHook>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct HookFailuresResponse {
+    pub failures: Vec<HookFailure>,
+}
+
+/// What happened to a hook as a result of a reply to a message
+/// delivered to it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookReplyOutcome {
+    Removed,
+    Ignored,
+    Retrying,
+    Disabled,
+    Delivered,
+    Recovered,
+}
+
+impl fmt::Display for HookReplyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookReplyOutcome::Removed => write!(f, "removed"),
+            HookReplyOutcome::Ignored => write!(f, "ignored"),
+            HookReplyOutcome::Retrying => write!(f, "retrying"),
+            HookReplyOutcome::Disabled => write!(f, "disabled"),
+            HookReplyOutcome::Delivered => write!(f, "delivered"),
+            HookReplyOutcome::Recovered => write!(f, "recovered"),
+        }
+    }
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum HookError {
     #[error("{0}")]
@@ -22,32 +177,98 @@ pub enum HookError {
     HookNotRegistered {},
 }
 
-// store all hook addresses in one item. We cannot have many of them before the contract becomes unusable anyway.
-pub struct Hooks<'a>(Item<'a, Vec<Addr>>);
+/// A registered hook.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Hook {
+    pub address: Addr,
+    pub config: HookConfig,
+}
+
+/// Hooks are stored by index. Indices are assigned in the order hooks
+/// are registered and are never reused, so a hook keeps its index,
+/// and replies addressed to it keep reaching it, when other hooks are
+/// removed.
+pub struct Hooks<'a> {
+    hooks: Map<'a, u64, Hook>,
+    indices: Map<'a, Addr, u64>,
+    next_index: Item<'a, u64>,
+    // Hooks were once stored as a single list under the raw storage
+    // key. Kept so that they can be moved to the map on migration.
+    legacy: Item<'a, Vec<Addr>>,
+}
 
 impl<'a> Hooks<'a> {
-    pub const fn new(storage_key: &'a str) -> Self {
-        Hooks(Item::new(storage_key))
+    /// Creates hooks stored under STORAGE_KEY whose indices are
+    /// looked up by address under INDEX_KEY.
+    pub const fn new(storage_key: &'a str, index_key: &'a str) -> Self {
+        Hooks {
+            hooks: Map::new(storage_key),
+            indices: Map::new(index_key),
+            next_index: Item::new(index_key),
+            legacy: Item::new(storage_key),
+        }
     }
 
     pub fn add_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
-        if !hooks.iter().any(|h| h == &addr) {
-            hooks.push(addr);
-        } else {
+        self.add_hook_with_options(storage, addr, HookOptions::default())
+    }
+
+    pub fn add_hook_with_options(
+        &self,
+        storage: &mut dyn Storage,
+        addr: Addr,
+        options: HookOptions,
+    ) -> Result<(), HookError> {
+        if self.indices.has(storage, addr.clone()) {
             return Err(HookError::HookAlreadyRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        let index = self.next_index.may_load(storage)?.unwrap_or_default();
+        self.next_index.save(storage, &(index + 1))?;
+        self.indices.save(storage, addr.clone(), &index)?;
+        self.hooks.save(
+            storage,
+            index,
+            &Hook {
+                address: addr,
+                config: HookConfig {
+                    options,
+                    ..Default::default()
+                },
+            },
+        )?;
+        Ok(())
     }
 
-    pub fn remove_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.load(storage)?;
-        if let Some(p) = hooks.iter().position(|x| x == &addr) {
-            hooks.remove(p);
-        } else {
-            return Err(HookError::HookNotRegistered {});
+    /// Moves hooks stored as a single list, as they were before
+    /// hooks had indices, into storage. Hooks are registered with the
+    /// default options in the order they were listed. Does nothing if
+    /// there is no such list.
+    pub fn migrate_legacy_hooks(&self, storage: &mut dyn Storage) -> Result<(), HookError> {
+        if let Some(hooks) = self.legacy.may_load(storage)? {
+            self.legacy.remove(storage);
+            for addr in hooks {
+                self.add_hook(storage, addr)?;
+            }
         }
-        Ok(self.0.save(storage, &hooks)?)
+        Ok(())
+    }
+
+    /// Returns ADDR's options and delivery history.
+    pub fn hook_config(&self, storage: &dyn Storage, addr: &Addr) -> Result<HookConfig, HookError> {
+        let index = self
+            .indices
+            .may_load(storage, addr.clone())?
+            .ok_or(HookError::HookNotRegistered {})?;
+        Ok(self.hooks.load(storage, index)?.config)
+    }
+
+    pub fn remove_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
+        let index = self
+            .indices
+            .may_load(storage, addr)?
+            .ok_or(HookError::HookNotRegistered {})?;
+        self.remove_hook_by_index(storage, index)?;
+        Ok(())
     }
 
     pub fn remove_hook_by_index(
@@ -55,28 +276,666 @@ impl<'a> Hooks<'a> {
         storage: &mut dyn Storage,
         index: u64,
     ) -> Result<Addr, HookError> {
-        let mut hooks = self.0.load(storage)?;
-        let hook = hooks.remove(index as usize);
-        self.0.save(storage, &hooks)?;
-        Ok(hook)
+        let hook = self
+            .hooks
+            .may_load(storage, index)?
+            .ok_or(HookError::HookNotRegistered {})?;
+        self.hooks.remove(storage, index);
+        self.indices.remove(storage, hook.address.clone());
+        Ok(hook.address)
     }
 
     pub fn prepare_hooks<F: FnMut(Addr) -> StdResult<SubMsg>>(
         &self,
         storage: &dyn Storage,
-        prep: F,
+        mut prep: F,
     ) -> StdResult<Vec<SubMsg>> {
-        self.0
-            .may_load(storage)?
-            .unwrap_or_default()
-            .into_iter()
-            .map(prep)
+        self.hooks
+            .range(storage, None, None, Order::Ascending)
+            .map(|hook| prep(hook?.1.address))
             .collect()
     }
 
-    pub fn query_hooks<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<HooksResponse> {
-        let hooks = self.0.may_load(deps.storage)?.unwrap_or_default();
-        let hooks = hooks.into_iter().map(String::from).collect();
+    /// Prepares a submessage executing the message returned by PREP on
+    /// each hook that is subscribed to EVENT and is not disabled,
+    /// respecting the hook's gas limit. PREP is given the hook's
+    /// options and may return None to send the hook nothing, for
+    /// example if the hook's filter excludes the message. Submessages reply
+    /// on error with the ID returned by REPLY_ID for the hook's index,
+    /// which should be passed to `Hooks::handle_reply`. Hooks that retry
+    /// failed deliveries are first sent the messages in their retry
+    /// queue, and their submessages also reply on success so that
+    /// each reply can be matched with the message it is for.
+    pub fn prepare_configured_hooks<F>(
+        &self,
+        storage: &mut dyn Storage,
+        event: &str,
+        reply_id: fn(u64) -> u64,
+        mut prep: F,
+    ) -> StdResult<Vec<SubMsg>>
+    where
+        F: FnMut(&Addr, &HookOptions) -> StdResult<Option<Binary>>,
+    {
+        let hooks = self
+            .hooks
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut msgs = vec![];
+        for (index, Hook { address, config }) in hooks {
+            if config.disabled {
+                continue;
+            }
+            let msg = if config.options.subscribed_to(event) {
+                prep(&address, &config.options)?
+            } else {
+                None
+            };
+            if let HookFailurePolicy::Retry { .. } = config.options.failure_policy {
+                let mut config = config;
+                let mut sending = std::mem::take(&mut config.retry_queue);
+                sending.extend(msg.map(|msg| HookMessage { msg, failures: 0 }));
+                if sending.is_empty() {
+                    continue;
+                }
+                for message in &sending {
+                    msgs.push(hook_submsg(
+                        &address,
+                        &config.options,
+                        reply_id(index),
+                        message.msg.clone(),
+                        ReplyOn::Always,
+                    ));
+                }
+                config.in_flight.extend(sending);
+                self.hooks.save(storage, index, &Hook { address, config })?;
+            } else if let Some(msg) = msg {
+                msgs.push(hook_submsg(
+                    &address,
+                    &config.options,
+                    reply_id(index),
+                    msg,
+                    ReplyOn::Error,
+                ));
+            }
+        }
+        Ok(msgs)
+    }
+
+    /// Handles the reply to a message delivered to the hook at INDEX
+    /// by applying the hook's failure policy.
+    pub fn handle_reply(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        index: u64,
+        result: SubMsgResult,
+    ) -> Result<(Addr, HookReplyOutcome), HookError> {
+        let Hook {
+            address,
+            mut config,
+        } = self
+            .hooks
+            .may_load(storage, index)?
+            .ok_or(HookError::HookNotRegistered {})?;
+
+        // Submessages are executed, and replied to, in the order they
+        // were sent, so this reply is for the oldest message in flight.
+        let message = if config.in_flight.is_empty() {
+            None
+        } else {
+            Some(config.in_flight.remove(0))
+        };
+        let outcome = match result {
+            SubMsgResult::Ok(_) => {
+                let outcome = if config.consecutive_failures > 0 {
+                    HookReplyOutcome::Recovered
+                } else {
+                    HookReplyOutcome::Delivered
+                };
+                config.consecutive_failures = 0;
+                outcome
+            }
+            SubMsgResult::Err(error) => {
+                if let HookFailurePolicy::Remove {} = config.options.failure_policy {
+                    self.remove_hook_by_index(storage, index)?;
+                    return Ok((address, HookReplyOutcome::Removed));
+                }
+                config.consecutive_failures += 1;
+                config.failures.push(HookFailure {
+                    error,
+                    height: block.height,
+                    time: block.time,
+                });
+                if config.failures.len() > MAX_HOOK_FAILURES {
+                    config.failures.remove(0);
+                }
+                match (config.options.failure_policy, message) {
+                    (HookFailurePolicy::Retry { times }, Some(mut message)) => {
+                        message.failures += 1;
+                        if message.failures > times || config.retry_queue.len() >= MAX_HOOK_RETRIES
+                        {
+                            config.disabled = true;
+                            HookReplyOutcome::Disabled
+                        } else {
+                            config.retry_queue.push(message);
+                            HookReplyOutcome::Retrying
+                        }
+                    }
+                    _ => HookReplyOutcome::Ignored,
+                }
+            }
+        };
+        self.hooks.save(
+            storage,
+            index,
+            &Hook {
+                address: address.clone(),
+                config,
+            },
+        )?;
+        Ok((address, outcome))
+    }
+
+    /// Lists registered hooks in the order they were registered,
+    /// starting after START_AFTER. All remaining hooks are listed if
+    /// LIMIT is None.
+    pub fn query_hooks<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+        start_after: Option<Addr>,
+        limit: Option<u64>,
+    ) -> StdResult<HooksResponse> {
+        let min = start_after
+            .map(|addr| self.indices.load(deps.storage, addr))
+            .transpose()?
+            .map(Bound::exclusive);
+        let hooks = self
+            .hooks
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|hook| hook.map(|(_, hook)| hook.address.into_string()))
+            .collect::<StdResult<_>>()?;
         Ok(HooksResponse { hooks })
     }
+
+    /// Lists hooks that have been disabled after exhausting their
+    /// retries along with the error of their last failed delivery.
+    pub fn query_disabled_hooks<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+    ) -> StdResult<DisabledHooksResponse> {
+        let mut disabled = vec![];
+        for hook in self.hooks.range(deps.storage, None, None, Order::Ascending) {
+            let (_, Hook { address, config }) = hook?;
+            if config.disabled {
+                disabled.push(DisabledHook {
+                    address: address.into_string(),
+                    last_error: config
+                        .failures
+                        .last()
+                        .map(|failure| failure.error.clone())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        Ok(DisabledHooksResponse { hooks: disabled })
+    }
+
+    /// Lists the most recent failed deliveries to ADDR.
+    pub fn query_hook_failures<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+        addr: &Addr,
+    ) -> StdResult<HookFailuresResponse> {
+        let failures = self
+            .hook_config(deps.storage, addr)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+            .failures;
+        Ok(HookFailuresResponse { failures })
+    }
+}
+
+fn hook_submsg(
+    address: &Addr,
+    options: &HookOptions,
+    id: u64,
+    msg: Binary,
+    reply_on: ReplyOn,
+) -> SubMsg {
+    SubMsg {
+        id,
+        msg: WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg,
+            funds: vec![],
+        }
+        .into(),
+        gas_limit: options.gas_limit,
+        reply_on,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, MockStorage},
+        SubMsgResponse,
+    };
+
+    use super::*;
+
+    fn mask(index: u64) -> u64 {
+        index
+    }
+
+    fn fail() -> SubMsgResult {
+        SubMsgResult::Err("oh no".to_string())
+    }
+
+    #[test]
+    fn test_hook_options() {
+        let mut storage = MockStorage::new();
+        let hooks = Hooks::new("hooks", "hook_indices");
+        let addr = Addr::unchecked("hook");
+        let options = HookOptions {
+            version: HookVersion::V2,
+            failure_policy: HookFailurePolicy::Ignore {},
+            gas_limit: Some(100_000),
+            events: Some(vec!["new_vote".to_string()]),
+            filter: Some(Binary::from(b"filter")),
+        };
+        hooks
+            .add_hook_with_options(&mut storage, addr.clone(), options.clone())
+            .unwrap();
+        assert_eq!(hooks.hook_config(&storage, &addr).unwrap().options, options);
+        assert!(options.subscribed_to("new_vote"));
+        assert!(!options.subscribed_to("new_proposal"));
+        assert!(HookOptions::default().subscribed_to("new_proposal"));
+
+        // Hooks are not sent messages for events they are not
+        // subscribed to or that PREP filters out.
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "new_proposal", mask, |_, _| {
+                Ok(Some(Binary::default()))
+            })
+            .unwrap();
+        assert!(msgs.is_empty());
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "new_vote", mask, |_, options| {
+                Ok(options
+                    .filter
+                    .clone()
+                    .filter(|filter| filter.as_slice() != b"filter"))
+            })
+            .unwrap();
+        assert!(msgs.is_empty());
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "new_vote", mask, |_, _| {
+                Ok(Some(Binary::default()))
+            })
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+
+        // Removing a hook forgets its options.
+        hooks.remove_hook(&mut storage, addr.clone()).unwrap();
+        assert_eq!(
+            hooks.hook_config(&storage, &addr),
+            Err(HookError::HookNotRegistered {})
+        );
+        hooks.add_hook(&mut storage, addr.clone()).unwrap();
+        assert_eq!(
+            hooks.hook_config(&storage, &addr).unwrap().options,
+            HookOptions::default()
+        );
+    }
+
+    #[test]
+    fn test_stable_indices() {
+        let mut storage = MockStorage::new();
+        let hooks = Hooks::new("hooks", "hook_indices");
+        for addr in ["a", "b", "c"] {
+            hooks.add_hook(&mut storage, Addr::unchecked(addr)).unwrap();
+        }
+        assert_eq!(
+            hooks.add_hook(&mut storage, Addr::unchecked("b")),
+            Err(HookError::HookAlreadyRegistered {})
+        );
+
+        // Removing a hook leaves the indices of the others unchanged
+        // and its index is not reused.
+        hooks
+            .remove_hook(&mut storage, Addr::unchecked("b"))
+            .unwrap();
+        hooks.add_hook(&mut storage, Addr::unchecked("d")).unwrap();
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                Ok(Some(Binary::default()))
+            })
+            .unwrap();
+        assert_eq!(
+            msgs.iter().map(|msg| msg.id).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert_eq!(
+            hooks.remove_hook_by_index(&mut storage, 1),
+            Err(HookError::HookNotRegistered {})
+        );
+        assert_eq!(
+            hooks.remove_hook_by_index(&mut storage, 2),
+            Ok(Addr::unchecked("c"))
+        );
+    }
+
+    #[test]
+    fn test_query_hooks_paginated() {
+        let mut deps = mock_dependencies();
+        let hooks = Hooks::new("hooks", "hook_indices");
+        for addr in ["c", "a", "b"] {
+            hooks
+                .add_hook(deps.as_mut().storage, Addr::unchecked(addr))
+                .unwrap();
+        }
+        assert_eq!(
+            hooks.query_hooks(deps.as_ref(), None, None).unwrap().hooks,
+            vec!["c", "a", "b"]
+        );
+        assert_eq!(
+            hooks
+                .query_hooks(deps.as_ref(), None, Some(2))
+                .unwrap()
+                .hooks,
+            vec!["c", "a"]
+        );
+        assert_eq!(
+            hooks
+                .query_hooks(deps.as_ref(), Some(Addr::unchecked("a")), Some(2))
+                .unwrap()
+                .hooks,
+            vec!["b"]
+        );
+        hooks
+            .query_hooks(deps.as_ref(), Some(Addr::unchecked("d")), None)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_migrate_legacy_hooks() {
+        let mut storage = MockStorage::new();
+        let legacy: Item<Vec<Addr>> = Item::new("hooks");
+        legacy
+            .save(
+                &mut storage,
+                &vec![Addr::unchecked("a"), Addr::unchecked("b")],
+            )
+            .unwrap();
+
+        let hooks = Hooks::new("hooks", "hook_indices");
+        hooks.migrate_legacy_hooks(&mut storage).unwrap();
+        assert!(legacy.may_load(&storage).unwrap().is_none());
+        let msgs = hooks
+            .prepare_hooks(&storage, |addr| {
+                Ok(SubMsg::new(WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg: Binary::default(),
+                    funds: vec![],
+                }))
+            })
+            .unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        // Migrating again does nothing.
+        hooks.migrate_legacy_hooks(&mut storage).unwrap();
+        hooks.add_hook(&mut storage, Addr::unchecked("c")).unwrap();
+        assert_eq!(
+            hooks.remove_hook_by_index(&mut storage, 2),
+            Ok(Addr::unchecked("c"))
+        );
+    }
+
+    #[test]
+    fn test_failure_policies() {
+        let mut storage = MockStorage::new();
+        let block = mock_env().block;
+        let hooks = Hooks::new("hooks", "hook_indices");
+        for (addr, failure_policy) in [
+            ("remove", HookFailurePolicy::Remove {}),
+            ("ignore", HookFailurePolicy::Ignore {}),
+            ("retry", HookFailurePolicy::Retry { times: 1 }),
+        ] {
+            hooks
+                .add_hook_with_options(
+                    &mut storage,
+                    Addr::unchecked(addr),
+                    HookOptions {
+                        failure_policy,
+                        gas_limit: Some(10),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+
+        // Hooks that retry reply on success so that replies can be
+        // matched with the messages they are for.
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                Ok(Some(Binary::default()))
+            })
+            .unwrap();
+        assert_eq!(msgs.len(), 3);
+        assert!(msgs.iter().all(|msg| msg.gas_limit == Some(10)));
+        assert_eq!(msgs[0].reply_on, ReplyOn::Error);
+        assert_eq!(msgs[1].reply_on, ReplyOn::Error);
+        assert_eq!(msgs[2].reply_on, ReplyOn::Always);
+
+        let (addr, outcome) = hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+        assert_eq!(
+            (addr.as_str(), outcome),
+            ("remove", HookReplyOutcome::Removed)
+        );
+        let (_, outcome) = hooks.handle_reply(&mut storage, &block, 1, fail()).unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Ignored);
+        let (_, outcome) = hooks.handle_reply(&mut storage, &block, 2, fail()).unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Retrying);
+
+        let config = hooks
+            .hook_config(&storage, &Addr::unchecked("retry"))
+            .unwrap();
+        assert_eq!(config.consecutive_failures, 1);
+        assert_eq!(config.failures.len(), 1);
+        assert_eq!(
+            config.failures[0],
+            HookFailure {
+                error: "oh no".to_string(),
+                height: block.height,
+                time: block.time,
+            }
+        );
+        assert_eq!(
+            config.retry_queue,
+            vec![HookMessage {
+                msg: Binary::default(),
+                failures: 1,
+            }]
+        );
+        assert!(config.in_flight.is_empty());
+    }
+
+    #[test]
+    fn test_retry_redelivers() {
+        let mut storage = MockStorage::new();
+        let block = mock_env().block;
+        let hooks = Hooks::new("hooks", "hook_indices");
+        let addr = Addr::unchecked("retry");
+        hooks
+            .add_hook_with_options(
+                &mut storage,
+                addr.clone(),
+                HookOptions {
+                    failure_policy: HookFailurePolicy::Retry { times: 1 },
+                    events: Some(vec!["subscribed".to_string()]),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let payloads = |msgs: Vec<SubMsg>| {
+            msgs.into_iter()
+                .map(|msg| match msg.msg {
+                    cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => msg,
+                    _ => panic!("unexpected message"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let success = || {
+            SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            })
+        };
+
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "subscribed", mask, |_, _| {
+                Ok(Some(Binary::from(b"first")))
+            })
+            .unwrap();
+        assert_eq!(payloads(msgs), vec![Binary::from(b"first")]);
+        let (_, outcome) = hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Retrying);
+
+        // The failed message is sent again ahead of the next one, even
+        // if the hook is not subscribed to the next event.
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "unsubscribed", mask, |_, _| {
+                Ok(Some(Binary::from(b"ignored")))
+            })
+            .unwrap();
+        assert_eq!(payloads(msgs), vec![Binary::from(b"first")]);
+        let (_, outcome) = hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Disabled);
+        assert!(hooks.hook_config(&storage, &addr).unwrap().disabled);
+
+        // Once disabled a hook is sent nothing until it is registered
+        // again.
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "subscribed", mask, |_, _| {
+                Ok(Some(Binary::from(b"second")))
+            })
+            .unwrap();
+        assert!(msgs.is_empty());
+        hooks.remove_hook(&mut storage, addr.clone()).unwrap();
+        hooks
+            .add_hook_with_options(
+                &mut storage,
+                addr.clone(),
+                HookOptions {
+                    failure_policy: HookFailurePolicy::Retry { times: 2 },
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                Ok(Some(Binary::from(b"third")))
+            })
+            .unwrap();
+        assert_eq!(payloads(msgs), vec![Binary::from(b"third")]);
+        let (_, outcome) = hooks.handle_reply(&mut storage, &block, 1, fail()).unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Retrying);
+
+        // A message that fails to be delivered is delivered later.
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                Ok(Some(Binary::from(b"fourth")))
+            })
+            .unwrap();
+        assert_eq!(
+            payloads(msgs),
+            vec![Binary::from(b"third"), Binary::from(b"fourth")]
+        );
+        let (_, outcome) = hooks
+            .handle_reply(&mut storage, &block, 1, success())
+            .unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Recovered);
+        let (_, outcome) = hooks
+            .handle_reply(&mut storage, &block, 1, success())
+            .unwrap();
+        assert_eq!(outcome, HookReplyOutcome::Delivered);
+
+        let config = hooks.hook_config(&storage, &addr).unwrap();
+        assert!(config.retry_queue.is_empty());
+        assert!(config.in_flight.is_empty());
+        assert_eq!(config.consecutive_failures, 0);
+        let msgs = hooks
+            .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                Ok(Some(Binary::from(b"fifth")))
+            })
+            .unwrap();
+        assert_eq!(payloads(msgs), vec![Binary::from(b"fifth")]);
+    }
+
+    #[test]
+    fn test_retry_queue_bounded() {
+        let mut storage = MockStorage::new();
+        let block = mock_env().block;
+        let hooks = Hooks::new("hooks", "hook_indices");
+        let addr = Addr::unchecked("retry");
+        hooks
+            .add_hook_with_options(
+                &mut storage,
+                addr.clone(),
+                HookOptions {
+                    failure_policy: HookFailurePolicy::Retry { times: 100 },
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        // Queued messages are sent again along with each new message
+        // and every delivery fails, so the queue grows until it is
+        // full.
+        for sent in 1..=MAX_HOOK_RETRIES + 1 {
+            let msgs = hooks
+                .prepare_configured_hooks(&mut storage, "event", mask, |_, _| {
+                    Ok(Some(Binary::default()))
+                })
+                .unwrap();
+            assert_eq!(msgs.len(), sent);
+            for _ in 1..sent {
+                hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+            }
+            let (_, outcome) = hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+            if sent <= MAX_HOOK_RETRIES {
+                assert_eq!(outcome, HookReplyOutcome::Retrying);
+            } else {
+                assert_eq!(outcome, HookReplyOutcome::Disabled);
+            }
+        }
+        let config = hooks.hook_config(&storage, &addr).unwrap();
+        assert!(config.disabled);
+        assert_eq!(config.retry_queue.len(), MAX_HOOK_RETRIES);
+    }
+
+    #[test]
+    fn test_failure_log_bounded() {
+        let mut storage = MockStorage::new();
+        let block = mock_env().block;
+        let hooks = Hooks::new("hooks", "hook_indices");
+        let addr = Addr::unchecked("ignore");
+        hooks
+            .add_hook_with_options(
+                &mut storage,
+                addr.clone(),
+                HookOptions {
+                    failure_policy: HookFailurePolicy::Ignore {},
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        for _ in 0..MAX_HOOK_FAILURES + 5 {
+            hooks.handle_reply(&mut storage, &block, 0, fail()).unwrap();
+        }
+        let config = hooks.hook_config(&storage, &addr).unwrap();
+        assert_eq!(config.failures.len(), MAX_HOOK_FAILURES);
+        assert_eq!(config.consecutive_failures, MAX_HOOK_FAILURES as u32 + 5);
+    }
 }
@@ -1,8 +1,11 @@
-use cosmwasm_std::{to_binary, StdResult, Storage, SubMsg, WasmMsg};
-use indexable_hooks::Hooks;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Api, BankMsg, CosmosMsg, StdResult, Storage, SubMsg, WasmMsg,
+};
+use indexable_hooks::{HookOptions, HookVersion, Hooks};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use voting::reply::mask_proposal_hook_index;
+use voting::status::Status;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -17,31 +20,170 @@ pub enum ProposalHookMsg {
     },
 }
 
+/// Version two of the proposal hook message format, sent to hooks
+/// registered with `HookVersion::V2`. Statuses are typed and every
+/// message includes the address of the proposal module that sent it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalHookMsgV2 {
+    NewProposal {
+        id: u64,
+        module: Addr,
+    },
+    ProposalStatusChanged {
+        id: u64,
+        old_status: Status,
+        new_status: Status,
+        module: Addr,
+    },
+    /// Sent when a proposal awaiting approval in a proposal module's
+    /// pre-propose queue is rejected. Approved proposals are sent as
+    /// `NewProposal` messages.
+    PendingProposalRejected {
+        pending_id: u64,
+        module: Addr,
+    },
+    /// Sent when a proposal is amended. Votes cast on the previous
+    /// version of the proposal no longer count and voting restarts.
+    ProposalAmended {
+        id: u64,
+        /// The version of the proposal after the amendment.
+        version: u64,
+        module: Addr,
+    },
+}
+
 // This is just a helper to properly serialize the above message
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ProposalHookExecuteMsg {
     ProposalHook(ProposalHookMsg),
+    ProposalHookV2(ProposalHookMsgV2),
+}
+
+/// The kinds of message proposal hooks are sent.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalHookEvent {
+    NewProposal,
+    ProposalStatusChanged,
+    PendingProposalRejected,
+    ProposalAmended,
+}
+
+impl ProposalHookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalHookEvent::NewProposal => "new_proposal",
+            ProposalHookEvent::ProposalStatusChanged => "proposal_status_changed",
+            ProposalHookEvent::PendingProposalRejected => "pending_proposal_rejected",
+            ProposalHookEvent::ProposalAmended => "proposal_amended",
+        }
+    }
 }
+
+/// Limits the messages a proposal hook is sent. A message is sent if
+/// it passes every filter that is set.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct ProposalHookFilter {
+    /// Only send these kinds of message.
+    #[serde(default)]
+    pub events: Option<Vec<ProposalHookEvent>>,
+    /// Only send status changes to one of these statuses, for example
+    /// `[executed]` to only be told about executed
+    /// proposals. Other kinds of message are not affected.
+    #[serde(default)]
+    pub statuses: Option<Vec<Status>>,
+    /// Only send messages about proposals with a message executing,
+    /// migrating, administering, or sending tokens to one of these
+    /// contracts.
+    #[serde(default)]
+    pub contracts: Option<Vec<String>>,
+}
+
+impl ProposalHookFilter {
+    /// Validates the filter's contract addresses and returns OPTIONS
+    /// set to apply the filter.
+    pub fn apply(self, api: &dyn Api, mut options: HookOptions) -> StdResult<HookOptions> {
+        let filter = ProposalHookFilter {
+            events: None,
+            statuses: self.statuses,
+            contracts: self
+                .contracts
+                .map(|contracts| {
+                    contracts
+                        .into_iter()
+                        .map(|contract| api.addr_validate(&contract).map(String::from))
+                        .collect::<StdResult<Vec<_>>>()
+                })
+                .transpose()?,
+        };
+        options.events = self.events.map(|events| {
+            events
+                .into_iter()
+                .map(|event| event.as_str().to_string())
+                .collect()
+        });
+        options.filter = if filter == ProposalHookFilter::default() {
+            None
+        } else {
+            Some(to_binary(&filter)?)
+        };
+        Ok(options)
+    }
+
+    fn matches(&self, new_status: Option<Status>, msgs: &[CosmosMsg]) -> bool {
+        let status_matches = match (&self.statuses, new_status) {
+            (Some(statuses), Some(new_status)) => statuses.contains(&new_status),
+            _ => true,
+        };
+        let contracts_match = self.contracts.as_ref().is_none_or(|contracts| {
+            msgs.iter().any(|msg| match msg_contract(msg) {
+                Some(addr) => contracts.iter().any(|contract| contract == addr),
+                None => false,
+            })
+        });
+        status_matches && contracts_match
+    }
+}
+
+/// Returns the address of the contract MSG executes, migrates,
+/// administers, or sends tokens to.
+fn msg_contract(msg: &CosmosMsg) -> Option<&str> {
+    match msg {
+        CosmosMsg::Wasm(
+            WasmMsg::Execute { contract_addr, .. }
+            | WasmMsg::Migrate { contract_addr, .. }
+            | WasmMsg::UpdateAdmin { contract_addr, .. }
+            | WasmMsg::ClearAdmin { contract_addr },
+        ) => Some(contract_addr),
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => Some(to_address),
+        _ => None,
+    }
+}
+
 /// Prepares new proposal hook messages. These messages reply on error
 /// and have even reply IDs.
 /// IDs are set to even numbers to then be interleaved with the vote hooks.
-pub fn new_proposal_hooks(hooks: Hooks, storage: &dyn Storage, id: u64) -> StdResult<Vec<SubMsg>> {
-    let msg = to_binary(&ProposalHookExecuteMsg::ProposalHook(
-        ProposalHookMsg::NewProposal { id },
-    ))?;
-    let mut index: u64 = 0;
-    hooks.prepare_hooks(storage, |a| {
-        let execute = WasmMsg::Execute {
-            contract_addr: a.to_string(),
-            msg: msg.clone(),
-            funds: vec![],
-        };
-        let masked_index = mask_proposal_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
-    })
+pub fn new_proposal_hooks(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    id: u64,
+    module: &Addr,
+    msgs: &[CosmosMsg],
+) -> StdResult<Vec<SubMsg>> {
+    prepare_hooks(
+        hooks,
+        storage,
+        ProposalHookEvent::NewProposal,
+        None,
+        msgs,
+        Some(ProposalHookMsg::NewProposal { id }),
+        ProposalHookMsgV2::NewProposal {
+            id,
+            module: module.clone(),
+        },
+    )
 }
 
 /// Prepares proposal status hook messages. These messages reply on error
@@ -49,32 +191,303 @@ pub fn new_proposal_hooks(hooks: Hooks, storage: &dyn Storage, id: u64) -> StdRe
 /// IDs are set to even numbers to then be interleaved with the vote hooks.
 pub fn proposal_status_changed_hooks(
     hooks: Hooks,
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     id: u64,
-    old_status: String,
-    new_status: String,
+    old_status: Status,
+    new_status: Status,
+    module: &Addr,
+    msgs: &[CosmosMsg],
 ) -> StdResult<Vec<SubMsg>> {
     if old_status == new_status {
         return Ok(vec![]);
     }
 
-    let msg = to_binary(&ProposalHookExecuteMsg::ProposalHook(
-        ProposalHookMsg::ProposalStatusChanged {
+    prepare_hooks(
+        hooks,
+        storage,
+        ProposalHookEvent::ProposalStatusChanged,
+        Some(new_status),
+        msgs,
+        Some(ProposalHookMsg::ProposalStatusChanged {
+            id,
+            old_status: old_status.to_string(),
+            new_status: new_status.to_string(),
+        }),
+        ProposalHookMsgV2::ProposalStatusChanged {
             id,
             old_status,
             new_status,
+            module: module.clone(),
+        },
+    )
+}
+
+/// Prepares pending proposal rejected hook messages. These messages
+/// reply on error and have even reply IDs. There is no V1 version of
+/// this message so only hooks registered with `HookVersion::V2` are
+/// sent it.
+pub fn pending_proposal_rejected_hooks(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    pending_id: u64,
+    module: &Addr,
+    msgs: &[CosmosMsg],
+) -> StdResult<Vec<SubMsg>> {
+    prepare_hooks(
+        hooks,
+        storage,
+        ProposalHookEvent::PendingProposalRejected,
+        None,
+        msgs,
+        None,
+        ProposalHookMsgV2::PendingProposalRejected {
+            pending_id,
+            module: module.clone(),
         },
-    ))?;
-    let mut index: u64 = 0;
-    hooks.prepare_hooks(storage, |a| {
-        let execute = WasmMsg::Execute {
-            contract_addr: a.to_string(),
-            msg: msg.clone(),
+    )
+}
+
+/// Prepares proposal amended hook messages. These messages reply on
+/// error and have even reply IDs. There is no V1 version of this
+/// message so only hooks registered with `HookVersion::V2` are sent
+/// it.
+pub fn proposal_amended_hooks(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    id: u64,
+    version: u64,
+    module: &Addr,
+    msgs: &[CosmosMsg],
+) -> StdResult<Vec<SubMsg>> {
+    prepare_hooks(
+        hooks,
+        storage,
+        ProposalHookEvent::ProposalAmended,
+        None,
+        msgs,
+        None,
+        ProposalHookMsgV2::ProposalAmended {
+            id,
+            version,
+            module: module.clone(),
+        },
+    )
+}
+
+/// Sends each hook subscribed to EVENT whose filter matches the
+/// proposal the message matching the version it was registered
+/// with. MSGS are the proposal's messages and NEW_STATUS its status
+/// after a status change. V1 hooks are sent nothing if V1 is None.
+fn prepare_hooks(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    event: ProposalHookEvent,
+    new_status: Option<Status>,
+    msgs: &[CosmosMsg],
+    v1: Option<ProposalHookMsg>,
+    v2: ProposalHookMsgV2,
+) -> StdResult<Vec<SubMsg>> {
+    let v1 = v1
+        .map(|v1| to_binary(&ProposalHookExecuteMsg::ProposalHook(v1)))
+        .transpose()?;
+    let v2 = to_binary(&ProposalHookExecuteMsg::ProposalHookV2(v2))?;
+    hooks.prepare_configured_hooks(
+        storage,
+        event.as_str(),
+        mask_proposal_hook_index,
+        |_, options| {
+            if let Some(filter) = &options.filter {
+                let filter: ProposalHookFilter = from_binary(filter)?;
+                if !filter.matches(new_status, msgs) {
+                    return Ok(None);
+                }
+            }
+            Ok(match options.version {
+                HookVersion::V1 => v1.clone(),
+                HookVersion::V2 => Some(v2.clone()),
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, testing::mock_dependencies, testing::MockStorage, Binary};
+
+    use super::*;
+
+    const HOOKS: Hooks = Hooks::new("hooks", "hook_indices");
+
+    #[test]
+    fn test_versioned_proposal_hooks() {
+        let mut storage = MockStorage::new();
+        HOOKS.add_hook(&mut storage, Addr::unchecked("v1")).unwrap();
+        HOOKS
+            .add_hook_with_options(
+                &mut storage,
+                Addr::unchecked("v2"),
+                HookOptions {
+                    version: HookVersion::V2,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let module = Addr::unchecked("module");
+        let msgs = proposal_status_changed_hooks(
+            HOOKS,
+            &mut storage,
+            1,
+            Status::Open,
+            Status::Passed,
+            &module,
+            &[],
+        )
+        .unwrap();
+        let msgs: Vec<(String, ProposalHookExecuteMsg)> = msgs
+            .into_iter()
+            .map(|submsg| match submsg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => (contract_addr, from_binary(&msg).unwrap()),
+                _ => panic!("unexpected message"),
+            })
+            .collect();
+        assert_eq!(
+            msgs,
+            vec![
+                (
+                    "v1".to_string(),
+                    ProposalHookExecuteMsg::ProposalHook(ProposalHookMsg::ProposalStatusChanged {
+                        id: 1,
+                        old_status: "open".to_string(),
+                        new_status: "passed".to_string(),
+                    })
+                ),
+                (
+                    "v2".to_string(),
+                    ProposalHookExecuteMsg::ProposalHookV2(
+                        ProposalHookMsgV2::ProposalStatusChanged {
+                            id: 1,
+                            old_status: Status::Open,
+                            new_status: Status::Passed,
+                            module: module.clone(),
+                        }
+                    )
+                ),
+            ]
+        );
+
+        // Pending proposal rejections and amendments are only sent to
+        // V2 hooks.
+        let msgs = pending_proposal_rejected_hooks(HOOKS, &mut storage, 1, &module, &[]).unwrap();
+        assert_eq!(receivers(msgs), vec!["v2"]);
+        let msgs = proposal_amended_hooks(HOOKS, &mut storage, 1, 2, &module, &[]).unwrap();
+        assert_eq!(receivers(msgs), vec!["v2"]);
+
+        // Unchanged statuses send no messages.
+        let msgs = proposal_status_changed_hooks(
+            HOOKS,
+            &mut storage,
+            1,
+            Status::Open,
+            Status::Open,
+            &Addr::unchecked("module"),
+            &[],
+        )
+        .unwrap();
+        assert!(msgs.is_empty());
+    }
+
+    fn receivers(msgs: Vec<SubMsg>) -> Vec<String> {
+        msgs.into_iter()
+            .map(|submsg| match submsg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => contract_addr,
+                _ => panic!("unexpected message"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filtered_proposal_hooks() {
+        let mut deps = mock_dependencies();
+        let api = deps.api;
+        for (addr, filter) in [
+            ("all", ProposalHookFilter::default()),
+            (
+                "executed",
+                ProposalHookFilter {
+                    events: Some(vec![ProposalHookEvent::ProposalStatusChanged]),
+                    statuses: Some(vec![Status::Executed]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "treasury",
+                ProposalHookFilter {
+                    contracts: Some(vec!["treasury".to_string()]),
+                    ..Default::default()
+                },
+            ),
+        ] {
+            let options = filter.apply(&api, HookOptions::default()).unwrap();
+            HOOKS
+                .add_hook_with_options(deps.as_mut().storage, Addr::unchecked(addr), options)
+                .unwrap();
+        }
+        assert_eq!(
+            HOOKS
+                .hook_config(deps.as_ref().storage, &Addr::unchecked("all"))
+                .unwrap()
+                .options,
+            HookOptions::default()
+        );
+
+        let module = Addr::unchecked("module");
+        let to_treasury = vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: coins(1, "ujuno"),
+        })];
+        let to_other = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "other".to_string(),
+            msg: Binary::default(),
             funds: vec![],
-        };
-        let masked_index = mask_proposal_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
-    })
+        })];
+
+        let storage = deps.as_mut().storage;
+        let msgs = new_proposal_hooks(HOOKS, storage, 1, &module, &to_other).unwrap();
+        assert_eq!(receivers(msgs), vec!["all"]);
+        let msgs = new_proposal_hooks(HOOKS, storage, 1, &module, &to_treasury).unwrap();
+        assert_eq!(receivers(msgs), vec!["all", "treasury"]);
+        let msgs = proposal_status_changed_hooks(
+            HOOKS,
+            storage,
+            1,
+            Status::Open,
+            Status::Passed,
+            &module,
+            &to_other,
+        )
+        .unwrap();
+        assert_eq!(receivers(msgs), vec!["all"]);
+        let msgs = proposal_status_changed_hooks(
+            HOOKS,
+            storage,
+            1,
+            Status::Passed,
+            Status::Executed,
+            &module,
+            &to_treasury,
+        )
+        .unwrap();
+        assert_eq!(receivers(msgs), vec!["all", "executed", "treasury"]);
+
+        // Contract addresses are validated.
+        ProposalHookFilter {
+            contracts: Some(vec!["".to_string()]),
+            ..Default::default()
+        }
+        .apply(&api, HookOptions::default())
+        .unwrap_err();
+    }
 }
@@ -1,5 +1,7 @@
-use cosmwasm_std::{to_binary, StdResult, Storage, SubMsg, WasmMsg};
-use indexable_hooks::Hooks;
+use std::fmt::Display;
+
+use cosmwasm_std::{to_binary, Addr, StdResult, Storage, SubMsg, Uint128};
+use indexable_hooks::{HookVersion, Hooks};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use voting::reply::mask_vote_hook_index;
@@ -11,41 +13,130 @@ pub enum VoteHookMsg {
         proposal_id: u64,
         voter: String,
         vote: String,
+        /// The voter's explanation of their vote, if they provided
+        /// one. Omitted from the message when not set so that
+        /// receivers unaware of this field continue to work.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rationale: Option<String>,
+    },
+}
+
+/// Version two of the vote hook message format, sent to hooks
+/// registered with `HookVersion::V2`. V is the proposal module's vote
+/// type, for example `voting::voting::Vote` for single choice
+/// proposals.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteHookMsgV2<V> {
+    NewVote {
+        proposal_id: u64,
+        voter: Addr,
+        vote: V,
+        /// The voting power behind the vote.
+        power: Uint128,
+        rationale: Option<String>,
+        /// The proposal module the vote was cast in.
+        module: Addr,
     },
 }
 
 // This is just a helper to properly serialize the above message
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
-pub enum VoteHookExecuteMsg {
+pub enum VoteHookExecuteMsg<V> {
     VoteHook(VoteHookMsg),
+    VoteHookV2(VoteHookMsgV2<V>),
 }
 
 /// Prepares new vote hook messages. These messages reply on error
 /// and have even reply IDs.
 /// IDs are set to odd numbers to then be interleaved with the proposal hooks.
-pub fn new_vote_hooks(
+/// Hooks registered with `HookVersion::V1` receive MSG with its vote
+/// and voter converted to strings.
+pub fn new_vote_hooks<V: Serialize + Display + Clone>(
     hooks: Hooks,
-    storage: &dyn Storage,
-    proposal_id: u64,
-    voter: String,
-    vote: String,
+    storage: &mut dyn Storage,
+    msg: VoteHookMsgV2<V>,
 ) -> StdResult<Vec<SubMsg>> {
-    let msg = to_binary(&VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+    let VoteHookMsgV2::NewVote {
         proposal_id,
         voter,
         vote,
+        rationale,
+        ..
+    } = msg.clone();
+    let v1 = to_binary(&VoteHookExecuteMsg::<V>::VoteHook(VoteHookMsg::NewVote {
+        proposal_id,
+        voter: voter.into_string(),
+        vote: vote.to_string(),
+        rationale,
     }))?;
-    let mut index: u64 = 0;
-    hooks.prepare_hooks(storage, |a| {
-        let execute = WasmMsg::Execute {
-            contract_addr: a.to_string(),
-            msg: msg.clone(),
-            funds: vec![],
-        };
-        let masked_index = mask_vote_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
+    let v2 = to_binary(&VoteHookExecuteMsg::VoteHookV2(msg))?;
+    hooks.prepare_configured_hooks(storage, "new_vote", mask_vote_hook_index, |_, options| {
+        Ok(Some(match options.version {
+            HookVersion::V1 => v1.clone(),
+            HookVersion::V2 => v2.clone(),
+        }))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_binary, testing::MockStorage, CosmosMsg, WasmMsg};
+    use indexable_hooks::HookOptions;
+    use voting::voting::Vote;
+
+    use super::*;
+
+    const HOOKS: Hooks = Hooks::new("hooks", "hook_indices");
+
+    #[test]
+    fn test_versioned_vote_hooks() {
+        let mut storage = MockStorage::new();
+        HOOKS.add_hook(&mut storage, Addr::unchecked("v1")).unwrap();
+        HOOKS
+            .add_hook_with_options(
+                &mut storage,
+                Addr::unchecked("v2"),
+                HookOptions {
+                    version: HookVersion::V2,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let new_vote = VoteHookMsgV2::NewVote {
+            proposal_id: 1,
+            voter: Addr::unchecked("ekez"),
+            vote: Vote::Yes,
+            power: Uint128::new(10),
+            rationale: Some("why not".to_string()),
+            module: Addr::unchecked("module"),
+        };
+        let msgs = new_vote_hooks(HOOKS, &mut storage, new_vote.clone()).unwrap();
+        let msgs: Vec<(String, VoteHookExecuteMsg<Vote>)> = msgs
+            .into_iter()
+            .map(|submsg| match submsg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => (contract_addr, from_binary(&msg).unwrap()),
+                _ => panic!("unexpected message"),
+            })
+            .collect();
+        assert_eq!(
+            msgs,
+            vec![
+                (
+                    "v1".to_string(),
+                    VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+                        proposal_id: 1,
+                        voter: "ekez".to_string(),
+                        vote: "yes".to_string(),
+                        rationale: Some("why not".to_string()),
+                    })
+                ),
+                ("v2".to_string(), VoteHookExecuteMsg::VoteHookV2(new_vote)),
+            ]
+        );
+    }
+}
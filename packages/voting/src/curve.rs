@@ -0,0 +1,225 @@
+use cosmwasm_std::{StdError, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::VotingError, threshold::Threshold};
+
+/// A curve that is applied to an address' voting power before its
+/// vote is counted. Non-linear curves reduce the influence of large
+/// holders on the outcome of a proposal.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingPowerCurve {
+    /// Votes are weighted by voting power.
+    Linear {},
+    /// Votes are weighted by the `degree`th root of voting power,
+    /// rounded down. A degree of two is quadratic voting.
+    Root { degree: u32 },
+}
+
+impl Default for VotingPowerCurve {
+    fn default() -> Self {
+        Self::Linear {}
+    }
+}
+
+impl VotingPowerCurve {
+    /// Validates that the curve is well formed. Roots must have a
+    /// degree of at least two, a degree of one being the linear
+    /// curve.
+    pub fn validate(&self) -> Result<(), VotingError> {
+        match self {
+            Self::Linear {} => Ok(()),
+            Self::Root { degree } => {
+                if *degree < 2 {
+                    Err(VotingError::InvalidRootDegree {})
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Validates that THRESHOLD may be used alongside this curve. An
+    /// absolute count is a number of uncurved votes, so it can not be
+    /// compared against curved vote weights.
+    pub fn validate_threshold(&self, threshold: &Threshold) -> Result<(), VotingError> {
+        match (self, threshold) {
+            (Self::Linear {}, _) => Ok(()),
+            (_, Threshold::AbsoluteCount { .. }) => Err(VotingError::CurvedAbsoluteCount {}),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Self::Linear {})
+    }
+
+    /// Returns the weight of a vote cast with POWER voting power.
+    pub fn apply(&self, power: Uint128) -> Uint128 {
+        match self {
+            Self::Linear {} => power,
+            Self::Root { degree } => Uint128::new(integer_root(power.u128(), *degree)),
+        }
+    }
+}
+
+/// Computes the largest integer whose `degree`th power is less than
+/// or equal to VALUE.
+fn integer_root(value: u128, degree: u32) -> u128 {
+    // r^degree <= u128::MAX implies r < 2^(128 / degree + 1).
+    let mut low = 0u128;
+    let mut high = value.min(1u128 << (128 / degree + 1).min(127));
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        match mid.checked_pow(degree) {
+            Some(pow) if pow <= value => low = mid,
+            _ => high = mid - 1,
+        }
+    }
+    low
+}
+
+/// Bookkeeping for proposals whose votes are weighted by a non-linear
+/// curve.
+///
+/// For a concave curve the sum of the curved powers of each voter is
+/// larger than the curve applied to their combined power, so the
+/// curved total power can not be known without knowing how the
+/// voting power of those who have not voted is split between them.
+/// Instead the total is estimated by assuming that voting power which
+/// has not yet voted carries the same curved weight per unit of power
+/// as the power which has voted. That is, the curved total power is
+/// the weight cast scaled by the uncurved total power over the
+/// uncurved power cast.
+///
+/// With this estimate the share of the curved total that has been
+/// cast is the same as the share of uncurved voting power that has
+/// voted, so curving votes does not change turnout. As the estimate
+/// changes with each vote it is only meaningful once voting has
+/// closed. Before any votes are cast the total is the curve applied
+/// to the uncurved total power.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CurvedPower {
+    /// The curve applied to voting power.
+    pub curve: VotingPowerCurve,
+    /// The uncurved total voting power at the time of the proposal's
+    /// creation.
+    pub total_power: Uint128,
+    /// The uncurved voting power of the addresses that have voted.
+    pub power_cast: Uint128,
+}
+
+impl CurvedPower {
+    pub fn new(curve: VotingPowerCurve, total_power: Uint128) -> Self {
+        Self {
+            curve,
+            total_power,
+            power_cast: Uint128::zero(),
+        }
+    }
+
+    /// Records the voting power of an address voting for the first
+    /// time.
+    pub fn add_voter(&mut self, power: Uint128) {
+        self.power_cast += power;
+    }
+
+    /// Records that POWER of the voting power that has voted is no
+    /// longer cast, for example because it was delegated power that
+    /// its owner has since cast themselves.
+    pub fn remove_power(&mut self, power: Uint128) -> StdResult<()> {
+        self.power_cast = self
+            .power_cast
+            .checked_sub(power)
+            .map_err(StdError::overflow)?;
+        Ok(())
+    }
+
+    /// Estimates the curved total power given that WEIGHT_CAST has
+    /// been cast so far.
+    pub fn total_power(&self, weight_cast: Uint128) -> Uint128 {
+        if self.power_cast.is_zero() {
+            self.curve.apply(self.total_power)
+        } else {
+            weight_cast.multiply_ratio(self.total_power.max(self.power_cast), self.power_cast)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_integer_root() {
+        assert_eq!(integer_root(0, 2), 0);
+        assert_eq!(integer_root(1, 2), 1);
+        assert_eq!(integer_root(15, 2), 3);
+        assert_eq!(integer_root(16, 2), 4);
+        assert_eq!(integer_root(26, 3), 2);
+        assert_eq!(integer_root(27, 3), 3);
+        assert_eq!(integer_root(u128::MAX, 2), u64::MAX as u128);
+        assert_eq!(integer_root(u128::MAX, 128), 1);
+        assert_eq!(integer_root(u128::MAX, 200), 1);
+    }
+
+    #[test]
+    fn test_curved_total_power() {
+        let curve = VotingPowerCurve::Root { degree: 2 };
+        let mut power = CurvedPower::new(curve, Uint128::new(200));
+        assert_eq!(power.total_power(Uint128::zero()), Uint128::new(14));
+
+        // Two voters with 100 power each have a combined weight of 20
+        // which is more than the curve applied to their combined
+        // power.
+        power.add_voter(Uint128::new(100));
+        let weight = curve.apply(Uint128::new(100));
+        assert_eq!(weight, Uint128::new(10));
+        assert_eq!(power.total_power(weight), Uint128::new(20));
+        power.add_voter(Uint128::new(100));
+        assert_eq!(power.total_power(weight + weight), Uint128::new(20));
+        power.remove_power(Uint128::new(100)).unwrap();
+        assert_eq!(power.total_power(weight), Uint128::new(20));
+
+        // More power than has been cast may not be removed.
+        power.remove_power(Uint128::new(101)).unwrap_err();
+        assert_eq!(power.power_cast, Uint128::new(100));
+
+        // A small voter does not count all outstanding power as a
+        // single voter, which would inflate turnout to 2 / (2 + 14).
+        let mut power = CurvedPower::new(curve, Uint128::new(200));
+        power.add_voter(Uint128::new(4));
+        let weight = curve.apply(Uint128::new(4));
+        assert_eq!(power.total_power(weight), Uint128::new(100));
+    }
+
+    #[test]
+    fn test_curved_absolute_count() {
+        let count = Threshold::AbsoluteCount {
+            threshold: Uint128::new(10),
+        };
+        let percent = Threshold::AbsolutePercentage {
+            percentage: crate::threshold::PercentageThreshold::Majority {},
+        };
+        let curve = VotingPowerCurve::Root { degree: 2 };
+        assert!(VotingPowerCurve::Linear {}
+            .validate_threshold(&count)
+            .is_ok());
+        assert!(curve.validate_threshold(&percent).is_ok());
+        assert_eq!(
+            curve.validate_threshold(&count),
+            Err(VotingError::CurvedAbsoluteCount {})
+        );
+    }
+
+    #[test]
+    fn test_validate_curve() {
+        assert!(VotingPowerCurve::Linear {}.validate().is_ok());
+        assert!(VotingPowerCurve::Root { degree: 2 }.validate().is_ok());
+        assert_eq!(
+            VotingPowerCurve::Root { degree: 1 }.validate(),
+            Err(VotingError::InvalidRootDegree {})
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, MessageInfo, StdResult, Uint128,
+    WasmMsg,
+};
+use cw_utils::{must_pay, nonpayable};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::VotingError;
 
 /// Information about the token to use for proposal deposits.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -15,44 +21,112 @@ pub enum DepositToken {
     /// `cw_core_macros::token_query`. Failing to implement that
     /// and using this option will cause instantiation to fail.
     VotingModuleToken {},
+    /// Use a native token as the deposit token. Deposits are paid by
+    /// sending the deposit amount along with the propose message.
+    Native { denom: String },
+}
+
+/// Counterpart to the `DepositToken` enum which has been processed.
+///
+/// This is untagged so that cw20 deposit tokens are serialized as a
+/// bare address. Deposit information stored before native deposits
+/// were supported may then be read without a migration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum CheckedDepositToken {
+    /// The address of a cw20 token.
+    Cw20(Addr),
+    /// The denomination of a native token.
+    Native { denom: String },
+}
+
+/// Determines what happens to a proposal's deposit once the proposal
+/// has been executed or closed. Deposits which are not refunded are
+/// sent to the DAO's treasury.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositRefundPolicy {
+    /// Deposits are always refunded.
+    Always {},
+    /// Deposits are only refunded for proposals that pass.
+    OnlyPassed {},
+    /// Deposits are never refunded.
+    Never {},
+    /// Proposals that pass have their deposits refunded. Proposals
+    /// that fail have `slash` percent of their deposit sent to the
+    /// DAO and the remainder refunded.
+    Slashed { slash: Decimal },
 }
 
 /// Information about the deposit required to create a proposal.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct DepositInfo {
-    /// The address of the cw20 token to be used for proposal
-    /// deposits.
+    /// The token to be used for proposal deposits.
     pub token: DepositToken,
     /// The number of tokens that must be deposited to create a
     /// proposal.
     pub deposit: Uint128,
-    /// If failed proposals should have their deposits refunded.
-    pub refund_failed_proposals: bool,
+    /// The policy used to refund deposits once a proposal has been
+    /// executed or closed.
+    pub refund_policy: DepositRefundPolicy,
 }
 
 /// Counterpart to the `DepositInfo` struct which has been processed.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct CheckedDepositInfo {
-    /// The address of the cw20 token to be used for proposal
-    /// deposits.
-    pub token: Addr,
+    /// The token to be used for proposal deposits.
+    pub token: CheckedDepositToken,
     /// The number of tokens that must be deposited to create a
     /// proposal.
     pub deposit: Uint128,
-    /// If failed proposals should have their deposits refunded.
-    pub refund_failed_proposals: bool,
+    /// The policy used to refund deposits once a proposal has been
+    /// executed or closed.
+    #[serde(
+        alias = "refund_failed_proposals",
+        deserialize_with = "deserialize_refund_policy"
+    )]
+    pub refund_policy: DepositRefundPolicy,
+}
+
+/// Deserializes a refund policy. Deposit information stored before
+/// refund policies were added has a `refund_failed_proposals` bool in
+/// place of the policy. `true` is read as `Always {}` and `false` as
+/// `OnlyPassed {}`.
+fn deserialize_refund_policy<'de, D>(deserializer: D) -> Result<DepositRefundPolicy, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PolicyOrBool {
+        Policy(DepositRefundPolicy),
+        Bool(bool),
+    }
+
+    Ok(match PolicyOrBool::deserialize(deserializer)? {
+        PolicyOrBool::Policy(policy) => policy,
+        PolicyOrBool::Bool(true) => DepositRefundPolicy::Always {},
+        PolicyOrBool::Bool(false) => DepositRefundPolicy::OnlyPassed {},
+    })
 }
 
 impl DepositInfo {
     /// Converts deposit info into checked deposit info.
-    pub fn into_checked(self, deps: Deps, dao: Addr) -> StdResult<CheckedDepositInfo> {
+    pub fn into_checked(self, deps: Deps, dao: Addr) -> Result<CheckedDepositInfo, VotingError> {
         let Self {
             token,
             deposit,
-            refund_failed_proposals,
+            refund_policy,
         } = self;
+        if let DepositRefundPolicy::Slashed { slash } = refund_policy {
+            if slash > Decimal::one() {
+                return Err(VotingError::InvalidSlash {});
+            }
+        }
         let token = match token {
-            DepositToken::Token { address } => deps.api.addr_validate(&address)?,
+            DepositToken::Token { address } => {
+                CheckedDepositToken::Cw20(deps.api.addr_validate(&address)?)
+            }
             DepositToken::VotingModuleToken {} => {
                 let voting_module: Addr = deps
                     .querier
@@ -61,65 +135,325 @@ impl DepositInfo {
                     voting_module,
                     &cw_core_interface::voting::Query::TokenContract {},
                 )?;
-                token_addr
+                CheckedDepositToken::Cw20(token_addr)
             }
+            DepositToken::Native { denom } => CheckedDepositToken::Native { denom },
         };
-        // Make an info query as a smoke test that we are indeed
-        // working with a token here. We can't turbofish this
-        // type. See <https://github.com/rust-lang/rust/issues/83701>
-        let _info: cw20::TokenInfoResponse = deps
-            .querier
-            .query_wasm_smart(token.clone(), &cw20::Cw20QueryMsg::TokenInfo {})?;
+        if let CheckedDepositToken::Cw20(token) = &token {
+            // Make an info query as a smoke test that we are indeed
+            // working with a token here. We can't turbofish this
+            // type. See <https://github.com/rust-lang/rust/issues/83701>
+            let _info: cw20::TokenInfoResponse = deps
+                .querier
+                .query_wasm_smart(token, &cw20::Cw20QueryMsg::TokenInfo {})?;
+        }
         Ok(CheckedDepositInfo {
             token,
             deposit,
-            refund_failed_proposals,
+            refund_policy,
         })
     }
 }
 
+/// Checks that a native token deposit has been paid by the funds sent
+/// with INFO. The funds must be exactly the deposit amount of the
+/// deposit denomination. Cw20 deposits are transfered by the message
+/// returned from `get_deposit_msg`, so if there is no native deposit
+/// to pay no funds may be sent.
+pub fn check_native_deposit_paid(
+    deposit_info: &Option<CheckedDepositInfo>,
+    info: &MessageInfo,
+) -> Result<(), VotingError> {
+    match deposit_info {
+        Some(CheckedDepositInfo {
+            token: CheckedDepositToken::Native { denom },
+            deposit,
+            ..
+        }) if !deposit.is_zero() => {
+            let paid = must_pay(info, denom)?;
+            if paid != *deposit {
+                return Err(VotingError::InvalidDeposit {
+                    expected: *deposit,
+                    denom: denom.clone(),
+                    actual: paid,
+                });
+            }
+        }
+        _ => nonpayable(info)?,
+    }
+    Ok(())
+}
+
 pub fn get_deposit_msg(
     info: &Option<CheckedDepositInfo>,
     contract: &Addr,
     sender: &Addr,
 ) -> StdResult<Vec<CosmosMsg>> {
     match info {
-        Some(info) => {
-            if info.deposit.is_zero() {
-                Ok(vec![])
-            } else {
-                let transfer_msg = WasmMsg::Execute {
-                    contract_addr: info.token.to_string(),
-                    funds: vec![],
-                    msg: to_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
-                        owner: sender.to_string(),
-                        recipient: contract.to_string(),
-                        amount: info.deposit,
-                    })?,
-                };
-                let transfer_msg: CosmosMsg = transfer_msg.into();
-                Ok(vec![transfer_msg])
-            }
+        Some(CheckedDepositInfo {
+            token: CheckedDepositToken::Cw20(token),
+            deposit,
+            ..
+        }) if !deposit.is_zero() => {
+            let transfer_msg = WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                funds: vec![],
+                msg: to_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                    owner: sender.to_string(),
+                    recipient: contract.to_string(),
+                    amount: *deposit,
+                })?,
+            };
+            let transfer_msg: CosmosMsg = transfer_msg.into();
+            Ok(vec![transfer_msg])
         }
-        None => Ok(vec![]),
+        // Native deposits are sent along with the propose message
+        // and checked by `check_native_deposit_paid`.
+        _ => Ok(vec![]),
     }
 }
 
-pub fn get_return_deposit_msg(
+/// Returns the messages needed to settle a proposal's deposit once
+/// the proposal has been executed or closed according to the deposit
+/// refund policy. PASSED is true if the proposal passed. Deposits that
+/// are not refunded to PROPOSER are sent to DAO.
+pub fn get_refund_deposit_msgs(
+    deposit_info: &CheckedDepositInfo,
+    proposer: &Addr,
+    dao: &Addr,
+    passed: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let refund = match (&deposit_info.refund_policy, passed) {
+        (DepositRefundPolicy::Always {}, _)
+        | (DepositRefundPolicy::OnlyPassed {}, true)
+        | (DepositRefundPolicy::Slashed { .. }, true) => deposit_info.deposit,
+        (DepositRefundPolicy::OnlyPassed {}, false) | (DepositRefundPolicy::Never {}, _) => {
+            Uint128::zero()
+        }
+        (DepositRefundPolicy::Slashed { slash }, false) => {
+            deposit_info.deposit - deposit_info.deposit * *slash
+        }
+    };
+    let mut msgs = get_transfer_msgs(&deposit_info.token, refund, proposer)?;
+    msgs.extend(get_transfer_msgs(
+        &deposit_info.token,
+        deposit_info.deposit - refund,
+        dao,
+    )?);
+    Ok(msgs)
+}
+
+/// Returns the messages needed to return a proposal's full deposit
+/// to RECEIVER regardless of the deposit refund policy.
+pub fn get_return_deposit_msgs(
     deposit_info: &CheckedDepositInfo,
     receiver: &Addr,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if deposit_info.deposit.is_zero() {
+    get_transfer_msgs(&deposit_info.token, deposit_info.deposit, receiver)
+}
+
+fn get_transfer_msgs(
+    token: &CheckedDepositToken,
+    amount: Uint128,
+    receiver: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    if amount.is_zero() {
         return Ok(vec![]);
     }
-    let transfer_msg = WasmMsg::Execute {
-        contract_addr: deposit_info.token.to_string(),
-        funds: vec![],
-        msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
-            recipient: receiver.to_string(),
-            amount: deposit_info.deposit,
-        })?,
+    let transfer_msg: CosmosMsg = match token {
+        CheckedDepositToken::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: receiver.to_string(),
+                amount,
+            })?,
+        }
+        .into(),
+        CheckedDepositToken::Native { denom } => BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
     };
-    let transfer_msg: CosmosMsg = transfer_msg.into();
     Ok(vec![transfer_msg])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coins, from_slice, testing::mock_info};
+    use cw_utils::PaymentError;
+
+    #[test]
+    fn test_cw20_deposit_token_serialization() {
+        // Deposit info stored before native deposits were supported
+        // has a bare address as its token.
+        let info: CheckedDepositInfo =
+            from_slice(br#"{"token":"juno1token","deposit":"1","refund_failed_proposals":true}"#)
+                .unwrap();
+        assert_eq!(
+            info.token,
+            CheckedDepositToken::Cw20(Addr::unchecked("juno1token"))
+        );
+
+        let info: CheckedDepositInfo = from_slice(
+            br#"{"token":{"denom":"ujuno"},"deposit":"1","refund_failed_proposals":true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            info.token,
+            CheckedDepositToken::Native {
+                denom: "ujuno".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_native_deposit_paid() {
+        let native_deposit = CheckedDepositInfo {
+            token: CheckedDepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always {},
+        };
+        let deposit_info = Some(native_deposit.clone());
+
+        check_native_deposit_paid(&deposit_info, &mock_info("ekez", &coins(10, "ujuno"))).unwrap();
+        assert_eq!(
+            check_native_deposit_paid(&deposit_info, &mock_info("ekez", &[])),
+            Err(VotingError::Payment(PaymentError::NoFunds {}))
+        );
+        assert_eq!(
+            check_native_deposit_paid(&deposit_info, &mock_info("ekez", &coins(9, "ujuno"))),
+            Err(VotingError::InvalidDeposit {
+                expected: Uint128::new(10),
+                denom: "ujuno".to_string(),
+                actual: Uint128::new(9),
+            })
+        );
+        assert_eq!(
+            check_native_deposit_paid(&deposit_info, &mock_info("ekez", &coins(10, "uatom"))),
+            Err(VotingError::Payment(PaymentError::MissingDenom(
+                "ujuno".to_string()
+            )))
+        );
+        let mut funds = coins(10, "ujuno");
+        funds.extend(coins(1, "uatom"));
+        assert_eq!(
+            check_native_deposit_paid(&deposit_info, &mock_info("ekez", &funds)),
+            Err(VotingError::Payment(PaymentError::MultipleDenoms {}))
+        );
+
+        // Funds may not be sent if there is no native deposit to pay.
+        let zero_deposit = Some(CheckedDepositInfo {
+            deposit: Uint128::zero(),
+            ..native_deposit
+        });
+        check_native_deposit_paid(&zero_deposit, &mock_info("ekez", &[])).unwrap();
+        assert_eq!(
+            check_native_deposit_paid(&zero_deposit, &mock_info("ekez", &coins(10, "ujuno"))),
+            Err(VotingError::Payment(PaymentError::NonPayable {}))
+        );
+        assert_eq!(
+            check_native_deposit_paid(&None, &mock_info("ekez", &coins(10, "ujuno"))),
+            Err(VotingError::Payment(PaymentError::NonPayable {}))
+        );
+
+        // Cw20 deposits are not paid with native tokens.
+        let deposit_info = Some(CheckedDepositInfo {
+            token: CheckedDepositToken::Cw20(Addr::unchecked("juno1token")),
+            deposit: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always {},
+        });
+        check_native_deposit_paid(&deposit_info, &mock_info("ekez", &[])).unwrap();
+        assert_eq!(
+            check_native_deposit_paid(&deposit_info, &mock_info("ekez", &coins(10, "ujuno"))),
+            Err(VotingError::Payment(PaymentError::NonPayable {}))
+        );
+    }
+
+    #[test]
+    fn test_refund_policy_serialization() {
+        // Deposit info stored before refund policies were added has a
+        // `refund_failed_proposals` bool.
+        let info: CheckedDepositInfo =
+            from_slice(br#"{"token":"juno1token","deposit":"1","refund_failed_proposals":true}"#)
+                .unwrap();
+        assert_eq!(info.refund_policy, DepositRefundPolicy::Always {});
+        let info: CheckedDepositInfo =
+            from_slice(br#"{"token":"juno1token","deposit":"1","refund_failed_proposals":false}"#)
+                .unwrap();
+        assert_eq!(info.refund_policy, DepositRefundPolicy::OnlyPassed {});
+
+        let info: CheckedDepositInfo = from_slice(
+            br#"{"token":"juno1token","deposit":"1","refund_policy":{"slashed":{"slash":"0.5"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            info.refund_policy,
+            DepositRefundPolicy::Slashed {
+                slash: Decimal::percent(50)
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_refund_deposit_msgs() {
+        let proposer = Addr::unchecked("ekez");
+        let dao = Addr::unchecked("dao");
+        let deposit_info = |refund_policy| CheckedDepositInfo {
+            token: CheckedDepositToken::Native {
+                denom: "ujuno".to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_policy,
+        };
+        let send = |to_address: &Addr, amount| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: to_address.to_string(),
+                amount: coins(amount, "ujuno"),
+            }
+            .into()
+        };
+
+        let info = deposit_info(DepositRefundPolicy::Always {});
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, false).unwrap(),
+            vec![send(&proposer, 10)]
+        );
+
+        let info = deposit_info(DepositRefundPolicy::OnlyPassed {});
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, true).unwrap(),
+            vec![send(&proposer, 10)]
+        );
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, false).unwrap(),
+            vec![send(&dao, 10)]
+        );
+
+        let info = deposit_info(DepositRefundPolicy::Never {});
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, true).unwrap(),
+            vec![send(&dao, 10)]
+        );
+
+        let info = deposit_info(DepositRefundPolicy::Slashed {
+            slash: Decimal::percent(25),
+        });
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, true).unwrap(),
+            vec![send(&proposer, 10)]
+        );
+        // Slashed amounts are rounded down.
+        assert_eq!(
+            get_refund_deposit_msgs(&info, &proposer, &dao, false).unwrap(),
+            vec![send(&proposer, 8), send(&dao, 2)]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -11,4 +12,29 @@ pub enum VotingError {
 
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
+
+    #[error("timelock and max_voting_period must have the same units (height or time)")]
+    TimelockUnitsConflict {},
+
+    #[error("Voting power curve roots must have a degree of at least two")]
+    InvalidRootDegree {},
+
+    #[error("Absolute count thresholds may not be used with a non-linear voting power curve")]
+    CurvedAbsoluteCount {},
+
+    #[error("Deposit slash percentage must be between 0 and 1")]
+    InvalidSlash {},
+
+    #[error("Vote rationale is ({size}) bytes, must be <= ({max}) bytes")]
+    RationaleTooLong { size: u64, max: u64 },
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Invalid native deposit. Expected ({expected}{denom}), got ({actual}{denom})")]
+    InvalidDeposit {
+        expected: Uint128,
+        denom: String,
+        actual: Uint128,
+    },
 }
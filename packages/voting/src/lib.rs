@@ -1,7 +1,10 @@
+pub mod curve;
 pub mod deposit;
 pub mod error;
 pub mod proposal;
 pub mod reply;
 pub mod status;
 pub mod threshold;
+pub mod timelock;
+pub mod veto;
 pub mod voting;
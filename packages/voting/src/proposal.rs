@@ -1,13 +1,54 @@
 use cosmwasm_std::Addr;
 
-use crate::{deposit::CheckedDepositInfo, status::Status};
+use crate::{deposit::CheckedDepositInfo, error::VotingError, status::Status};
 
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
 pub const MAX_PROPOSAL_SIZE: u64 = 30_000;
+/// Maximum size, in bytes, of the rationale a voter may attach to
+/// their vote.
+pub const MAX_RATIONALE_LENGTH: u64 = 1_024;
 
 pub trait Proposal {
     fn proposer(&self) -> Addr;
     fn deposit_info(&self) -> Option<CheckedDepositInfo>;
     fn status(&self) -> Status;
 }
+
+/// Validates that a vote's rationale is no longer than
+/// `MAX_RATIONALE_LENGTH` bytes. Passes the rationale through the
+/// function.
+pub fn validate_rationale(rationale: Option<String>) -> Result<Option<String>, VotingError> {
+    match rationale {
+        Some(rationale) if rationale.len() as u64 > MAX_RATIONALE_LENGTH => {
+            Err(VotingError::RationaleTooLong {
+                size: rationale.len() as u64,
+                max: MAX_RATIONALE_LENGTH,
+            })
+        }
+        rationale => Ok(rationale),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rationale() {
+        assert_eq!(validate_rationale(None), Ok(None));
+        assert_eq!(
+            validate_rationale(Some("good proposal".to_string())),
+            Ok(Some("good proposal".to_string()))
+        );
+        let max = "a".repeat(MAX_RATIONALE_LENGTH as usize);
+        assert_eq!(validate_rationale(Some(max.clone())), Ok(Some(max)));
+        assert_eq!(
+            validate_rationale(Some("a".repeat(MAX_RATIONALE_LENGTH as usize + 1))),
+            Err(VotingError::RationaleTooLong {
+                size: MAX_RATIONALE_LENGTH + 1,
+                max: MAX_RATIONALE_LENGTH
+            })
+        );
+    }
+}
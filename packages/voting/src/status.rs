@@ -18,6 +18,12 @@ pub enum Status {
     Closed,
     // The proposal has failed during execution
     ExecutionFailed,
+    /// The proposal was vetoed during its timelock. A proposal
+    /// deposit refund has been issued if applicable.
+    Vetoed,
+    /// The proposal was withdrawn by its proposer before it was voted
+    /// on. A proposal deposit refund has been issued if applicable.
+    Withdrawn,
 }
 
 impl std::fmt::Display for Status {
@@ -29,6 +35,8 @@ impl std::fmt::Display for Status {
             Status::Executed => write!(f, "executed"),
             Status::Closed => write!(f, "closed"),
             Status::ExecutionFailed => write!(f, "execution_failed"),
+            Status::Vetoed => write!(f, "vetoed"),
+            Status::Withdrawn => write!(f, "withdrawn"),
         }
     }
 }
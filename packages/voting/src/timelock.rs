@@ -0,0 +1,152 @@
+use cosmwasm_std::{BlockInfo, StdResult};
+use cw_utils::{Duration, Expiration};
+
+use crate::error::VotingError;
+
+/// Validates that a proposal module's timelock has the same units as
+/// its max voting period. Timelocks begin when a proposal passes,
+/// which is measured in the units of its voting period, so the two
+/// must be added together. Passes the timelock through the function.
+pub fn validate_timelock(
+    timelock: Option<Duration>,
+    max_voting_period: Duration,
+) -> Result<Option<Duration>, VotingError> {
+    match (timelock, max_voting_period) {
+        (None, _)
+        | (Some(Duration::Height(_)), Duration::Height(_))
+        | (Some(Duration::Time(_)), Duration::Time(_)) => Ok(timelock),
+        _ => Err(VotingError::TimelockUnitsConflict {}),
+    }
+}
+
+/// Gets the amount of time remaining until EXPIRATION. None if
+/// EXPIRATION has passed or will never be reached.
+pub fn remaining(expiration: Expiration, block: &BlockInfo) -> Option<Duration> {
+    if expiration.is_expired(block) {
+        return None;
+    }
+    match expiration {
+        Expiration::AtHeight(height) => Some(Duration::Height(height - block.height)),
+        Expiration::AtTime(time) => Some(Duration::Time(time.seconds() - block.time.seconds())),
+        Expiration::Never {} => None,
+    }
+}
+
+/// Returns the point in time of BLOCK in the same units as UNITS,
+/// that is BLOCK's height if UNITS is a height and its time
+/// otherwise.
+pub fn block_expiration(units: &Expiration, block: &BlockInfo) -> Expiration {
+    match units {
+        Expiration::AtHeight(_) => Expiration::AtHeight(block.height),
+        _ => Expiration::AtTime(block.time),
+    }
+}
+
+/// Finds the point at which a proposal passed given that it has
+/// passed as of BLOCK and that its votes have not changed since its
+/// status was last updated.
+///
+/// With its votes fixed a proposal's status only changes once its
+/// min voting period ends or once it expires. The proposal passed at
+/// the first of those points that BLOCK has reached at which
+/// IS_PASSED holds, and at BLOCK if there is none. The returned
+/// point has the same units as EXPIRATION.
+pub fn passed_at<F>(
+    min_voting_period: Option<Expiration>,
+    expiration: Expiration,
+    block: &BlockInfo,
+    is_passed: F,
+) -> StdResult<Expiration>
+where
+    F: Fn(&BlockInfo) -> StdResult<bool>,
+{
+    for point in min_voting_period.into_iter().chain(Some(expiration)) {
+        if !point.is_expired(block) {
+            continue;
+        }
+        let at = match point {
+            Expiration::AtHeight(height) => BlockInfo {
+                height,
+                ..block.clone()
+            },
+            Expiration::AtTime(time) => BlockInfo {
+                time,
+                ..block.clone()
+            },
+            Expiration::Never {} => continue,
+        };
+        if is_passed(&at)? {
+            return Ok(point);
+        }
+    }
+    Ok(block_expiration(&expiration, block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn test_validate_timelock() {
+        assert_eq!(
+            validate_timelock(Some(Duration::Height(10)), Duration::Height(100)),
+            Ok(Some(Duration::Height(10)))
+        );
+        assert_eq!(validate_timelock(None, Duration::Time(100)), Ok(None));
+        assert_eq!(
+            validate_timelock(Some(Duration::Height(10)), Duration::Time(100)),
+            Err(VotingError::TimelockUnitsConflict {})
+        );
+    }
+
+    #[test]
+    fn test_passed_at() {
+        let block = mock_env().block;
+        let min = Expiration::AtHeight(block.height - 10);
+        let expiration = Expiration::AtHeight(block.height - 5);
+
+        // Passed once the min voting period ended.
+        assert_eq!(
+            passed_at(Some(min), expiration, &block, |_| Ok(true)),
+            Ok(min)
+        );
+        // Passed once voting ended.
+        assert_eq!(
+            passed_at(Some(min), expiration, &block, |at| Ok(
+                at.height >= block.height - 5
+            )),
+            Ok(expiration)
+        );
+        // Passed now, for example after a vote.
+        let expiration = Expiration::AtHeight(block.height + 5);
+        assert_eq!(
+            passed_at(None, expiration, &block, |_| Ok(true)),
+            Ok(Expiration::AtHeight(block.height))
+        );
+        assert_eq!(
+            passed_at(
+                None,
+                Expiration::AtTime(block.time.plus_seconds(5)),
+                &block,
+                |_| Ok(true)
+            ),
+            Ok(Expiration::AtTime(block.time))
+        );
+    }
+
+    #[test]
+    fn test_remaining() {
+        let block = mock_env().block;
+        assert_eq!(
+            remaining(Expiration::AtHeight(block.height + 5), &block),
+            Some(Duration::Height(5))
+        );
+        assert_eq!(
+            remaining(Expiration::AtTime(block.time.plus_seconds(60)), &block),
+            Some(Duration::Time(60))
+        );
+        assert_eq!(remaining(Expiration::AtHeight(block.height), &block), None);
+        assert_eq!(remaining(Expiration::Never {}, &block), None);
+    }
+}
@@ -0,0 +1,53 @@
+use cosmwasm_std::{Addr, Deps, StdResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about who may veto passed proposals during their
+/// timelock.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Vetoer {
+    /// A specific address may veto proposals.
+    Address { address: String },
+    /// The parent DAO of the DAO this proposal module belongs to may
+    /// veto proposals. A DAO's parent is its cw-core admin. The admin
+    /// is looked up when a veto is attempted so a change of parent
+    /// DAO takes effect immediately. If the DAO is its own admin it
+    /// has no parent and no one may veto proposals.
+    ParentDao {},
+}
+
+/// Counterpart to the `Vetoer` enum which has been processed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckedVetoer {
+    Address { address: Addr },
+    ParentDao {},
+}
+
+impl Vetoer {
+    /// Converts a vetoer into a checked vetoer.
+    pub fn into_checked(self, deps: Deps) -> StdResult<CheckedVetoer> {
+        Ok(match self {
+            Vetoer::Address { address } => CheckedVetoer::Address {
+                address: deps.api.addr_validate(&address)?,
+            },
+            Vetoer::ParentDao {} => CheckedVetoer::ParentDao {},
+        })
+    }
+}
+
+impl CheckedVetoer {
+    /// Returns true if SENDER may veto proposals on behalf of DAO.
+    pub fn is_vetoer(&self, deps: Deps, dao: &Addr, sender: &Addr) -> StdResult<bool> {
+        match self {
+            CheckedVetoer::Address { address } => Ok(address == sender),
+            CheckedVetoer::ParentDao {} => {
+                let admin: Addr = deps
+                    .querier
+                    .query_wasm_smart(dao, &cw_core::msg::QueryMsg::Admin {})?;
+                Ok(admin != *dao && admin == *sender)
+            }
+        }
+    }
+}
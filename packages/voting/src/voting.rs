@@ -30,48 +30,138 @@ pub enum Vote {
     Abstain,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct MultipleChoiceVote {
     // A vote indicates which option the user has selected.
     pub option_id: u32,
+    /// Additional options selected by the voter. On ranked choice
+    /// proposals these are the options the voter would like to see win
+    /// should `option_id` be eliminated, from most to least
+    /// preferred. On approval proposals these are the other options
+    /// the voter approves of. Must be empty for other voting
+    /// strategies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rankings: Vec<u32>,
+}
+
+impl MultipleChoiceVote {
+    /// Returns every option selected by this vote in order of
+    /// preference, starting with `option_id`.
+    pub fn preferences(&self) -> Vec<u32> {
+        std::iter::once(self.option_id)
+            .chain(self.rankings.iter().copied())
+            .collect()
+    }
 }
 
 impl std::fmt::Display for MultipleChoiceVote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.option_id)
+        write!(f, "{}", self.option_id)?;
+        for option_id in self.rankings.iter() {
+            write!(f, ",{}", option_id)?;
+        }
+        Ok(())
     }
 }
 
+/// The total voting power behind a particular ordering of options on
+/// a ranked choice proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RankedBallots {
+    /// Option IDs from most to least preferred.
+    pub preferences: Vec<u32>,
+    /// The sum of the voting power of all ballots with this ordering.
+    pub weight: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct MultipleChoiceVotes {
     // Vote counts is a vector of integers indicating the vote weight for each option
     // (the index corresponds to the option).
     pub vote_weights: Vec<Uint128>,
+    /// The total voting power behind all votes cast. Only tracked for
+    /// approval proposals, where a vote's power counts towards every
+    /// option it approves and `vote_weights` may sum to more than the
+    /// power that has been cast.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_cast: Option<Uint128>,
 }
 
 impl MultipleChoiceVotes {
-    /// Sum of all vote weights
+    /// The total voting power that has been cast. This is the sum of
+    /// all vote weights unless votes may select more than one option.
     pub fn total(&self) -> Uint128 {
-        self.vote_weights.iter().sum()
+        self.power_cast
+            .unwrap_or_else(|| self.vote_weights.iter().sum())
     }
 
-    pub fn add_vote(&mut self, vote: MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
+    pub fn add_vote(&mut self, vote: &MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
         self.vote_weights[vote.option_id as usize] = self.vote_weights[vote.option_id as usize]
             .checked_add(weight)
             .map_err(StdError::overflow)?;
         Ok(())
     }
 
-    pub fn remove_vote(&mut self, vote: MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
+    pub fn remove_vote(&mut self, vote: &MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
         self.vote_weights[vote.option_id as usize] = self.vote_weights[vote.option_id as usize]
             .checked_sub(weight)
             .map_err(StdError::overflow)?;
         Ok(())
     }
 
+    /// Adds an approval vote. The full weight of the vote is added to
+    /// every option it approves of.
+    pub fn add_approval_vote(
+        &mut self,
+        vote: &MultipleChoiceVote,
+        weight: Uint128,
+    ) -> StdResult<()> {
+        let power_cast = self
+            .total()
+            .checked_add(weight)
+            .map_err(StdError::overflow)?;
+        for option_id in vote.preferences() {
+            self.add_vote(
+                &MultipleChoiceVote {
+                    option_id,
+                    rankings: vec![],
+                },
+                weight,
+            )?;
+        }
+        self.power_cast = Some(power_cast);
+        Ok(())
+    }
+
+    /// Removes an approval vote previously added with
+    /// `add_approval_vote`, taking its weight away from every option
+    /// it approved of.
+    pub fn remove_approval_vote(
+        &mut self,
+        vote: &MultipleChoiceVote,
+        weight: Uint128,
+    ) -> StdResult<()> {
+        let power_cast = self
+            .total()
+            .checked_sub(weight)
+            .map_err(StdError::overflow)?;
+        for option_id in vote.preferences() {
+            self.remove_vote(
+                &MultipleChoiceVote {
+                    option_id,
+                    rankings: vec![],
+                },
+                weight,
+            )?;
+        }
+        self.power_cast = Some(power_cast);
+        Ok(())
+    }
+
     pub fn zero(num_choices: usize) -> Self {
         Self {
             vote_weights: vec![Uint128::zero(); num_choices],
+            power_cast: None,
         }
     }
 }
@@ -302,6 +392,52 @@ mod test {
         assert_eq!(votes.abstain, Uint128::new(40));
     }
 
+    #[test]
+    fn ranked_vote_preferences() {
+        let ranked = MultipleChoiceVote {
+            option_id: 1,
+            rankings: vec![0, 2],
+        };
+        assert_eq!(ranked.preferences(), vec![1, 0, 2]);
+        assert_eq!(ranked.to_string(), "1,0,2");
+    }
+
+    #[test]
+    fn count_approval_votes() {
+        let mut votes = MultipleChoiceVotes::zero(3);
+        let approve_both = MultipleChoiceVote {
+            option_id: 0,
+            rankings: vec![1],
+        };
+        votes
+            .add_approval_vote(&approve_both, Uint128::new(10))
+            .unwrap();
+        votes
+            .add_approval_vote(
+                &MultipleChoiceVote {
+                    option_id: 1,
+                    rankings: vec![],
+                },
+                Uint128::new(4),
+            )
+            .unwrap();
+
+        assert_eq!(votes.total(), Uint128::new(14));
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(10), Uint128::new(14), Uint128::zero()]
+        );
+
+        votes
+            .remove_approval_vote(&approve_both, Uint128::new(10))
+            .unwrap();
+        assert_eq!(votes.total(), Uint128::new(4));
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::zero(), Uint128::new(4), Uint128::zero()]
+        );
+    }
+
     #[test]
     fn vote_comparisons() {
         assert!(!compare_vote_count(